@@ -1,13 +1,11 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use ndarray::Array;
 use ndarray::Array4;
 use ndarray_rand::rand_distr::Uniform;
 use ndarray_rand::RandomExt;
 
-use vtk::array::Components;
 use vtk::Array as _;
 
-fn write_binary(n: usize) -> () {
+fn write_binary(n: usize) {
     let array: Array4<f32> = ndarray::Array::random((3, n, n, n), Uniform::new(0., 10.));
 
     let container = vtk::Vector3D::new(array);
@@ -16,7 +14,9 @@ fn write_binary(n: usize) -> () {
     let buf_writer = std::io::BufWriter::new(writer);
     let mut event_writer = vtk::EventWriter::new(buf_writer);
 
-    container.write_binary(&mut event_writer, false).unwrap();
+    container
+        .write_binary(&mut event_writer, vtk::ByteOrder::LittleEndian)
+        .unwrap();
 }
 
 fn write_binary_bench(c: &mut Criterion) {
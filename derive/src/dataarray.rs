@@ -62,51 +62,71 @@ struct MyFieldReceiver {
     /// This magic field name pulls the type from the input.
     #[allow(dead_code)]
     ty: syn::Type,
+
+    /// `#[vtk(cell)]` places the array in the `<CellData>` section instead of `<PointData>`
+    #[darling(default)]
+    cell: bool,
+
+    /// `#[vtk(skip)]` fields are not written
+    #[darling(default)]
+    skip: bool,
+
+    /// `#[vtk(lazy)]` fields are only read on demand when parsing, and are not written
+    #[darling(default)]
+    lazy: bool,
 }
 
 fn appended_encoding_body(fields: Vec<&MyFieldReceiver>) -> Result<proc_macro2::TokenStream> {
 
     let mut array_headers = quote!();
+    let mut cell_array_headers = quote!();
     let mut appended_body = quote!();
+    let mut appended_sizes = quote!();
 
-    for field in &fields {
+    for (idx, field) in fields.iter().enumerate() {
         // convert the field identifier to a string literal
         // so `write_dataarray` understands it
         let field_name = &field.ident.as_ref().unwrap();
         let lit = syn::LitStr::new(&field_name.to_string(), proc_macro2::Span::call_site());
 
-        array_headers = quote! {
-            #array_headers
-
+        // every array is written to the appended section in the order of the fields, so
+        // the offset of each array is found from the position of its field
+        let header = quote! {
             let ref_field = &self.#field_name;
             let comps = vtk::Array::components(ref_field);
 
             let prec = vtk::Array::precision(ref_field);
 
-            vtk::write_appended_dataarray_header(writer, #lit, offset, comps, prec)?;
-            let size = vtk::Array::size_of_elem(ref_field);
-            offset += (size * self.#field_name.len()) as i64;
-        }
-    }
+            vtk::write_appended_dataarray_header(writer, #lit, offsets[#idx], comps, prec)?;
+        };
 
-    for (idx, field) in fields.iter().enumerate() {
-        // convert the field identifier to a string literal
-        // so `write_dataarray` understands it
-        let field_name = &field.ident.as_ref().unwrap();
+        if field.cell {
+            cell_array_headers = quote! {
+                #cell_array_headers
 
-        // check to see if this is the last iteration of the loop
-        let new_write = if idx == fields.len() -1 {
-            // there are no more arrays to write, we are last
-            quote!(vtk::Array::write_binary(&self.#field_name, writer, true)?;)
+                #header
+            };
         } else {
-            // if we have another array to write then we are not the last
-            quote!(vtk::Array::write_binary(&self.#field_name, writer, false)?;)
+            array_headers = quote! {
+                #array_headers
+
+                #header
+            };
+        }
+
+        appended_sizes = quote! {
+            #appended_sizes
+            vtk::Array::size_of_elem(&self.#field_name) * vtk::Array::length(&self.#field_name),
         };
+    }
+
+    for field in &fields {
+        let field_name = &field.ident.as_ref().unwrap();
 
         appended_body = quote! {
             #appended_body
 
-            #new_write
+            vtk::Array::write_binary(&self.#field_name, writer, byte_order)?;
         }
     }
 
@@ -115,19 +135,35 @@ fn appended_encoding_body(fields: Vec<&MyFieldReceiver>) -> Result<proc_macro2::
         Ok(())
     );
 
+    cell_array_headers = quote!(
+        #cell_array_headers
+        Ok(())
+    );
+
     appended_body = quote!(
         #appended_body
         Ok(())
     );
 
+    let appended_sizes = quote!(
+        fn appended_sizes(&self) -> Vec<usize> {
+            vec![#appended_sizes]
+        }
+    );
+
     Ok(assemble_trait(
         array_headers,
+        cell_array_headers,
         appended_body,
+        appended_sizes,
+        array_declarations(&fields),
+        has_cell_arrays(&fields),
     ))
 }
 
 fn inline_encoding(fields: Vec<&MyFieldReceiver>, encoding: Encoding) -> Result<proc_macro2::TokenStream> {
     let mut array_headers = quote!();
+    let mut cell_array_headers = quote!();
     let appended_body = quote!(Ok(()));
 
     let vtk_encoding = match encoding {
@@ -142,10 +178,22 @@ fn inline_encoding(fields: Vec<&MyFieldReceiver>, encoding: Encoding) -> Result<
         let field_name = &field.ident.as_ref().unwrap();
         let lit = syn::LitStr::new(&field_name.to_string(), proc_macro2::Span::call_site());
 
-        array_headers = quote! {
-            #array_headers
+        let write = quote! {
+            vtk::write_inline_dataarray(writer, &self.#field_name, #lit, #vtk_encoding, byte_order)?;
+        };
+
+        if field.cell {
+            cell_array_headers = quote! {
+                #cell_array_headers
+
+                #write
+            }
+        } else {
+            array_headers = quote! {
+                #array_headers
 
-            vtk::write_inline_dataarray(writer, &self.#field_name, #lit, #vtk_encoding)?;
+                #write
+            }
         }
     }
 
@@ -154,30 +202,92 @@ fn inline_encoding(fields: Vec<&MyFieldReceiver>, encoding: Encoding) -> Result<
         Ok(())
     );
 
+    cell_array_headers = quote!(
+        #cell_array_headers
+        Ok(())
+    );
+
     Ok(assemble_trait(
         array_headers,
+        cell_array_headers,
         appended_body,
+        quote!(),
+        array_declarations(&fields),
+        has_cell_arrays(&fields),
     ))
 }
 
+fn has_cell_arrays(fields: &[&MyFieldReceiver]) -> bool {
+    fields.iter().any(|field| field.cell)
+}
+
+/// builds the body of `DataArray::array_declarations`
+fn array_declarations(fields: &[&MyFieldReceiver]) -> proc_macro2::TokenStream {
+    let mut declarations = quote!();
+
+    for field in fields {
+        let field_name = &field.ident.as_ref().unwrap();
+        let lit = syn::LitStr::new(&field_name.to_string(), proc_macro2::Span::call_site());
+        let cell = field.cell;
+
+        declarations = quote! {
+            #declarations
+            vtk::ArrayDeclaration::new(
+                #lit,
+                vtk::Array::precision(&self.#field_name),
+                vtk::Array::components(&self.#field_name),
+                #cell,
+            ),
+        };
+    }
+
+    quote!(
+        fn array_declarations(&self) -> Vec<vtk::ArrayDeclaration> {
+            vec![#declarations]
+        }
+    )
+}
+
 fn assemble_trait(
     array_headers: proc_macro2::TokenStream,
+    cell_array_headers: proc_macro2::TokenStream,
     appended_arrays: proc_macro2::TokenStream,
+    appended_sizes: proc_macro2::TokenStream,
+    declarations: proc_macro2::TokenStream,
+    has_cell_arrays: bool,
 ) -> proc_macro2::TokenStream {
     quote!(
+        #[allow(unused_variables)]
         fn write_array_header<W: std::io::Write>(
             &self,
             writer: &mut vtk::EventWriter<W>,
-            mut offset: i64
+            offsets: &[i64],
+            byte_order: vtk::ByteOrder
         ) -> Result<(), vtk::Error> {
             #array_headers
         }
+        #[allow(unused_variables)]
+        fn write_cell_array_header<W: std::io::Write>(
+            &self,
+            writer: &mut vtk::EventWriter<W>,
+            offsets: &[i64],
+            byte_order: vtk::ByteOrder
+        ) -> Result<(), vtk::Error> {
+            #cell_array_headers
+        }
+        fn has_cell_arrays(&self) -> bool {
+            #has_cell_arrays
+        }
+        #declarations
+        #[allow(unused_variables)]
         fn write_array_appended<W: std::io::Write>(
             &self,
             writer: &mut vtk::EventWriter<W>,
+            byte_order: vtk::ByteOrder,
         ) -> Result<(), vtk::Error> {
             #appended_arrays
         }
+        #appended_sizes
     )
 }
 
@@ -196,7 +306,10 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream> {
         .as_ref()
         .take_struct()
         .expect("Should never be enum")
-        .fields;
+        .fields
+        .into_iter()
+        .filter(|field| !field.skip && !field.lazy)
+        .collect();
 
     let trait_body = 
         match encoding {
@@ -4,7 +4,7 @@ mod parse_dataarray;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(DataArray, attributes(vtk_write))]
+#[proc_macro_derive(DataArray, attributes(vtk_write, vtk))]
 pub fn derive_dataarray(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = parse_macro_input!(input as DeriveInput);
@@ -15,7 +15,7 @@ pub fn derive_dataarray(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(ParseArray, attributes(vtk_parse))]
+#[proc_macro_derive(ParseArray, attributes(vtk_parse, vtk))]
 pub fn derive_parse_dataarray(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = parse_macro_input!(input as DeriveInput);
@@ -25,7 +25,7 @@ struct InputReceiver{
 }
 
 #[derive(Debug, FromField)]
-#[darling(attributes(vtk_parse))]
+#[darling(attributes(vtk_parse, vtk))]
 struct FieldReceiver {
     /// Get the ident of the field. For fields in tuple or newtype structs or
     /// enum bodies, this can be `None`.
@@ -34,6 +34,18 @@ struct FieldReceiver {
     /// This magic field name pulls the type from the input.
     #[allow(dead_code)]
     ty: syn::Type,
+
+    /// `#[vtk(cell)]` reads the array from the `<CellData>` section instead of `<PointData>`
+    #[darling(default)]
+    cell: bool,
+
+    /// `#[vtk(skip)]` does not read the array, the field is always `Default::default()`
+    #[darling(default)]
+    skip: bool,
+
+    /// `#[vtk(lazy)]` only reads the attributes of the array into a `vtk::LazyArray`
+    #[darling(default)]
+    lazy: bool,
 }
 
 #[derive(Debug)]
@@ -41,7 +53,11 @@ struct ValidatedField {
     ident: syn::Ident,
 
     #[allow(dead_code)]
-    ty: syn::Type
+    ty: syn::Type,
+
+    cell: bool,
+
+    lazy: bool,
 }
 
 struct Visitor {
@@ -49,14 +65,14 @@ struct Visitor {
     tokens: proc_macro2::TokenStream
 }
 
-fn create_visitor(original_struct: &syn::Ident, fields: &[ValidatedField], span_type: &syn::Path) -> Visitor {
+fn create_visitor(original_struct: &syn::Ident, fields: &[ValidatedField], skipped: &[syn::Ident], span_type: &syn::Path) -> Visitor {
     // first find out what we are naming the struct
     let mut visitor_name = original_struct.to_string();
     visitor_name.push_str("Visitor");
     let ident = syn::Ident::new(&visitor_name, original_struct.span());
 
     
-    let trait_impl = create_visitor_trait_impl(&ident, original_struct, fields, span_type);
+    let trait_impl = create_visitor_trait_impl(&ident, original_struct, fields, skipped, span_type);
     let struct_def = create_visitor_struct_definition(&ident, fields);
     let tokens = quote!(
         #struct_def
@@ -72,9 +88,15 @@ fn create_visitor_struct_definition(visitor_name: &syn::Ident, fields: &[Validat
     for field in fields {
         let field_name = &field.ident;
 
+        let field_type = if field.lazy {
+            quote!(vtk::LazyArray)
+        } else {
+            quote!(vtk::parse::PartialDataArrayBuffered)
+        };
+
         out = quote!(
             #out
-            #field_name: vtk::parse::PartialDataArrayBuffered,
+            #field_name: #field_type,
         );
     }
 
@@ -86,20 +108,31 @@ fn create_visitor_struct_definition(visitor_name: &syn::Ident, fields: &[Validat
     )
 }
 
-fn create_visitor_trait_impl(visitor_name: &syn::Ident, original_name: &syn::Ident, fields: &[ValidatedField], span_type: &syn::Path) -> proc_macro2::TokenStream {
+fn create_visitor_trait_impl(visitor_name: &syn::Ident, original_name: &syn::Ident, fields: &[ValidatedField], skipped: &[syn::Ident], span_type: &syn::Path) -> proc_macro2::TokenStream {
     let read_headers = visitor_read_headers(visitor_name, fields);
+    let read_piece_headers = visitor_read_piece_headers(span_type, fields, &read_headers);
+
+    // lazy arrays are located from the start of `buffer` when the file is not known
+    let file_start = if fields.iter().any(|field| field.lazy) {
+        quote!(let file = buffer;)
+    } else {
+        quote!()
+    };
     let append_to_buffer = visitor_buffer_append(fields);
-    let finish = visitor_finish(original_name, fields);
+    let finish = visitor_finish(original_name, fields, skipped);
 
  
     let out = quote!(
         impl vtk::Visitor<#span_type> for #visitor_name {
             type Output = #original_name;
 
-            fn read_headers<'a>(spans: &#span_type, buffer: &'a [u8]) -> vtk::nom::IResult<&'a [u8], Self> {
+            fn read_headers<'a>(spans: &#span_type, buffer: &'a [u8]) -> vtk::parse::IResult<'a, Self> {
+                #file_start
                 #read_headers
             }
 
+            #read_piece_headers
+
             fn add_to_appended_reader<'a, 'b>(
                 &'a self,
                 buffer: &'b mut Vec<std::cell::RefMut<'a, vtk::parse::OffsetBuffer>>,
@@ -116,23 +149,70 @@ fn create_visitor_trait_impl(visitor_name: &syn::Ident, original_name: &syn::Ide
     out
 }
 
+/// builds `Visitor::read_piece_headers` for visitors with lazy fields, which locate their arrays
+/// from the start of the file instead of the start of the piece
+fn visitor_read_piece_headers(span_type: &syn::Path, fields: &[ValidatedField], read_headers: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if !fields.iter().any(|field| field.lazy) {
+        return quote!();
+    }
+
+    quote!(
+        fn read_piece_headers<'a>(
+            spans: &#span_type,
+            dataset: &'a [u8],
+            piece: &'a [u8],
+        ) -> vtk::parse::IResult<'a, Self> {
+            // `dataset` starts at the beginning of the file
+            let (buffer, file) = (piece, dataset);
+            #read_headers
+        }
+    )
+}
+
 /// builds the body of `Visitor::read_headers`
 fn visitor_read_headers(visitor_name: &syn::Ident, fields: &[ValidatedField]) -> proc_macro2::TokenStream {
     let mut out = quote!(
         let rest = buffer;
     );
 
-    for field in fields {
+    // each section is found from the start of the piece since the <PointData> and <CellData>
+    // may come before or after the arrays of the mesh. The arrays of a section are looked up by
+    // name, so they may be in any order and arrays without a field are ignored
+    let point_fields : Vec<_> = fields.iter().filter(|field| !field.cell).collect();
+    let cell_fields : Vec<_> = fields.iter().filter(|field| field.cell).collect();
 
-        let fieldname = &field.ident;
-        let lit = syn::LitByteStr::new(&fieldname.to_string().as_bytes(), fieldname.span());
+    for (section, section_fields) in [("PointData", point_fields), ("CellData", cell_fields)] {
+        if section_fields.is_empty() {
+            continue
+        }
+
+        let section = syn::LitByteStr::new(section.as_bytes(), visitor_name.span());
 
-        // TODO: fix this size estimation somehow?
         out = quote!(
             #out
-            let (rest, #fieldname) = vtk::parse::parse_dataarray_or_lazy(rest, #lit, 0)?;
-            let #fieldname = vtk::parse::PartialDataArrayBuffered::new(#fieldname, 0);
+            let (rest, mut section_arrays) = vtk::parse::read_section_arrays(buffer, #section)?;
         );
+
+        for field in section_fields {
+            let fieldname = &field.ident;
+            let lit = syn::LitByteStr::new(&fieldname.to_string().as_bytes(), fieldname.span());
+
+            if field.lazy {
+                // the array is left in place, only its attributes are read
+                out = quote!(
+                    #out
+                    let #fieldname = section_arrays.lazy(#lit, file)?;
+                );
+                continue
+            }
+
+            // TODO: fix this size estimation somehow?
+            out = quote!(
+                #out
+                let #fieldname = section_arrays.take(#lit, 0)?;
+                let #fieldname = vtk::parse::PartialDataArrayBuffered::new(#fieldname, 0);
+            );
+        }
     }
 
     //
@@ -176,7 +256,7 @@ fn make_fields_comma_separated(fields: &[ValidatedField]) -> proc_macro2::TokenS
 fn visitor_buffer_append(fields: &[ValidatedField]) -> proc_macro2::TokenStream {
     let mut out = quote!();
 
-    for field in fields {
+    for field in fields.iter().filter(|field| !field.lazy) {
         let fieldname = &field.ident;
         out = quote!(
             #out
@@ -188,25 +268,56 @@ fn visitor_buffer_append(fields: &[ValidatedField]) -> proc_macro2::TokenStream
 }
 
 /// builds the body of `Visitor::finish`
-fn visitor_finish(output_ident: &syn::Ident, fields: &[ValidatedField]) -> proc_macro2::TokenStream {
+fn visitor_finish(output_ident: &syn::Ident, fields: &[ValidatedField], skipped: &[syn::Ident]) -> proc_macro2::TokenStream {
     let mut out = quote!();
 
+    // cell data has one less value than point data in each direction
+    if fields.iter().any(|field| field.cell && !field.lazy) {
+        out = quote!(
+            let cell_spans = vtk::ParseSpan::cell_spans(spans);
+        );
+    }
+
     for field in fields {
         let fieldname = &field.ident;
+        let name = fieldname.to_string();
+
+        if field.lazy {
+            out = quote!(
+                #out
+                let #fieldname = self.#fieldname;
+            );
+            continue
+        }
+
+        let field_spans = if field.cell {
+            quote!(&cell_spans)
+        } else {
+            quote!(spans)
+        };
 
         out = quote!(
             #out
             let comp  = self.#fieldname.components();
             let #fieldname = self.#fieldname.into_buffer();
-            let #fieldname = vtk::FromBuffer::from_buffer(#fieldname, spans, comp);
+            let #fieldname = vtk::FromBuffer::from_buffer(#fieldname, #field_spans, comp)
+                .map_err(|e| e.with_array_name(#name))?;
         )
     }
 
     let comma_sep_fields = make_fields_comma_separated(fields);
 
+    let mut skipped_fields = quote!();
+    for fieldname in skipped {
+        skipped_fields = quote!(
+            #skipped_fields
+            #fieldname: Default::default(),
+        );
+    }
+
     quote!(
         #out 
-        Ok(#output_ident { #comma_sep_fields} )
+        Ok(#output_ident { #comma_sep_fields #skipped_fields } )
     )
 }
 
@@ -226,24 +337,28 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream> {
 
     check_no_references(&generics.params)?;
 
-    let fields : Result<Vec<_>> = data
-        .take_struct()
-        .expect("Should never be enum")
-        .fields
-        .into_iter()
-        .map(|field: FieldReceiver| {
-            if let Some(ident) = &field.ident {
-                Ok(ValidatedField { ident: ident.clone(), ty: field.ty })
-            } else {
-                Err(syn::Error::new(field.ty.span(), "does not handle tuple struct"))
-            }
-            
-        })
-        .collect();
-    let fields = fields?;
+    let mut fields = Vec::new();
+    let mut skipped = Vec::new();
+
+    for field in data.take_struct().expect("Should never be enum").fields {
+        let ident = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => return Err(syn::Error::new(field.ty.span(), "does not handle tuple struct")),
+        };
+
+        if field.skip && field.lazy {
+            return Err(syn::Error::new(ident.span(), "a field can not be both `skip` and `lazy`"));
+        }
+
+        if field.skip {
+            skipped.push(ident);
+        } else {
+            fields.push(ValidatedField { ident, ty: field.ty, cell: field.cell, lazy: field.lazy });
+        }
+    }
 
 
-    let Visitor { name: visitor_name, tokens: visitor_tokens}  = create_visitor(&ident, &fields, &spans.0);
+    let Visitor { name: visitor_name, tokens: visitor_tokens}  = create_visitor(&ident, &fields, &skipped, &spans.0);
 
     let out = quote!(
         #visitor_tokens
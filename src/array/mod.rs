@@ -34,23 +34,98 @@ pub trait Components {
     fn iter(&self) -> Self::Iter;
 }
 
-impl<T> FromBuffer<T> for Vec<f64> {
-    fn from_buffer(buffer: Vec<f64>, _spans: &T, _components: usize) -> Self {
-        buffer
+/// make sure a decoded buffer holds exactly as many values as the container it fills.
+///
+/// The name of the array is not known here, it is filled in by the caller with
+/// [`ParseError::with_array_name`]
+pub(crate) fn check_length(buffer: &Buffer, expected: usize) -> Result<(), ParseError> {
+    if buffer.len() == expected {
+        Ok(())
+    } else {
+        Err(ParseError::ArrayLength {
+            array: String::new(),
+            expected,
+            found: buffer.len(),
+        })
+    }
+}
+
+impl<T, NUM: Numeric> FromBuffer<T> for Vec<NUM> {
+    fn from_buffer(buffer: Buffer, _spans: &T, _components: usize) -> Result<Self, ParseError> {
+        buffer.into_vec()
+    }
+}
+
+/// split a buffer into points, which must hold the three components of `number_of_points` points
+fn points_from_buffer<NUM: Numeric>(
+    buffer: Buffer,
+    number_of_points: usize,
+) -> Result<Vec<[NUM; 3]>, ParseError> {
+    check_length(&buffer, 3 * number_of_points)?;
+
+    let points = buffer
+        .into_vec::<NUM>()?
+        .chunks_exact(3)
+        .map(|point| [point[0], point[1], point[2]])
+        .collect();
+
+    Ok(points)
+}
+
+impl<NUM: Numeric> FromBuffer<crate::Spans3D> for Vec<[NUM; 3]> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::Spans3D,
+        _components: usize,
+    ) -> Result<Self, ParseError> {
+        points_from_buffer(buffer, spans.x_len() * spans.y_len() * spans.z_len())
     }
 }
 
-impl FromBuffer<crate::Spans3D> for ndarray::Array4<f64> {
-    fn from_buffer(buffer: Vec<f64>, spans: &crate::Spans3D, components: usize) -> Self {
-        let mut arr = Self::from_shape_vec(
-            (spans.x_len(), spans.y_len(), spans.z_len(), components),
-            buffer,
-        )
-        .unwrap();
+impl<NUM: Numeric> FromBuffer<crate::Spans2D> for Vec<[NUM; 3]> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::Spans2D,
+        _components: usize,
+    ) -> Result<Self, ParseError> {
+        points_from_buffer(buffer, spans.x_len() * spans.y_len())
+    }
+}
+
+impl<NUM: Numeric> FromBuffer<crate::UnstructuredSpans> for Vec<[NUM; 3]> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::UnstructuredSpans,
+        _components: usize,
+    ) -> Result<Self, ParseError> {
+        points_from_buffer(buffer, spans.number_of_points)
+    }
+}
+
+impl<NUM: Numeric> FromBuffer<crate::PolySpans> for Vec<[NUM; 3]> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::PolySpans,
+        _components: usize,
+    ) -> Result<Self, ParseError> {
+        points_from_buffer(buffer, spans.number_of_points)
+    }
+}
+
+impl<NUM: Numeric> FromBuffer<crate::Spans3D> for ndarray::Array4<NUM> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::Spans3D,
+        components: usize,
+    ) -> Result<Self, ParseError> {
+        let shape = (spans.x_len(), spans.y_len(), spans.z_len(), components);
+        check_length(&buffer, shape.0 * shape.1 * shape.2 * shape.3)?;
+
+        let mut arr = Self::from_shape_vec(shape, buffer.into_vec()?).unwrap();
         // this axes swap accounts for how the data is read. It shoud now match _exactly_
         // how the information is input
         arr.swap_axes(0, 2);
-        arr
+        Ok(arr)
     }
 }
 
@@ -76,11 +151,9 @@ where
         let mut data = String::new();
         let iter = self.iter();
 
-        for float in iter {
-            let mut buffer = ryu::Buffer::new();
-            let mut num = buffer.format(float).to_string();
-            num.push(' ');
-            data.push_str(&num)
+        for value in iter {
+            value.push_ascii(&mut data);
+            data.push(' ');
         }
 
         writer.write(XmlEvent::Characters(&data))?;
@@ -94,6 +167,7 @@ where
         &self,
         writer: &mut EventWriter<W>,
         name: &str,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error> {
         crate::write_vtk::write_inline_array_header(
             writer,
@@ -103,21 +177,16 @@ where
             NUM::as_precision(),
         )?;
 
-        let mut byte_data: Vec<u8> = Vec::with_capacity((self.length() + 1) * 8);
-
-        // for some reason paraview expects the first 8 bytes to be garbage information -
-        // I have no idea why this is the case but the first 8 bytes must be ignored
-        // for things to work correctly
-        byte_data.extend_from_slice("12345678".as_bytes());
+        let mut byte_data: Vec<u8> = Vec::with_capacity(self.length() * 8);
 
         let iter = self.iter();
 
         for float in iter {
-            float.extend_le_bytes(&mut byte_data);
+            float.extend_bytes(&mut byte_data, byte_order);
         }
 
         // encode as base64
-        let data = base64::encode(byte_data.as_slice());
+        let data = crate::write_vtk::encode_base64_array(&byte_data, byte_order);
 
         writer.write(XmlEvent::Characters(&data))?;
 
@@ -129,24 +198,12 @@ where
     fn write_binary<W: Write>(
         &self,
         writer: &mut EventWriter<W>,
-        is_last: bool,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error> {
         let writer = writer.inner_mut();
 
-        let mut iter = self.iter().peekable();
-
-        loop {
-            if let Some(float) = iter.next() {
-                // edge case: if the array ends with 0.0 then any following data arrays will fail to parse
-                // see https://gitlab.kitware.com/paraview/paraview/-/issues/20982
-                if !is_last && iter.peek().is_none() && float == NUM::ZERO {
-                    NUM::SMALL.write_le_bytes(writer)?;
-                } else {
-                    float.write_le_bytes(writer)?;
-                }
-            } else {
-                break;
-            }
+        for float in self.iter() {
+            float.write_bytes(writer, byte_order)?;
         }
 
         Ok(())
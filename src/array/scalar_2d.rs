@@ -31,9 +31,12 @@ where
     }
 }
 
-impl FromBuffer<crate::Spans2D> for Scalar2D<f64> {
-    fn from_buffer(buffer: Vec<f64>, spans: &crate::Spans2D, _: usize) -> Self {
-        let mut arr = Array4::from_shape_vec((spans.x_len(), spans.y_len(), 1, 1), buffer).unwrap();
+impl<NUM: Numeric> FromBuffer<crate::Spans2D> for Scalar2D<NUM> {
+    fn from_buffer(buffer: Buffer, spans: &crate::Spans2D, _: usize) -> Result<Self, ParseError> {
+        let shape = (spans.x_len(), spans.y_len(), 1, 1);
+        super::check_length(&buffer, shape.0 * shape.1)?;
+
+        let mut arr = Array4::from_shape_vec(shape, buffer.into_vec()?).unwrap();
 
         // this axes swap accounts for how the data is read. It shoud now match _exactly_
         // how the information is input
@@ -41,7 +44,9 @@ impl FromBuffer<crate::Spans2D> for Scalar2D<f64> {
         arr.swap_axes(0, 3);
         arr.swap_axes(1, 2);
 
-        Scalar2D::new(arr.into_shape((spans.x_len(), spans.y_len())).unwrap())
+        Ok(Scalar2D::new(
+            arr.into_shape((spans.x_len(), spans.y_len())).unwrap(),
+        ))
     }
 }
 
@@ -31,23 +31,26 @@ where
     }
 }
 
-impl FromBuffer<crate::Spans3D> for Scalar3D<f64> {
-    fn from_buffer(buffer: Vec<f64>, spans: &crate::Spans3D, components: usize) -> Self {
-        let mut arr = Array4::from_shape_vec(
-            (components, spans.x_len(), spans.y_len(), spans.z_len()),
-            buffer,
-        )
-        .unwrap();
+impl<NUM: Numeric> FromBuffer<crate::Spans3D> for Scalar3D<NUM> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::Spans3D,
+        components: usize,
+    ) -> Result<Self, ParseError> {
+        let shape = (components, spans.x_len(), spans.y_len(), spans.z_len());
+        super::check_length(&buffer, shape.0 * shape.1 * shape.2 * shape.3)?;
+
+        let mut arr = Array4::from_shape_vec(shape, buffer.into_vec()?).unwrap();
         // this axes swap accounts for how the data is read. It shoud now match _exactly_
         // how the information is input
 
         arr.swap_axes(0, 3);
         arr.swap_axes(1, 2);
 
-        Scalar3D::new(
+        Ok(Scalar3D::new(
             arr.into_shape((spans.x_len(), spans.y_len(), spans.z_len()))
                 .unwrap(),
-        )
+        ))
     }
 }
 
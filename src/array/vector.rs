@@ -15,15 +15,16 @@ where
         &self,
         writer: &mut EventWriter<W>,
         name: &str,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error> {
-        self.as_slice().write_base64(writer, name)
+        self.as_slice().write_base64(writer, name, byte_order)
     }
     fn write_binary<W: Write>(
         &self,
         writer: &mut EventWriter<W>,
-        is_last: bool,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error> {
-        self.as_slice().write_binary(writer, is_last)
+        self.as_slice().write_binary(writer, byte_order)
     }
 
     fn length(&self) -> usize {
@@ -59,16 +60,12 @@ where
             1,
             NUM::as_precision(),
         )?;
-        let data : String =
-            // write out all numbers with 12 points of precision
-            self.into_iter()
-                .map(|x| {
-                    let mut buffer = ryu::Buffer::new();
-                    let mut num = buffer.format(*x).to_string();
-                    num.push(' ');
-                    num
-                })
-                .collect();
+        let mut data = String::new();
+
+        for value in self.iter() {
+            value.push_ascii(&mut data);
+            data.push(' ');
+        }
 
         writer.write(XmlEvent::Characters(&data))?;
 
@@ -80,6 +77,7 @@ where
         &self,
         writer: &mut EventWriter<W>,
         name: &str,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error> {
         crate::write_vtk::write_inline_array_header(
             writer,
@@ -88,19 +86,14 @@ where
             1,
             NUM::as_precision(),
         )?;
-        let mut byte_data: Vec<u8> = Vec::with_capacity((self.len() + 1) * 8);
-
-        // for some reason paraview expects the first 8 bytes to be garbage information -
-        // I have no idea why this is the case but the first 8 bytes must be ignored
-        // for things to work correctly
-        byte_data.extend_from_slice("12345678".as_bytes());
+        let mut byte_data: Vec<u8> = Vec::with_capacity(self.len() * 8);
 
-        // convert the floats into LE bytes
-        self.into_iter()
-            .for_each(|float| float.extend_le_bytes(&mut byte_data));
+        // convert the floats into bytes
+        self.iter()
+            .for_each(|float| float.extend_bytes(&mut byte_data, byte_order));
 
         // encode as base64
-        let data = base64::encode(byte_data.as_slice());
+        let data = crate::write_vtk::encode_base64_array(&byte_data, byte_order);
 
         writer.write(XmlEvent::Characters(&data))?;
 
@@ -112,24 +105,12 @@ where
     fn write_binary<W: Write>(
         &self,
         writer: &mut EventWriter<W>,
-        is_last: bool,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error> {
         let writer = writer.inner_mut();
 
-        let mut iter = self.iter().peekable();
-
-        loop {
-            if let Some(float) = iter.next() {
-                // edge case: if the array ends with 0.0 then any following data arrays will fail to parse
-                // see https://gitlab.kitware.com/paraview/paraview/-/issues/20982
-                if !is_last && iter.peek().is_none() && *float == NUM::ZERO {
-                    NUM::SMALL.write_le_bytes(writer)?;
-                } else {
-                    float.write_le_bytes(writer)?;
-                }
-            } else {
-                break;
-            }
+        for float in self.iter() {
+            float.write_bytes(writer, byte_order)?;
         }
         Ok(())
     }
@@ -150,3 +131,98 @@ where
         NUM::SIZE
     }
 }
+
+/// A list of points in 3D space, such as the points of an unstructured mesh. The three
+/// components of each point are written one after the other
+impl<NUM> Array for Vec<[NUM; 3]>
+where
+    NUM: Numeric,
+{
+    fn write_ascii<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        name: &str,
+    ) -> Result<(), crate::Error> {
+        crate::write_vtk::write_inline_array_header(
+            writer,
+            crate::write_vtk::Encoding::Ascii,
+            name,
+            3,
+            NUM::as_precision(),
+        )?;
+        let mut data = String::new();
+
+        for value in flatten_points(self) {
+            value.push_ascii(&mut data);
+            data.push(' ');
+        }
+
+        writer.write(XmlEvent::Characters(&data))?;
+
+        crate::write_vtk::close_inline_array_header(writer)?;
+
+        Ok(())
+    }
+
+    fn write_base64<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        name: &str,
+        byte_order: ByteOrder,
+    ) -> Result<(), crate::Error> {
+        crate::write_vtk::write_inline_array_header(
+            writer,
+            crate::write_vtk::Encoding::Base64,
+            name,
+            3,
+            NUM::as_precision(),
+        )?;
+        let mut byte_data: Vec<u8> = Vec::with_capacity(self.len() * 3 * NUM::SIZE);
+
+        for value in flatten_points(self) {
+            value.extend_bytes(&mut byte_data, byte_order);
+        }
+
+        let data = crate::write_vtk::encode_base64_array(&byte_data, byte_order);
+
+        writer.write(XmlEvent::Characters(&data))?;
+
+        crate::write_vtk::close_inline_array_header(writer)?;
+
+        Ok(())
+    }
+
+    fn write_binary<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), crate::Error> {
+        let writer = writer.inner_mut();
+
+        for value in flatten_points(self) {
+            value.write_bytes(writer, byte_order)?;
+        }
+        Ok(())
+    }
+
+    fn length(&self) -> usize {
+        self.len() * 3
+    }
+
+    fn components(&self) -> usize {
+        3
+    }
+
+    fn precision(&self) -> Precision {
+        NUM::as_precision()
+    }
+
+    fn size_of_elem(&self) -> usize {
+        NUM::SIZE
+    }
+}
+
+/// the components of every point, without copying the points
+fn flatten_points<NUM: Numeric>(points: &[[NUM; 3]]) -> impl Iterator<Item = NUM> + '_ {
+    points.iter().flat_map(|point| point.iter().copied())
+}
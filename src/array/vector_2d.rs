@@ -30,18 +30,24 @@ where
     }
 }
 
-impl FromBuffer<crate::Spans2D> for Vector2D<f64> {
-    fn from_buffer(buffer: Vec<f64>, spans: &crate::Spans2D, components: usize) -> Self {
-        let mut arr =
-            Array4::from_shape_vec((components, spans.x_len(), spans.y_len(), 1), buffer).unwrap();
+impl<NUM: Numeric> FromBuffer<crate::Spans2D> for Vector2D<NUM> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::Spans2D,
+        components: usize,
+    ) -> Result<Self, ParseError> {
+        let shape = (components, spans.x_len(), spans.y_len(), 1);
+        super::check_length(&buffer, shape.0 * shape.1 * shape.2)?;
+
+        let mut arr = Array4::from_shape_vec(shape, buffer.into_vec()?).unwrap();
 
         arr.swap_axes(0, 3);
         arr.swap_axes(1, 2);
 
-        Vector2D::new(
+        Ok(Vector2D::new(
             arr.into_shape((components, spans.x_len(), spans.y_len()))
                 .unwrap(),
-        )
+        ))
     }
 }
 
@@ -40,13 +40,16 @@ pub struct Vector3DIter<NUM> {
     z: usize,
 }
 
-impl FromBuffer<crate::Spans3D> for Vector3D<f64> {
-    fn from_buffer(buffer: Vec<f64>, spans: &crate::Spans3D, components: usize) -> Self {
-        let mut arr = ndarray::Array5::from_shape_vec(
-            (components, spans.x_len(), spans.y_len(), spans.z_len(), 1),
-            buffer,
-        )
-        .unwrap();
+impl<NUM: Numeric> FromBuffer<crate::Spans3D> for Vector3D<NUM> {
+    fn from_buffer(
+        buffer: Buffer,
+        spans: &crate::Spans3D,
+        components: usize,
+    ) -> Result<Self, ParseError> {
+        let shape = (components, spans.x_len(), spans.y_len(), spans.z_len(), 1);
+        super::check_length(&buffer, shape.0 * shape.1 * shape.2 * shape.3)?;
+
+        let mut arr = ndarray::Array5::from_shape_vec(shape, buffer.into_vec()?).unwrap();
         // this axes swap accounts for how the data is read. It shoud now match _exactly_
         // how the information is input
 
@@ -56,7 +59,7 @@ impl FromBuffer<crate::Spans3D> for Vector3D<f64> {
         let arr = arr
             .into_shape((components, spans.x_len(), spans.y_len(), spans.z_len()))
             .unwrap();
-        Vector3D::new(arr)
+        Ok(Vector3D::new(arr))
     }
 }
 
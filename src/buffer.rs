@@ -0,0 +1,115 @@
+//! The values of an array as they are decoded from a file
+use crate::prelude::*;
+
+use crate::utils;
+
+/// The values of an array in the kind of number of its `type` attribute.
+///
+/// Floating point arrays are decoded to `f64`, which holds every `Float32` value exactly.
+/// Signed and unsigned integer arrays are decoded to `i64` and `u64`, so that large `Int64` and
+/// `UInt64` values are not rounded as they would be by a `f64`. The values are converted to the
+/// element type of a container with [`into_vec`](Buffer::into_vec).
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Buffer {
+    Float(Vec<f64>),
+    Int(Vec<i64>),
+    UInt(Vec<u64>),
+}
+
+impl Buffer {
+    /// an empty buffer for the values of an array of type `precision`
+    pub fn with_capacity(precision: Precision, capacity: usize) -> Self {
+        match precision {
+            Precision::Float32 | Precision::Float64 => Self::Float(Vec::with_capacity(capacity)),
+            Precision::Int8 | Precision::Int16 | Precision::Int32 | Precision::Int64 => {
+                Self::Int(Vec::with_capacity(capacity))
+            }
+            Precision::UInt8 | Precision::UInt16 | Precision::UInt32 | Precision::UInt64 => {
+                Self::UInt(Vec::with_capacity(capacity))
+            }
+        }
+    }
+
+    /// the number of values in the buffer
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Float(values) => values.len(),
+            Self::Int(values) => values.len(),
+            Self::UInt(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// convert every value to `NUM`.
+    ///
+    /// Values that do not fit in `NUM` (such as `300` in a `u8`, or `1.5` in an `i32`) are an
+    /// [`UnrepresentableValue`](ParseError::UnrepresentableValue) error, whose array name is
+    /// filled in by the caller with [`ParseError::with_array_name`]
+    pub fn into_vec<NUM: Numeric>(self) -> Result<Vec<NUM>, ParseError> {
+        match self {
+            Self::Float(values) => convert(values, NUM::from_f64),
+            Self::Int(values) => convert(values, NUM::from_i64),
+            Self::UInt(values) => convert(values, NUM::from_u64),
+        }
+    }
+
+    /// decode the raw bytes of elements of type `precision`, appending them to the buffer
+    pub(crate) fn extend_from_bytes(
+        &mut self,
+        bytes: &[u8],
+        precision: Precision,
+        byte_order: ByteOrder,
+    ) {
+        let elements = bytes.chunks_exact(precision.size_of());
+
+        match self {
+            Self::Float(values) => values.extend(
+                elements.map(|element| utils::bytes_to_float(element, precision, byte_order)),
+            ),
+            Self::Int(values) => values.extend(
+                elements.map(|element| utils::bytes_to_int(element, precision, byte_order)),
+            ),
+            Self::UInt(values) => values.extend(
+                elements.map(|element| utils::bytes_to_uint(element, precision, byte_order)),
+            ),
+        }
+    }
+}
+
+fn convert<T, NUM>(values: Vec<T>, from: fn(T) -> Option<NUM>) -> Result<Vec<NUM>, ParseError>
+where
+    T: Copy + std::fmt::Display,
+    NUM: Numeric,
+{
+    values
+        .into_iter()
+        .map(|value| {
+            from(value).ok_or_else(|| ParseError::UnrepresentableValue {
+                array: String::new(),
+                value: value.to_string(),
+                precision: NUM::as_precision(),
+            })
+        })
+        .collect()
+}
+
+impl From<Vec<f64>> for Buffer {
+    fn from(values: Vec<f64>) -> Self {
+        Self::Float(values)
+    }
+}
+
+impl From<Vec<i64>> for Buffer {
+    fn from(values: Vec<i64>) -> Self {
+        Self::Int(values)
+    }
+}
+
+impl From<Vec<u64>> for Buffer {
+    fn from(values: Vec<u64>) -> Self {
+        Self::UInt(values)
+    }
+}
@@ -0,0 +1,245 @@
+//! ParaView `.pvd` collections, which list the files of a time series
+//!
+//! A [`Collection`] records the time and part of every file written during a run. The `.pvd`
+//! file is rewritten by [`flush`](Collection::flush) as files are added, so that a run that
+//! stops early still leaves a collection of every file that was completed. On restart, the
+//! existing collection is read back with [`open`](Collection::open) and appended to.
+use crate::prelude::*;
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use nom::bytes::complete::take_till;
+
+/// A single `<DataSet>` of a collection
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionEntry {
+    /// the `timestep` of the file
+    pub time: f64,
+    /// the `part` of the dataset that the file contains, for datasets split into several files
+    /// at every time
+    pub part: usize,
+    /// the path of the file, relative to the `.pvd` file
+    pub file: String,
+}
+
+impl CollectionEntry {
+    pub fn new(time: f64, part: usize, file: &str) -> Self {
+        Self {
+            time,
+            part,
+            file: file.to_string(),
+        }
+    }
+}
+
+/// The `.pvd` file of a time series.
+///
+/// ```no_run
+/// #[derive(vtk::DataArray)]
+/// pub struct Fields {
+///     rho: vtk::Scalar3D<f64>,
+/// }
+///
+/// # fn snapshot(step: usize) -> vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, Fields> {
+/// #     unimplemented!()
+/// # }
+/// // continue the collection of a previous run, if there is one
+/// let mut collection = vtk::Collection::open("./output/flow.pvd".as_ref()).unwrap();
+/// collection.remove_after(0.5);
+///
+/// for step in 0..10 {
+///     let time = 0.5 + step as f64 * 0.1;
+///     let file = format!("flow_{:04}.vtr", step);
+///
+///     // writes ./output/flow_0000.vtr, and rewrites ./output/flow.pvd with the new entry
+///     collection.write_vtk(time, 0, &file, snapshot(step)).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collection {
+    path: PathBuf,
+    entries: Vec<CollectionEntry>,
+}
+
+impl Collection {
+    /// an empty collection that is written to `path`, replacing any existing file once it is
+    /// flushed
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// the collection at `path` with the entries of the existing file, or an empty collection
+    /// if the file does not exist yet
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let mut collection = Self::new(path);
+
+        let mut file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(collection),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        collection.entries = parse_pvd(&buffer)?;
+
+        Ok(collection)
+    }
+
+    /// add the file `file` (relative to the `.pvd` file) containing `part` of the dataset at
+    /// `time`. The collection is only written by [`flush`](Collection::flush)
+    pub fn add(&mut self, time: f64, part: usize, file: &str) {
+        self.entries.push(CollectionEntry::new(time, part, file));
+    }
+
+    /// write `data` to `file` (relative to the `.pvd` file) with [`write_vtk`](crate::write_vtk)
+    /// and add it to the collection. The collection is flushed once the file is complete
+    pub fn write_vtk<D, DOMAIN, EncMesh, EncArray>(
+        &mut self,
+        time: f64,
+        part: usize,
+        file: &str,
+        data: VtkData<DOMAIN, D>,
+    ) -> Result<(), Error>
+    where
+        D: DataArray<EncArray>,
+        DOMAIN: Domain<EncMesh>,
+        EncArray: Encode,
+        EncMesh: Encode,
+    {
+        let path = self.directory().join(file);
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        crate::write_vtk(&mut writer, data)?;
+        // the last bytes of the file are only written by the flush, so an error writing them
+        // must be returned before the file is added to the collection
+        writer.flush()?;
+
+        self.add(time, part, file);
+        self.flush()
+    }
+
+    /// remove every entry after `time`, such as the files of a previous run that are written
+    /// again after a restart from `time`
+    pub fn remove_after(&mut self, time: f64) {
+        self.entries.retain(|entry| entry.time <= time);
+    }
+
+    /// the path of the `.pvd` file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// the directory that the paths of the entries are relative to
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new(""))
+    }
+
+    /// every entry of the collection, in the order they were added
+    pub fn entries(&self) -> &[CollectionEntry] {
+        &self.entries
+    }
+
+    /// write the collection to its `.pvd` file.
+    ///
+    /// The collection is written to a temporary file next to the `.pvd` file that then replaces
+    /// it, so the `.pvd` file is always a complete collection even if the program stops while
+    /// it is being written
+    pub fn flush(&self) -> Result<(), Error> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&temporary)?);
+        self.write(&mut writer)?;
+
+        // the contents of the temporary file are on disk before it replaces the `.pvd` file, so
+        // a crash after the rename does not leave an empty collection
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        drop(file);
+
+        std::fs::rename(&temporary, &self.path)?;
+
+        Ok(())
+    }
+
+    /// write the `.pvd` file to `writer`
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut writer = EventWriter::new(writer);
+
+        writer.write(XmlEvent::StartDocument {
+            version: xml::common::XmlVersion::Version10,
+            encoding: None,
+            standalone: None,
+        })?;
+
+        write_vtk::start_element(
+            &mut writer,
+            "VTKFile",
+            &[
+                ("type", "Collection"),
+                ("version", "0.1"),
+                ("byte_order", ByteOrder::LittleEndian.to_str()),
+            ],
+        )?;
+        write_vtk::open_element(&mut writer, "Collection")?;
+
+        for entry in &self.entries {
+            let time = entry.time.to_string();
+            let part = entry.part.to_string();
+
+            write_vtk::start_element(
+                &mut writer,
+                "DataSet",
+                &[
+                    ("timestep", &time),
+                    ("group", ""),
+                    ("part", &part),
+                    ("file", &entry.file),
+                ],
+            )?;
+            write_vtk::close_element(&mut writer, "DataSet")?;
+        }
+
+        write_vtk::close_element(&mut writer, "Collection")?;
+        write_vtk::close_element(&mut writer, "VTKFile")?;
+
+        Ok(())
+    }
+}
+
+/// read every `<DataSet>` of the bytes of a `.pvd` file, see [`Collection::open`]
+pub fn parse_pvd(bytes: &[u8]) -> Result<Vec<CollectionEntry>, Error> {
+    let (_, entries) = read_entries(bytes)
+        .map_err(|e| ParseError::from_nom_in(e, bytes, "could not read the collection file"))?;
+
+    Ok(entries)
+}
+
+fn read_entries(xml: &[u8]) -> IResult<'_, Vec<CollectionEntry>> {
+    let mut entries = Vec::new();
+    let mut rest = xml;
+
+    while let Ok((dataset_start, _)) = parse::take_until_consume(rest, b"<DataSet") {
+        let (dataset_end, element) = take_till(|c| c == b'>')(dataset_start)?;
+        let (_, attributes) = parse::read_element_attributes(element)?;
+
+        let file = String::from_utf8_lossy(attributes.require(b"file")?).into_owned();
+        let time = parse::parse_number(attributes.require(b"timestep")?, &file)?;
+
+        // `part` is optional, and may be left empty
+        let part = match attributes.get(b"part") {
+            Some(part) if !part.is_empty() => parse::parse_number(part, &file)?,
+            _ => 0,
+        };
+
+        entries.push(CollectionEntry { time, part, file });
+        rest = dataset_end;
+    }
+
+    Ok((rest, entries))
+}
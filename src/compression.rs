@@ -0,0 +1,249 @@
+//! Block-wise compression of the arrays in the appended section of a file
+//!
+//! VTK compresses every appended array on its own. The uncompressed bytes of an array are split
+//! into blocks of [`BLOCK_SIZE`] bytes that are compressed independently, and the compressed
+//! blocks are preceded by a header of `header_type` integers:
+//!
+//! ```text
+//! [number of blocks] [uncompressed block size] [uncompressed size of the last block] [compressed size of each block ...]
+//! ```
+//!
+//! where the size of the last block is `0` if it is a full block.
+use crate::prelude::*;
+
+use std::io;
+
+/// The number of uncompressed bytes in every (but the last) block of an array
+pub(crate) const BLOCK_SIZE: usize = 32768;
+
+/// The algorithm used to compress the appended arrays of a file, written to the
+/// `compressor` attribute of the `<VTKFile>` element.
+///
+/// Every compressor is behind a cargo feature of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compressor {
+    /// `vtkZLibDataCompressor`, requires the `zlib` feature
+    #[cfg(feature = "zlib")]
+    ZLib,
+    /// `vtkLZ4DataCompressor`, requires the `lz4` feature. Fast to compress and decompress,
+    /// but with larger files than the other compressors
+    #[cfg(feature = "lz4")]
+    Lz4,
+    /// `vtkLZMADataCompressor`, requires the `lzma` feature. Slow to compress, but with the
+    /// smallest files
+    #[cfg(feature = "lzma")]
+    Lzma,
+}
+
+impl Compressor {
+    pub(crate) fn to_str(self) -> &'static str {
+        match self {
+            #[cfg(feature = "zlib")]
+            Self::ZLib => "vtkZLibDataCompressor",
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => "vtkLZ4DataCompressor",
+            #[cfg(feature = "lzma")]
+            Self::Lzma => "vtkLZMADataCompressor",
+        }
+    }
+
+    /// parse the `compressor` attribute of a `<VTKFile>`. Compressors that are unknown or
+    /// whose feature is not enabled return `None`
+    pub(crate) fn from_bytes(name: &[u8]) -> Option<Self> {
+        match name {
+            #[cfg(feature = "zlib")]
+            b"vtkZLibDataCompressor" => Some(Self::ZLib),
+            #[cfg(feature = "lz4")]
+            b"vtkLZ4DataCompressor" => Some(Self::Lz4),
+            #[cfg(feature = "lzma")]
+            b"vtkLZMADataCompressor" => Some(Self::Lzma),
+            _ => None,
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn compress_block(self, block: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "zlib")]
+            Self::ZLib => {
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(block)?;
+                encoder.finish()
+            }
+            // vtk stores raw lz4 blocks without the lz4 frame format
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Ok(lz4_flex::block::compress(block)),
+            // and lzma blocks in the xz container format
+            #[cfg(feature = "lzma")]
+            Self::Lzma => {
+                let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+                encoder.write_all(block)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    /// decompress a single block that is `size` bytes long when uncompressed. `size` is read
+    /// from the file, so no more than `size` bytes are decompressed and the block is only
+    /// allocated as it is read
+    #[allow(unused_variables)]
+    fn decompress_block(self, block: &[u8], size: usize) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "zlib")]
+            Self::ZLib => {
+                use std::io::Read;
+                let mut out = Vec::new();
+                flate2::read::ZlibDecoder::new(block)
+                    .take(size as u64)
+                    .read_to_end(&mut out)?;
+                Ok(out)
+            }
+            // lz4 allocates the whole block up front, but a block expands to at most 255 times
+            // its compressed size
+            #[cfg(feature = "lz4")]
+            Self::Lz4 if size > block.len().saturating_mul(255) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "lz4 block is smaller than its uncompressed size allows",
+            )),
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => lz4_flex::block::decompress(block, size)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            #[cfg(feature = "lzma")]
+            Self::Lzma => {
+                use std::io::Read;
+                let mut out = Vec::new();
+                xz2::read::XzDecoder::new(block)
+                    .take(size as u64)
+                    .read_to_end(&mut out)?;
+                Ok(out)
+            }
+        }
+    }
+}
+
+/// compress the raw bytes of a single array, returning the (`UInt64`) header in `byte_order`
+/// followed by the compressed blocks
+pub(crate) fn compress(
+    bytes: &[u8],
+    compressor: Compressor,
+    byte_order: ByteOrder,
+) -> io::Result<Vec<u8>> {
+    let blocks = bytes
+        .chunks(BLOCK_SIZE)
+        .map(|block| compressor.compress_block(block))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut header = vec![
+        blocks.len() as u64,
+        BLOCK_SIZE as u64,
+        (bytes.len() % BLOCK_SIZE) as u64,
+    ];
+    header.extend(blocks.iter().map(|block| block.len() as u64));
+
+    let mut out = Vec::with_capacity(
+        header.len() * 8 + blocks.iter().map(|block| block.len()).sum::<usize>(),
+    );
+
+    for value in header {
+        value.extend_bytes(&mut out, byte_order);
+    }
+
+    for block in blocks {
+        out.extend_from_slice(&block);
+    }
+
+    Ok(out)
+}
+
+/// decompress a single array whose header starts at the beginning of `bytes`. `bytes` may
+/// contain more data (such as the following arrays) after the array.
+///
+/// `header_type` is the type of the integers in the header, either `UInt32` or `UInt64`, stored
+/// in `byte_order`. Every size in the header is read from the file, so it is checked against the
+/// length of `bytes` before anything is allocated for it
+pub(crate) fn decompress(
+    bytes: &[u8],
+    compressor: Compressor,
+    header_type: Precision,
+    byte_order: ByteOrder,
+) -> Result<Vec<u8>, ParseError> {
+    let width = header_type.size_of();
+
+    let read_header = |idx: usize| -> Result<usize, ParseError> {
+        let value = idx
+            .checked_mul(width)
+            .and_then(|start| bytes.get(start..start.checked_add(width)?))
+            .ok_or_else(|| invalid("the header is longer than the appended section"))?;
+
+        usize::try_from(crate::utils::bytes_to_uint(value, header_type, byte_order))
+            .map_err(|_| invalid("a size in the header is too large"))
+    };
+
+    let num_blocks = read_header(0)?;
+    let block_size = read_header(1)?;
+    let last_block_size = read_header(2)?;
+
+    let header_end = num_blocks
+        .checked_add(3)
+        .and_then(|values| values.checked_mul(width))
+        .filter(|header_end| *header_end <= bytes.len())
+        .ok_or_else(|| invalid("the header is longer than the appended section"))?;
+
+    let uncompressed_size = match (num_blocks, last_block_size) {
+        (0, _) => Some(0),
+        (_, 0) => num_blocks.checked_mul(block_size),
+        (_, last) => (num_blocks - 1)
+            .checked_mul(block_size)
+            .and_then(|size| size.checked_add(last)),
+    }
+    .ok_or_else(|| invalid("the uncompressed size in the header is too large"))?;
+
+    // find every block before decompressing any of them, so that the sizes of a header that
+    // does not describe the file are never allocated
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut block_start = header_end;
+
+    for idx in 0..num_blocks {
+        let compressed_size = read_header(3 + idx)?;
+        let block = block_start
+            .checked_add(compressed_size)
+            .and_then(|block_end| bytes.get(block_start..block_end))
+            .ok_or_else(|| invalid("a block is longer than the appended section"))?;
+
+        blocks.push(block);
+        block_start += compressed_size;
+    }
+
+    let mut out = Vec::new();
+
+    for (idx, block) in blocks.into_iter().enumerate() {
+        // every block but the last is a full block
+        let size = if idx + 1 == num_blocks && last_block_size != 0 {
+            last_block_size
+        } else {
+            block_size
+        };
+
+        let block = compressor
+            .decompress_block(block, size)
+            .map_err(|_| invalid("a block could not be decompressed"))?;
+
+        out.extend(block);
+    }
+
+    if out.len() != uncompressed_size {
+        return Err(invalid(
+            "the decompressed array does not match the size described by its header",
+        ));
+    }
+
+    Ok(out)
+}
+
+fn invalid(reason: &'static str) -> ParseError {
+    ParseError::InvalidCompression {
+        array: String::new(),
+        reason,
+    }
+}
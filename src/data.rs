@@ -1,3 +1,5 @@
+use crate::FieldData;
+
 #[derive(Debug, Default, Clone, PartialEq)]
 /// Container type to read and write vtk files from.
 ///
@@ -10,15 +12,44 @@
 /// implemented for two container types for rectilinear data:
 /// [Rectilinear3D](`crate::Rectilinear3D`) and [Rectilinear2D](`crate::Rectilinear2D`) (depending
 /// on the dimensionality of your data).
+///
+/// `field_data` holds metadata for the whole dataset (such as the simulation time) and is
+/// empty unless set with [`with_field_data`](`VtkData::with_field_data`).
+///
+/// `field_data` was added in 0.5, so struct literals written for 0.4 (`VtkData { domain, data }`)
+/// no longer compile. Construct the container with [`new`](`VtkData::new`) instead, or fill the
+/// remaining fields with `..Default::default()`:
+///
+/// ```
+/// # let domain = ();
+/// # let data = ();
+/// let vtk = vtk::VtkData {
+///     domain,
+///     data,
+///     ..Default::default()
+/// };
+/// assert!(vtk.field_data.is_empty());
+/// ```
 pub struct VtkData<DOMAIN, D> {
     pub domain: DOMAIN,
     pub data: D,
+    pub field_data: FieldData,
 }
 
 impl<DOMAIN, D> VtkData<DOMAIN, D> {
     /// Construct a `vtk` container for writing to a file
     pub fn new(domain: DOMAIN, data: D) -> VtkData<DOMAIN, D> {
-        VtkData { domain, data }
+        VtkData {
+            domain,
+            data,
+            field_data: FieldData::default(),
+        }
+    }
+
+    /// attach dataset-wide metadata that is written to a `<FieldData>` element
+    pub fn with_field_data(mut self, field_data: FieldData) -> VtkData<DOMAIN, D> {
+        self.field_data = field_data;
+        self
     }
 
     /// change the datatype of the data stored in this container while leaving the
@@ -27,6 +58,7 @@ impl<DOMAIN, D> VtkData<DOMAIN, D> {
         VtkData {
             domain: self.domain,
             data: new_data,
+            field_data: self.field_data,
         }
     }
 }
@@ -0,0 +1,280 @@
+//! Reading files whose arrays are not known at compile time
+//!
+//! [`read_vtk`](crate::read_vtk) requires a [`ParseArray`](crate::ParseArray) type describing
+//! the arrays of the file. For inspecting arbitrary files, [`read_vtk_dynamic`] reads every
+//! array of the `<PointData>`, `<CellData>` and `<FieldData>` sections along with the domain of
+//! any grid type this crate can read.
+use crate::prelude::*;
+use crate::FieldData;
+use crate::{Mesh3D, PolyMesh, StructuredMesh3D, UniformMesh3D, UnstructuredMesh};
+use crate::{PolyData, Rectilinear3D, Structured3D, Uniform3D, Unstructured};
+
+use nom::bytes::complete::take_until;
+use std::io::Read;
+
+/// The contents of a file read by [`read_vtk_dynamic`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynVtk {
+    pub domain: DynDomain,
+    pub point_data: DynArrays,
+    pub cell_data: DynArrays,
+    pub field_data: FieldData,
+}
+
+/// The domain of a file read by [`read_vtk_dynamic`], depending on the `type` of the file.
+///
+/// Two dimensional files are written as a single plane of a three dimensional domain, and
+/// are read as such.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynDomain {
+    Rectilinear(Rectilinear3D<f64, Binary>),
+    Uniform(Uniform3D<f64>),
+    Structured(Structured3D<f64, Binary>),
+    Unstructured(Unstructured<f64, Binary>),
+    PolyData(PolyData<f64, Binary>),
+}
+
+impl DynDomain {
+    /// the `type` of the file the domain was read from
+    pub fn grid_type(&self) -> GridType {
+        match self {
+            Self::Rectilinear(_) => GridType::RectilinearGrid,
+            Self::Uniform(_) => GridType::ImageData,
+            Self::Structured(_) => GridType::StructuredGrid,
+            Self::Unstructured(_) => GridType::UnstructuredGrid,
+            Self::PolyData(_) => GridType::PolyData,
+        }
+    }
+}
+
+/// A single array of a file, with the values of every component of every tuple in the order
+/// they are stored in the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynArray {
+    /// the `type` of the array in the file. The values are decoded to floats, signed or unsigned
+    /// integers depending on it
+    pub precision: Precision,
+    pub components: usize,
+    pub values: Buffer,
+}
+
+impl DynArray {
+    /// a copy of the values converted to `NUM`, or an
+    /// [`UnrepresentableValue`](ParseError::UnrepresentableValue) error if a value does not fit
+    /// in `NUM`
+    pub fn to_vec<NUM: Numeric>(&self) -> Result<Vec<NUM>, ParseError> {
+        self.values.clone().into_vec()
+    }
+
+    /// the number of tuples (such as points or cells) of the array
+    pub fn tuples(&self) -> usize {
+        self.values
+            .len()
+            .checked_div(self.components)
+            .unwrap_or_default()
+    }
+}
+
+/// The arrays of a `<PointData>` or `<CellData>` section, in the order they appear in the file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DynArrays {
+    entries: Vec<(String, DynArray)>,
+}
+
+impl DynArrays {
+    /// fetch the array named `name`, if present
+    pub fn get(&self, name: &str) -> Option<&DynArray> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, array)| array)
+    }
+
+    /// iterate over the names and arrays of the section
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DynArray)> {
+        self.entries
+            .iter()
+            .map(|(name, array)| (name.as_str(), array))
+    }
+
+    /// the names of every array of the section
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// the number of arrays
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// if the section does not contain any arrays
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// read every array of a file, regardless of its grid type
+///
+/// ```no_run
+/// let vtk = vtk::read_vtk_dynamic("./data/output.vtr".as_ref()).unwrap();
+///
+/// for (name, array) in vtk.point_data.iter() {
+///     println!("{}: {} x {:?}", name, array.tuples(), array.precision);
+/// }
+/// ```
+pub fn read_vtk_dynamic(path: &std::path::Path) -> Result<DynVtk, Error> {
+    let mut file = std::fs::File::open(path)?;
+    let mut buffer = Vec::with_capacity(1024 * 1024 * 3);
+    file.read_to_end(&mut buffer)?;
+
+    parse_dynamic(&buffer)
+}
+
+/// read every array of the bytes of a file, see [`read_vtk_dynamic`]
+pub fn parse_dynamic(bytes: &[u8]) -> Result<DynVtk, Error> {
+    let (_, attributes) = parse::read_file_attributes(bytes)
+        .map_err(|e| ParseError::from_nom_in(e, bytes, "could not read the <VTKFile> element"))?;
+
+    let grid_type = attributes.grid_type.ok_or_else(|| {
+        ParseError::from_nom(
+            parse::NomError::nom(bytes, nom::error::ErrorKind::Tag),
+            "the `type` of the <VTKFile> element is missing or not supported",
+        )
+    })?;
+
+    let (domain, data, field_data) = match grid_type {
+        GridType::RectilinearGrid => {
+            let vtk: VtkData<Rectilinear3D<f64, Binary>, DynData> =
+                parse::parse_xml_document::<_, _, _, Mesh3D<f64, Binary>, _, _>(bytes)?;
+            (DynDomain::Rectilinear(vtk.domain), vtk.data, vtk.field_data)
+        }
+        GridType::ImageData => {
+            let vtk: VtkData<Uniform3D<f64>, DynData> =
+                parse::parse_xml_document::<_, _, _, UniformMesh3D<f64>, _, _>(bytes)?;
+            (DynDomain::Uniform(vtk.domain), vtk.data, vtk.field_data)
+        }
+        GridType::StructuredGrid => {
+            let vtk: VtkData<Structured3D<f64, Binary>, DynData> =
+                parse::parse_xml_document::<_, _, _, StructuredMesh3D<f64, Binary>, _, _>(bytes)?;
+            (DynDomain::Structured(vtk.domain), vtk.data, vtk.field_data)
+        }
+        GridType::UnstructuredGrid => {
+            let vtk: VtkData<Unstructured<f64, Binary>, DynData> =
+                parse::parse_xml_document::<_, _, _, UnstructuredMesh<f64, Binary>, _, _>(bytes)?;
+            (
+                DynDomain::Unstructured(vtk.domain),
+                vtk.data,
+                vtk.field_data,
+            )
+        }
+        GridType::PolyData => {
+            let vtk: VtkData<PolyData<f64, Binary>, DynData> =
+                parse::parse_xml_document::<_, _, _, PolyMesh<f64, Binary>, _, _>(bytes)?;
+            (DynDomain::PolyData(vtk.domain), vtk.data, vtk.field_data)
+        }
+    };
+
+    Ok(DynVtk {
+        domain,
+        point_data: data.point_data,
+        cell_data: data.cell_data,
+        field_data,
+    })
+}
+
+/// every array of the `<PointData>` and `<CellData>` sections of a file
+struct DynData {
+    point_data: DynArrays,
+    cell_data: DynArrays,
+}
+
+impl ParseArray for DynData {
+    type Visitor = DynDataVisitor;
+}
+
+struct DynDataVisitor {
+    point_data: Vec<DynArrayVisitor>,
+    cell_data: Vec<DynArrayVisitor>,
+}
+
+struct DynArrayVisitor {
+    name: String,
+    precision: Precision,
+    array: parse::PartialDataArrayBuffered,
+}
+
+/// take every array of `section`. Files without the section have no arrays in it, while a
+/// section that can not be read (such as one without a closing tag) is an error
+fn read_section<'a>(buffer: &'a [u8], section: &[u8]) -> IResult<'a, Vec<DynArrayVisitor>> {
+    let mut opening_tag = b"<".to_vec();
+    opening_tag.extend_from_slice(section);
+
+    if take_until::<_, _, parse::NomError>(opening_tag.as_slice())(buffer).is_err() {
+        return Ok((buffer, Vec::new()));
+    }
+
+    let (_, mut section_arrays) = parse::read_section_arrays(buffer, section)?;
+
+    let names: Vec<&[u8]> = section_arrays.names().collect();
+    let mut arrays = Vec::with_capacity(names.len());
+
+    for name in names {
+        let description = section_arrays.describe(name)?;
+        let array = section_arrays.take(name, 0)?;
+
+        arrays.push(DynArrayVisitor {
+            name: description.name,
+            precision: description.header.precision(),
+            array: parse::PartialDataArrayBuffered::new(array, 0),
+        });
+    }
+
+    Ok((buffer, arrays))
+}
+
+fn finish_section(arrays: Vec<DynArrayVisitor>) -> DynArrays {
+    let entries = arrays
+        .into_iter()
+        .map(|visitor| {
+            let components = visitor.array.components();
+            let array = DynArray {
+                precision: visitor.precision,
+                components,
+                values: visitor.array.into_buffer(),
+            };
+            (visitor.name, array)
+        })
+        .collect();
+
+    DynArrays { entries }
+}
+
+impl<SPAN> Visitor<SPAN> for DynDataVisitor {
+    type Output = DynData;
+
+    fn read_headers<'a>(_spans: &SPAN, buffer: &'a [u8]) -> IResult<'a, Self> {
+        let (_, point_data) = read_section(buffer, b"PointData")?;
+        let (_, cell_data) = read_section(buffer, b"CellData")?;
+
+        Ok((
+            buffer,
+            Self {
+                point_data,
+                cell_data,
+            },
+        ))
+    }
+
+    fn add_to_appended_reader<'a>(&'a self, buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {
+        for visitor in self.point_data.iter().chain(&self.cell_data) {
+            visitor.array.append_to_reader_list(buffer);
+        }
+    }
+
+    fn finish(self, _spans: &SPAN) -> Result<Self::Output, ParseError> {
+        Ok(DynData {
+            point_data: finish_section(self.point_data),
+            cell_data: finish_section(self.cell_data),
+        })
+    }
+}
@@ -0,0 +1,248 @@
+//! Dataset-wide metadata (such as the simulation time or Reynolds number) stored in a
+//! `<FieldData>` element
+use crate::prelude::*;
+
+use std::borrow::Cow;
+
+use xml::attribute::Attribute;
+use xml::name::Name;
+use xml::namespace::Namespace;
+
+/// Metadata describing an entire dataset rather than the points or cells of a mesh.
+///
+/// Every entry is written to a `<FieldData>` element directly before the `<Piece>` of the file
+/// and is read back automatically by [`read_vtk`](`crate::read_vtk`), so the entries do not need
+/// to be declared in the [`ParseArray`](`crate::ParseArray`) type of the file. Entries are kept in
+/// insertion order.
+///
+/// ```
+/// let mut field_data = vtk::FieldData::new();
+/// field_data.insert("TimeValue", 0.25);
+/// field_data.insert("Iteration", 1200i64);
+/// field_data.insert("Solver", "streams");
+///
+/// assert_eq!(field_data.get("TimeValue").and_then(vtk::FieldValue::as_float), Some(0.25));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldData {
+    entries: Vec<(String, FieldValue)>,
+}
+
+impl FieldData {
+    /// create a container without any entries
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add an entry, replacing the value of any existing entry with the same name
+    pub fn insert<T: Into<FieldValue>>(&mut self, name: &str, value: T) {
+        let value = value.into();
+
+        match self.entries.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((name.to_string(), value)),
+        }
+    }
+
+    /// fetch the value of the entry `name`, if present
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// iterate over the names and values of all entries in the order they were inserted
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FieldValue)> {
+        self.entries
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// the number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// if there are no entries (and no `<FieldData>` element will be written)
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// write the `<FieldData>` element and all of its (ascii) arrays. Nothing is written if there
+    /// are no entries.
+    pub(crate) fn write<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        write_vtk::open_element(writer, "FieldData")?;
+
+        for (name, value) in self.iter() {
+            let tuples = value.len().to_string();
+
+            writer.write(XmlEvent::StartElement {
+                name: Name::from("DataArray"),
+                attributes: vec![
+                    Attribute::new(Name::from("type"), value.type_name()),
+                    Attribute::new(Name::from("Name"), name),
+                    Attribute::new(Name::from("NumberOfTuples"), &tuples),
+                    Attribute::new(Name::from("format"), "ascii"),
+                ]
+                .into(),
+                namespace: Cow::Owned(Namespace::empty()),
+            })?;
+
+            writer.write(XmlEvent::Characters(&value.to_ascii()))?;
+
+            write_vtk::close_element(writer, "DataArray")?;
+        }
+
+        write_vtk::close_element(writer, "FieldData")?;
+
+        Ok(())
+    }
+}
+
+/// The typed values of a single [`FieldData`] entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// written as a `Float64` array
+    Float(Vec<f64>),
+    /// written as an `Int64` array
+    Int(Vec<i64>),
+    /// written as a `String` array
+    String(Vec<String>),
+}
+
+impl FieldValue {
+    /// the first value of a `FieldValue::Float` entry
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(values) => values.first().copied(),
+            _ => None,
+        }
+    }
+
+    /// the first value of a `FieldValue::Int` entry
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(values) => values.first().copied(),
+            _ => None,
+        }
+    }
+
+    /// the first value of a `FieldValue::String` entry
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(values) => values.first().map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// the number of values (`NumberOfTuples`) in the entry
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Float(values) => values.len(),
+            Self::Int(values) => values.len(),
+            Self::String(values) => values.len(),
+        }
+    }
+
+    /// if the entry does not contain any values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Float(_) => "Float64",
+            Self::Int(_) => "Int64",
+            Self::String(_) => "String",
+        }
+    }
+
+    /// format the values as the contents of an ascii `DataArray`.
+    ///
+    /// vtk stores strings as their (ascii) character codes with each string terminated by a `0`
+    fn to_ascii(&self) -> String {
+        match self {
+            Self::Float(values) => values
+                .iter()
+                .map(|value| {
+                    let mut buffer = ryu::Buffer::new();
+                    buffer.format(*value).to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            Self::Int(values) => values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            Self::String(values) => values
+                .iter()
+                .flat_map(|value| value.bytes().chain(std::iter::once(0)))
+                .map(|byte| byte.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// build the value from the decoded character codes of a `String` array
+    pub(crate) fn from_character_codes(codes: &[u8]) -> Self {
+        let mut strings: Vec<String> = codes
+            .split(|code| *code == 0)
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+            .collect();
+
+        // the terminating `0` of the last string leaves an empty string at the end
+        if codes.last() == Some(&0) {
+            strings.pop();
+        }
+
+        Self::String(strings)
+    }
+}
+
+impl From<f64> for FieldValue {
+    fn from(x: f64) -> Self {
+        Self::Float(vec![x])
+    }
+}
+
+impl From<Vec<f64>> for FieldValue {
+    fn from(x: Vec<f64>) -> Self {
+        Self::Float(x)
+    }
+}
+
+impl From<i64> for FieldValue {
+    fn from(x: i64) -> Self {
+        Self::Int(vec![x])
+    }
+}
+
+impl From<Vec<i64>> for FieldValue {
+    fn from(x: Vec<i64>) -> Self {
+        Self::Int(x)
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(x: &str) -> Self {
+        Self::String(vec![x.to_string()])
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(x: String) -> Self {
+        Self::String(vec![x])
+    }
+}
+
+impl From<Vec<String>> for FieldValue {
+    fn from(x: Vec<String>) -> Self {
+        Self::String(x)
+    }
+}
@@ -0,0 +1,99 @@
+//! Arrays that are only read from a file once they are needed
+use crate::parse::{DataArrayDescription, PartialDataArrayBuffered};
+use crate::prelude::*;
+use crate::DynArray;
+
+/// An array of a file that has not been read yet.
+///
+/// Fields of this type marked with `#[vtk(lazy)]` in a derived [`ParseArray`](crate::ParseArray)
+/// only read the attributes of their `DataArray` when the file is parsed. The values are read
+/// with [`load`](LazyArray::load) from the bytes of the same file, which is cheap for a
+/// [`MappedFile`](crate::MappedFile) since only the pages of the array are read from disk.
+///
+/// Lazy arrays only save memory when the file is mapped with the `mmap` feature.
+/// [`read_vtk`](crate::read_vtk) reads the whole file into memory while it is parsed, and the
+/// file has to be read again for `load`.
+///
+/// ```
+/// #[derive(vtk::ParseArray)]
+/// #[vtk_parse(spans = "vtk::Spans3D")]
+/// pub struct Fields {
+///     rho: vtk::Scalar3D<f64>,
+///     // only the header of this array is read
+///     #[vtk(lazy)]
+///     velocity: vtk::LazyArray,
+///     // not read at all, this is always `Default::default()`
+///     #[vtk(skip)]
+///     notes: Vec<f64>,
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LazyArray {
+    description: DataArrayDescription,
+    header_offset: usize,
+}
+
+impl LazyArray {
+    /// an array described by `description`, whose `<DataArray>` element starts `header_offset`
+    /// bytes from the start of the file. The offset identifies the piece of the array in a file
+    /// with several pieces.
+    pub fn new(description: DataArrayDescription, header_offset: usize) -> Self {
+        Self {
+            description,
+            header_offset,
+        }
+    }
+
+    /// the `Name` of the array
+    pub fn name(&self) -> &str {
+        &self.description.name
+    }
+
+    /// everything that is known about the array without reading it
+    pub fn description(&self) -> &DataArrayDescription {
+        &self.description
+    }
+
+    /// read the values of the array from `bytes`, the contents of the file it was parsed from
+    pub fn load(&self, bytes: &[u8]) -> Result<DynArray, Error> {
+        let xml = parse::xml_elements(bytes);
+
+        let (_, attributes) = parse::read_file_attributes(xml).map_err(|e| {
+            ParseError::from_nom_in(e, bytes, "unsupported attributes of the <VTKFile> element")
+        })?;
+
+        let header = xml
+            .get(self.header_offset..)
+            .filter(|header| header.starts_with(b"<DataArray"))
+            .ok_or_else(|| ParseError::MissingArray {
+                array: self.name().to_string(),
+                offset: None,
+            })?;
+        let (_, array) = parse::parse_dataarray_or_lazy(header, self.name().as_bytes(), 0)
+            .map_err(|e| ParseError::from_nom_in(e, bytes, "could not find the lazy array"))?;
+
+        let array = PartialDataArrayBuffered::new(array, 0);
+        let mut buffers = Vec::new();
+        array.append_to_reader_list(&mut buffers);
+        parse::read_appended_array_buffers(buffers, &bytes[xml.len()..], attributes)?;
+
+        Ok(DynArray {
+            precision: self.description.header.precision(),
+            components: array.components(),
+            values: array.into_buffer(),
+        })
+    }
+
+    /// read the values of the array from `bytes` (see [`load`](LazyArray::load)) into a
+    /// container, such as a [`Scalar3D`](crate::Scalar3D)
+    pub fn load_as<T, SPAN>(&self, bytes: &[u8], spans: &SPAN) -> Result<T, Error>
+    where
+        T: FromBuffer<SPAN>,
+    {
+        let array = self.load(bytes)?;
+        let container = T::from_buffer(array.values, spans, array.components)
+            .map_err(|e| e.with_array_name(self.name()))?;
+
+        Ok(container)
+    }
+}
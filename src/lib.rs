@@ -1,10 +1,22 @@
 #![doc = include_str!("../README.md")]
 
 pub mod array;
+mod buffer;
+mod collection;
+mod compression;
 mod data;
+mod dynamic;
+mod field_data;
+mod lazy;
 pub mod mesh;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod multiblock;
+mod parallel;
 pub mod parse;
 pub mod prelude;
+mod stream;
+mod summary;
 mod traits;
 mod utils;
 mod write_vtk;
@@ -15,19 +27,40 @@ pub use traits::ParseArray;
 pub use traits::ParseMesh;
 pub use traits::Visitor;
 
+pub use buffer::Buffer;
+pub use collection::{parse_pvd, Collection, CollectionEntry};
+pub use compression::Compressor;
 pub use data::VtkData;
-
+pub use dynamic::{parse_dynamic, read_vtk_dynamic, DynArray, DynArrays, DynDomain, DynVtk};
+pub use field_data::{FieldData, FieldValue};
+pub use lazy::LazyArray;
+#[cfg(feature = "mmap")]
+pub use mmap::{read_vtk_mmap, MappedFile};
+pub use multiblock::{parse_vtm, read_vtm, Block, MultiBlock};
+pub use parallel::{parse_pvtr, read_pvtr, ParallelDataset, Stitch};
+pub use parallel::{ArrayDeclaration, ParallelPiece, ParallelRectilinear};
+pub use stream::{StreamArray, StreamError, StreamSchema, StreamWriter};
+pub use summary::{parse_summary, read_summary, FileSummary, PieceSummary, SectionSummary};
+
+pub use mesh::{CellArray, PolyData, PolyMesh, PolySpans};
+pub use mesh::{CellType, Unstructured, UnstructuredMesh, UnstructuredSpans};
 pub use mesh::{Mesh2D, Rectilinear2D, Spans2D};
 pub use mesh::{Mesh3D, Rectilinear3D, Spans3D};
+pub use mesh::{Structured3D, StructuredMesh3D};
+pub use mesh::{Uniform2D, Uniform3D, UniformMesh2D, UniformMesh3D};
 
 pub use array::{Scalar2D, Scalar3D, Vector2D, Vector3D};
 
 pub use traits::*;
 pub use traits::{Array, FromBuffer};
-pub use write_vtk::write_vtk;
-pub use write_vtk::{write_appended_dataarray_header, write_inline_dataarray, Encoding};
+pub use write_vtk::{
+    write_appended_dataarray_header, write_inline_dataarray, ByteOrder, Encoding, GridType,
+    Precision,
+};
+pub use write_vtk::{write_vtk, write_vtk_pieces, write_vtk_with_options, WriteOptions};
 
 pub use parse::read_and_parse as read_vtk;
+pub use parse::read_and_parse_pieces as read_vtk_pieces;
 pub use parse::ParseError;
 //type ParseError = ();
 
@@ -51,6 +84,8 @@ pub enum Error {
     Utf8(#[from] std::string::FromUtf8Error),
     #[error("Could not write XML data to file: `{0}`")]
     XmlWrite(#[from] xml::writer::Error),
+    #[error("The values written to a stream did not match its schema: `{0}`")]
+    Stream(#[from] stream::StreamError),
 }
 
 /// Binary encoding marker type
@@ -81,17 +116,16 @@ impl traits::Encode for Base64 {
     fn is_binary() -> bool {
         false
     }
+
+    fn is_compressible() -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
 mod helpers {
-    use super::write_vtk::Encoding;
-    use super::EventWriter;
     use crate as vtk;
     use crate::prelude::*;
-    use crate::Binary;
-    use std::io::Write;
-    use std::ops::{Add, Div, Sub};
 
     #[derive(Debug, Clone, Default, PartialEq)]
     pub struct SpanData {
@@ -107,23 +141,24 @@ mod helpers {
         fn read_headers<'a>(
             _spans: &vtk::Spans3D,
             buffer: &'a [u8],
-        ) -> nom::IResult<&'a [u8], Self> {
-            let rest = buffer;
-            let (rest, u) = vtk::parse::parse_dataarray_or_lazy(rest, b"u", 0)?;
+        ) -> vtk::parse::IResult<'a, Self> {
+            let (rest, mut point_data) = vtk::parse::read_section_arrays(buffer, b"PointData")?;
+            let u = point_data.take(b"u", 0)?;
             let u = vtk::parse::PartialDataArrayBuffered::new(u, 0);
             let visitor = SpanDataVisitor { u };
             Ok((rest, visitor))
         }
-        fn add_to_appended_reader<'a, 'b>(
+        fn add_to_appended_reader<'a>(
             &'a self,
-            buffer: &'b mut Vec<std::cell::RefMut<'a, vtk::parse::OffsetBuffer>>,
+            buffer: &mut Vec<std::cell::RefMut<'a, vtk::parse::OffsetBuffer>>,
         ) {
             self.u.append_to_reader_list(buffer);
         }
         fn finish(self, spans: &vtk::Spans3D) -> Result<Self::Output, vtk::ParseError> {
             let comp = self.u.components();
             let u = self.u.into_buffer();
-            let u = vtk::FromBuffer::from_buffer(u, &spans, comp);
+            let u = vtk::FromBuffer::from_buffer(u, &spans, comp)
+                .map_err(|e| e.with_array_name("u"))?;
             Ok(SpanData { u })
         }
     }
@@ -136,19 +171,30 @@ mod helpers {
         fn write_array_header<W: std::io::Write>(
             &self,
             writer: &mut vtk::EventWriter<W>,
-            offset: i64,
+            offsets: &[i64],
+            _byte_order: vtk::ByteOrder,
         ) -> Result<(), vtk::Error> {
             let ref_field = &self.u;
             let comps = vtk::Array::components(ref_field);
-            vtk::write_appended_dataarray_header(writer, "u", offset, comps, Precision::Float64)?;
+            vtk::write_appended_dataarray_header(
+                writer,
+                "u",
+                offsets[0],
+                comps,
+                Precision::Float64,
+            )?;
             Ok(())
         }
         fn write_array_appended<W: std::io::Write>(
             &self,
             writer: &mut vtk::EventWriter<W>,
+            byte_order: vtk::ByteOrder,
         ) -> Result<(), vtk::Error> {
-            vtk::Array::write_binary(&self.u, writer, true)?;
+            vtk::Array::write_binary(&self.u, writer, byte_order)?;
             Ok(())
         }
+        fn appended_sizes(&self) -> Vec<usize> {
+            vec![vtk::Array::size_of_elem(&self.u) * vtk::Array::length(&self.u)]
+        }
     }
 }
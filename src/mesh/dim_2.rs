@@ -1,3 +1,4 @@
+use super::dim_3::cell_end;
 use crate::prelude::*;
 use std::marker::PhantomData;
 
@@ -116,13 +117,17 @@ impl Spans2D {
     /// This function panics if there are not 6 `usize` values
     /// separated by a single space each
     pub fn from_span_string(span_string: &str) -> Self {
-        let mut split = span_string.split_ascii_whitespace();
+        <Self as ParseSpan>::from_str(span_string).expect("extent must contain 4 integers")
+    }
 
-        Spans2D {
-            x_start: split.next().unwrap().parse().unwrap(),
-            x_end: split.next().unwrap().parse().unwrap(),
-            y_start: split.next().unwrap().parse().unwrap(),
-            y_end: split.next().unwrap().parse().unwrap(),
+    /// The spans of the cells between the points of these spans, which have one less
+    /// element in each direction. Directions with a single point keep a single cell.
+    pub fn cell_spans(&self) -> Self {
+        Self {
+            x_start: self.x_start,
+            x_end: cell_end(self.x_start, self.x_end),
+            y_start: self.y_start,
+            y_end: cell_end(self.y_start, self.y_end),
         }
     }
 
@@ -137,10 +142,13 @@ impl Spans2D {
     pub fn y_len(&self) -> usize {
         self.y_end - self.y_start + 1
     }
+}
 
-    /// Format the spans into a string that would be written to a vtk file
-    pub(crate) fn to_string(&self) -> String {
-        format!(
+/// Format the spans into a string that would be written to a vtk file
+impl std::fmt::Display for Spans2D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
             "{} {} {} {} 1 1",
             self.x_start, self.x_end, self.y_start, self.y_end
         )
@@ -148,8 +156,20 @@ impl Spans2D {
 }
 
 impl ParseSpan for Spans2D {
-    fn from_str(extent: &str) -> Self {
-        Spans2D::from_span_string(extent)
+    fn from_str(extent: &str) -> Option<Self> {
+        let mut split = extent.split_ascii_whitespace();
+        let mut next = || split.next()?.parse().ok();
+
+        Some(Spans2D {
+            x_start: next()?,
+            x_end: next()?,
+            y_start: next()?,
+            y_end: next()?,
+        })
+    }
+
+    fn cell_spans(&self) -> Self {
+        self.cell_spans()
     }
 }
 
@@ -158,26 +178,50 @@ where
     NUM: Numeric,
 {
     // only write the headers here
-    fn write_mesh_header<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
-        let mut offset = 0;
-
-        write_vtk::write_appended_dataarray_header(writer, "X", offset, 1, NUM::as_precision())?;
-        offset += (std::mem::size_of::<NUM>() * (self.mesh.x_locations.len())) as i64;
-
-        write_vtk::write_appended_dataarray_header(writer, "Y", offset, 1, NUM::as_precision())?;
-        offset += (std::mem::size_of::<NUM>() * (self.mesh.y_locations.len())) as i64;
-
-        write_vtk::write_appended_dataarray_header(writer, "Z", offset, 1, NUM::as_precision())?;
-        //offset += std::mem::size_of::<NUM>() as i64;
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        offsets: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Coordinates")?;
+
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "X",
+            offsets[0],
+            1,
+            NUM::as_precision(),
+        )?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "Y",
+            offsets[1],
+            1,
+            NUM::as_precision(),
+        )?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "Z",
+            offsets[2],
+            1,
+            NUM::as_precision(),
+        )?;
+
+        write_vtk::close_element(writer, "Coordinates")?;
 
         Ok(())
     }
 
     //
-    fn write_mesh_appended<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
-        self.mesh.x_locations.write_binary(writer, false)?;
-        self.mesh.y_locations.write_binary(writer, false)?;
-        vec![NUM::ZERO].write_binary(writer, false)?;
+    fn write_mesh_appended<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        self.mesh.x_locations.write_binary(writer, byte_order)?;
+        self.mesh.y_locations.write_binary(writer, byte_order)?;
+        vec![NUM::ZERO].write_binary(writer, byte_order)?;
         Ok(())
     }
 
@@ -185,14 +229,16 @@ where
         self.spans.to_string()
     }
 
-    fn mesh_bytes(&self) -> usize {
-        let mut offset = 0;
-
-        offset += std::mem::size_of::<NUM>() * (self.mesh.x_locations.len());
-        offset += std::mem::size_of::<NUM>() * (self.mesh.y_locations.len());
-        offset += std::mem::size_of::<NUM>();
+    fn mesh_appended_sizes(&self) -> Vec<usize> {
+        vec![
+            std::mem::size_of::<NUM>() * self.mesh.x_locations.len(),
+            std::mem::size_of::<NUM>() * self.mesh.y_locations.len(),
+            std::mem::size_of::<NUM>(),
+        ]
+    }
 
-        offset
+    fn grid_type(&self) -> GridType {
+        GridType::RectilinearGrid
     }
 }
 
@@ -201,16 +247,29 @@ where
     NUM: Numeric,
 {
     // only write the headers here
-    fn write_mesh_header<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Coordinates")?;
+
         self.mesh.x_locations.write_ascii(writer, "X")?;
         self.mesh.y_locations.write_ascii(writer, "Y")?;
         vec![NUM::ZERO].write_ascii(writer, "Z")?;
 
+        write_vtk::close_element(writer, "Coordinates")?;
+
         Ok(())
     }
 
     //
-    fn write_mesh_appended<W: Write>(&self, _: &mut EventWriter<W>) -> Result<(), Error> {
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
         Ok(())
     }
 
@@ -218,14 +277,8 @@ where
         self.spans.to_string()
     }
 
-    fn mesh_bytes(&self) -> usize {
-        let mut offset = 0;
-
-        offset += std::mem::size_of::<NUM>() * (self.mesh.x_locations.len());
-        offset += std::mem::size_of::<NUM>() * (self.mesh.y_locations.len());
-        offset += std::mem::size_of::<NUM>();
-
-        offset
+    fn grid_type(&self) -> GridType {
+        GridType::RectilinearGrid
     }
 }
 
@@ -243,10 +296,12 @@ pub struct Mesh2DVisitor {
 impl Visitor<Spans2D> for Mesh2DVisitor {
     type Output = Mesh2D<f64, Binary>;
 
-    fn read_headers<'a>(spans: &Spans2D, buffer: &'a [u8]) -> IResult<&'a [u8], Self> {
-        let (rest, x) = parse::parse_dataarray_or_lazy(buffer, b"X", spans.x_len())?;
-        let (rest, y) = parse::parse_dataarray_or_lazy(rest, b"Y", spans.y_len())?;
-        let (rest, z) = parse::parse_dataarray_or_lazy(rest, b"Z", 1)?;
+    fn read_headers<'a>(spans: &Spans2D, buffer: &'a [u8]) -> IResult<'a, Self> {
+        let (rest, _) = parse::skip_to_section(buffer, b"Coordinates")?;
+
+        let (rest, x) = parse::parse_next_dataarray_or_lazy(rest, spans.x_len())?;
+        let (rest, y) = parse::parse_next_dataarray_or_lazy(rest, spans.y_len())?;
+        let (rest, z) = parse::parse_next_dataarray_or_lazy(rest, 1)?;
 
         let x_locations = parse::PartialDataArrayBuffered::new(x, spans.x_len());
         let y_locations = parse::PartialDataArrayBuffered::new(y, spans.y_len());
@@ -261,19 +316,18 @@ impl Visitor<Spans2D> for Mesh2DVisitor {
         Ok((rest, visitor))
     }
 
-    fn add_to_appended_reader<'a, 'b>(
-        &'a self,
-        buffer: &'b mut Vec<RefMut<'a, parse::OffsetBuffer>>,
-    ) {
+    fn add_to_appended_reader<'a>(&'a self, buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {
         self.x_locations.append_to_reader_list(buffer);
         self.y_locations.append_to_reader_list(buffer);
         self.z_locations.append_to_reader_list(buffer);
     }
 
     fn finish(self, _spans: &Spans2D) -> Result<Self::Output, ParseError> {
-        let x_locations = self.x_locations.into_buffer();
-        let y_locations = self.y_locations.into_buffer();
-        //let z_locations = self.z_locations.into_buffer();
+        let x_locations = self.x_locations.into_buffer().into_vec();
+        let x_locations = x_locations.map_err(|e| e.with_array_name("X"))?;
+        let y_locations = self.y_locations.into_buffer().into_vec();
+        let y_locations = y_locations.map_err(|e| e.with_array_name("Y"))?;
+        //let z_locations = self.z_locations.into_buffer().into_vec();
 
         Ok(Mesh2D::new(x_locations, y_locations))
     }
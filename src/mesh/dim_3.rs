@@ -136,15 +136,25 @@ impl Spans3D {
     /// This function panics if there are not 6 `usize` values
     /// separated by a single space each
     pub fn from_span_string(span_string: &str) -> Self {
-        let mut split = span_string.split_ascii_whitespace();
-
-        Spans3D {
-            x_start: split.next().unwrap().parse().unwrap(),
-            x_end: split.next().unwrap().parse().unwrap(),
-            y_start: split.next().unwrap().parse().unwrap(),
-            y_end: split.next().unwrap().parse().unwrap(),
-            z_start: split.next().unwrap().parse().unwrap(),
-            z_end: split.next().unwrap().parse().unwrap(),
+        <Self as ParseSpan>::from_str(span_string).expect("extent must contain 6 integers")
+    }
+
+    /// The spans of the cells between the points of these spans, which have one less
+    /// element in each direction. Directions with a single point keep a single cell.
+    ///
+    /// # Example
+    /// ```
+    /// let cells = vtk::Spans3D::new(10, 20, 1).cell_spans();
+    /// assert_eq!((cells.x_len(), cells.y_len(), cells.z_len()), (9, 19, 1));
+    /// ```
+    pub fn cell_spans(&self) -> Self {
+        Self {
+            x_start: self.x_start,
+            x_end: cell_end(self.x_start, self.x_end),
+            y_start: self.y_start,
+            y_end: cell_end(self.y_start, self.y_end),
+            z_start: self.z_start,
+            z_end: cell_end(self.z_start, self.z_end),
         }
     }
 
@@ -165,10 +175,22 @@ impl Spans3D {
     pub fn z_len(&self) -> usize {
         self.z_end - self.z_start + 1
     }
+}
+
+/// the last index of the cells between the points `start..=end`
+pub(crate) fn cell_end(start: usize, end: usize) -> usize {
+    if end > start {
+        end - 1
+    } else {
+        end
+    }
+}
 
-    /// Format the spans into a string that would be written to a vtk file
-    pub(crate) fn to_string(&self) -> String {
-        format!(
+/// Format the spans into a string that would be written to a vtk file
+impl std::fmt::Display for Spans3D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
             "{} {} {} {} {} {}",
             self.x_start, self.x_end, self.y_start, self.y_end, self.z_start, self.z_end
         )
@@ -176,8 +198,22 @@ impl Spans3D {
 }
 
 impl ParseSpan for Spans3D {
-    fn from_str(extent: &str) -> Self {
-        Spans3D::from_span_string(extent)
+    fn from_str(extent: &str) -> Option<Self> {
+        let mut split = extent.split_ascii_whitespace();
+        let mut next = || split.next()?.parse().ok();
+
+        Some(Spans3D {
+            x_start: next()?,
+            x_end: next()?,
+            y_start: next()?,
+            y_end: next()?,
+            z_start: next()?,
+            z_end: next()?,
+        })
+    }
+
+    fn cell_spans(&self) -> Self {
+        self.cell_spans()
     }
 }
 
@@ -186,26 +222,50 @@ where
     NUM: Numeric,
 {
     // only write the headers here
-    fn write_mesh_header<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
-        let mut offset = 0;
-
-        write_vtk::write_appended_dataarray_header(writer, "X", offset, 1, NUM::as_precision())?;
-        offset += (std::mem::size_of::<NUM>() * (self.mesh.x_locations.len())) as i64;
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        offsets: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Coordinates")?;
+
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "X",
+            offsets[0],
+            1,
+            NUM::as_precision(),
+        )?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "Y",
+            offsets[1],
+            1,
+            NUM::as_precision(),
+        )?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "Z",
+            offsets[2],
+            1,
+            NUM::as_precision(),
+        )?;
+
+        write_vtk::close_element(writer, "Coordinates")?;
 
-        write_vtk::write_appended_dataarray_header(writer, "Y", offset, 1, NUM::as_precision())?;
-        offset += (std::mem::size_of::<NUM>() * (self.mesh.y_locations.len())) as i64;
-
-        write_vtk::write_appended_dataarray_header(writer, "Z", offset, 1, NUM::as_precision())?;
-        //offset += (std::mem::size_of::<NUM>() * (self.z_locations.len())) as i64;
-        //
         Ok(())
     }
 
     //
-    fn write_mesh_appended<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
-        self.mesh.x_locations.write_binary(writer, false)?;
-        self.mesh.y_locations.write_binary(writer, false)?;
-        self.mesh.z_locations.write_binary(writer, false)?;
+    fn write_mesh_appended<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        self.mesh.x_locations.write_binary(writer, byte_order)?;
+        self.mesh.y_locations.write_binary(writer, byte_order)?;
+        self.mesh.z_locations.write_binary(writer, byte_order)?;
         Ok(())
     }
 
@@ -213,14 +273,16 @@ where
         self.spans.to_string()
     }
 
-    fn mesh_bytes(&self) -> usize {
-        let mut offset = 0;
-
-        offset += std::mem::size_of::<NUM>() * (self.mesh.x_locations.len());
-        offset += std::mem::size_of::<NUM>() * (self.mesh.y_locations.len());
-        offset += std::mem::size_of::<NUM>() * (self.mesh.z_locations.len());
+    fn mesh_appended_sizes(&self) -> Vec<usize> {
+        vec![
+            std::mem::size_of::<NUM>() * self.mesh.x_locations.len(),
+            std::mem::size_of::<NUM>() * self.mesh.y_locations.len(),
+            std::mem::size_of::<NUM>() * self.mesh.z_locations.len(),
+        ]
+    }
 
-        offset
+    fn grid_type(&self) -> GridType {
+        GridType::RectilinearGrid
     }
 }
 
@@ -229,16 +291,29 @@ where
     NUM: Numeric,
 {
     // only write the headers here
-    fn write_mesh_header<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Coordinates")?;
+
         self.mesh.x_locations.write_ascii(writer, "X")?;
         self.mesh.y_locations.write_ascii(writer, "Y")?;
         self.mesh.z_locations.write_ascii(writer, "Z")?;
 
+        write_vtk::close_element(writer, "Coordinates")?;
+
         Ok(())
     }
 
     //
-    fn write_mesh_appended<W: Write>(&self, _: &mut EventWriter<W>) -> Result<(), Error> {
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
         Ok(())
     }
 
@@ -246,14 +321,8 @@ where
         self.spans.to_string()
     }
 
-    fn mesh_bytes(&self) -> usize {
-        let mut offset = 0;
-
-        offset += std::mem::size_of::<NUM>() * (self.mesh.x_locations.len());
-        offset += std::mem::size_of::<NUM>() * (self.mesh.y_locations.len());
-        offset += std::mem::size_of::<NUM>() * (self.mesh.z_locations.len());
-
-        offset
+    fn grid_type(&self) -> GridType {
+        GridType::RectilinearGrid
     }
 }
 
@@ -271,10 +340,14 @@ pub struct Mesh3DVisitor {
 impl Visitor<Spans3D> for Mesh3DVisitor {
     type Output = Mesh3D<f64, Binary>;
 
-    fn read_headers<'a>(spans: &Spans3D, buffer: &'a [u8]) -> IResult<&'a [u8], Self> {
-        let (rest, x) = parse::parse_dataarray_or_lazy(buffer, b"X", spans.x_len())?;
-        let (rest, y) = parse::parse_dataarray_or_lazy(rest, b"Y", spans.y_len())?;
-        let (rest, z) = parse::parse_dataarray_or_lazy(rest, b"Z", spans.z_len())?;
+    fn read_headers<'a>(spans: &Spans3D, buffer: &'a [u8]) -> IResult<'a, Self> {
+        // the coordinate arrays are always written in x, y, z order, but their names
+        // depend on the program that wrote the file
+        let (rest, _) = parse::skip_to_section(buffer, b"Coordinates")?;
+
+        let (rest, x) = parse::parse_next_dataarray_or_lazy(rest, spans.x_len())?;
+        let (rest, y) = parse::parse_next_dataarray_or_lazy(rest, spans.y_len())?;
+        let (rest, z) = parse::parse_next_dataarray_or_lazy(rest, spans.z_len())?;
 
         let x_locations = parse::PartialDataArrayBuffered::new(x, spans.x_len());
         let y_locations = parse::PartialDataArrayBuffered::new(y, spans.y_len());
@@ -289,28 +362,30 @@ impl Visitor<Spans3D> for Mesh3DVisitor {
         Ok((rest, visitor))
     }
 
-    fn add_to_appended_reader<'a, 'b>(
-        &'a self,
-        buffer: &'b mut Vec<RefMut<'a, parse::OffsetBuffer>>,
-    ) {
+    fn add_to_appended_reader<'a>(&'a self, buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {
         self.x_locations.append_to_reader_list(buffer);
         self.y_locations.append_to_reader_list(buffer);
         self.z_locations.append_to_reader_list(buffer);
     }
 
     fn finish(self, _spans: &Spans3D) -> Result<Self::Output, ParseError> {
-        let x_locations = self.x_locations.into_buffer();
-        let y_locations = self.y_locations.into_buffer();
-        let z_locations = self.z_locations.into_buffer();
+        let x_locations = self.x_locations.into_buffer().into_vec();
+        let x_locations = x_locations.map_err(|e| e.with_array_name("X"))?;
+        let y_locations = self.y_locations.into_buffer().into_vec();
+        let y_locations = y_locations.map_err(|e| e.with_array_name("Y"))?;
+        let z_locations = self.z_locations.into_buffer().into_vec();
+        let z_locations = z_locations.map_err(|e| e.with_array_name("Z"))?;
 
         Ok(Mesh3D::new(x_locations, y_locations, z_locations))
     }
 }
 
 #[cfg(test)]
+#[allow(dead_code)]
 struct ArrayContainer;
 
 #[cfg(test)]
+#[allow(dead_code)]
 struct ArrayContainerVisitor;
 
 #[cfg(test)]
@@ -322,18 +397,15 @@ impl ParseArray for ArrayContainer {
 impl<T> Visitor<T> for ArrayContainerVisitor {
     type Output = ArrayContainer;
 
-    fn read_headers<'a>(spans: &T, buffer: &'a [u8]) -> IResult<&'a [u8], Self> {
+    fn read_headers<'a>(_spans: &T, _buffer: &'a [u8]) -> IResult<'a, Self> {
         unimplemented!()
     }
 
-    fn add_to_appended_reader<'a, 'b>(
-        &'a self,
-        buffer: &'b mut Vec<RefMut<'a, parse::OffsetBuffer>>,
-    ) {
+    fn add_to_appended_reader<'a>(&'a self, _buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {
         unimplemented!()
     }
 
-    fn finish(self, spans: &T) -> Result<Self::Output, ParseError> {
+    fn finish(self, _spans: &T) -> Result<Self::Output, ParseError> {
         unimplemented!()
     }
 }
@@ -342,6 +414,6 @@ impl<T> Visitor<T> for ArrayContainerVisitor {
 fn compile_dim3_write() {
     let data = Mesh3D::<f64, Binary>::new(vec![], vec![], vec![]);
     let spans = Spans3D::new(1, 1, 1);
-    let domain = Rectilinear3D::new(data, spans);
+    let _domain = Rectilinear3D::new(data, spans);
     //let data =
 }
@@ -12,6 +12,19 @@
 //! Objects implementing the [Domain](`crate::Domain`) trait (such as [`Rectilinear3D`])
 //!can be written to files with the [write_vtk](`crate::write_vtk()`) function.
 //!
+//! If the points of your domain are uniformly spaced, [`Uniform3D`] and [`Uniform2D`]
+//! describe the mesh with only an origin and a spacing. These domains are written as
+//! `ImageData` (`.vti`) files which are smaller and faster for ParaView to process.
+//!
+//! Curvilinear meshes (such as stretched, body-fitted grids) cannot be described by
+//! separable coordinate arrays. [`Structured3D`] stores the location of every point and is
+//! written as a `StructuredGrid` (`.vts`).
+//!
+//! Meshes without any structured topology (such as surface meshes or the leaves of an
+//! adaptive mesh) are described by [`Unstructured`], which stores points together with the
+//! connectivity of each cell and is written as an `UnstructuredGrid` (`.vtu`). Particles,
+//! streamlines and surfaces are better described by [`PolyData`] (`.vtp`).
+//!
 //!
 //! ## Defining your own domain for writing files
 //!
@@ -49,9 +62,19 @@
 
 mod dim_2;
 mod dim_3;
+mod poly_data;
+mod structured_3d;
+mod uniform_2d;
+mod uniform_3d;
+mod unstructured;
 
 pub use dim_2::{Mesh2D, Rectilinear2D, Spans2D};
 pub use dim_3::{Mesh3D, Rectilinear3D, Spans3D};
+pub use poly_data::{CellArray, PolyData, PolyMesh, PolySpans};
+pub use structured_3d::{Structured3D, StructuredMesh3D};
+pub use uniform_2d::{Uniform2D, UniformMesh2D};
+pub use uniform_3d::{Uniform3D, UniformMesh3D};
+pub use unstructured::{CellType, Unstructured, UnstructuredMesh, UnstructuredSpans};
 
 #[doc(hidden)]
 pub use dim_3::Mesh3DVisitor;
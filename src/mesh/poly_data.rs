@@ -0,0 +1,604 @@
+use crate::array::check_length;
+use crate::prelude::*;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Full information on a domain of points, lines and surfaces. If you are writing
+/// a vtk file, this is a candidate type to store in the `domain` field
+/// of [VtkData](`crate::VtkData`)
+///
+/// This domain is written as `PolyData` (`.vtp`), which is well suited for particles,
+/// streamlines and extracted surfaces. Data arrays for this domain should be stored as flat
+/// containers (`Vec<f64>`) with one value (or one value per component) per point.
+pub struct PolyData<NUM, Encoding> {
+    pub spans: PolySpans,
+    pub mesh: PolyMesh<NUM, Encoding>,
+}
+
+impl<NUM, Encoding> PolyData<NUM, Encoding>
+where
+    NUM: Numeric,
+{
+    /// create a new domain from mesh information. The number of points and cells is
+    /// taken from the mesh.
+    pub fn new(mesh: PolyMesh<NUM, Encoding>) -> PolyData<NUM, Encoding> {
+        let spans = PolySpans {
+            number_of_points: mesh.points.len(),
+            number_of_verts: mesh.verts.len(),
+            number_of_lines: mesh.lines.len(),
+            number_of_strips: mesh.strips.len(),
+            number_of_polys: mesh.polys.len(),
+        };
+
+        Self { mesh, spans }
+    }
+}
+
+// from impl is required for generic parsing
+impl<NUM, T> From<(PolyMesh<NUM, T>, PolySpans)> for PolyData<NUM, T>
+where
+    NUM: Numeric,
+{
+    fn from(x: (PolyMesh<NUM, T>, PolySpans)) -> Self {
+        Self {
+            mesh: x.0,
+            spans: x.1,
+        }
+    }
+}
+
+/// A list of cells, each made of a variable number of points
+///
+/// The point indices of every cell are concatenated in `connectivity`, and `offsets` contains
+/// the index in `connectivity` one past the end of each cell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellArray {
+    pub connectivity: Vec<i64>,
+    pub offsets: Vec<i64>,
+}
+
+impl CellArray {
+    /// Construct the cells from the raw connectivity and offsets arrays
+    pub fn new(connectivity: Vec<i64>, offsets: Vec<i64>) -> Self {
+        Self {
+            connectivity,
+            offsets,
+        }
+    }
+
+    /// add a cell made of the points at indices `point_ids`
+    pub fn push(&mut self, point_ids: &[i64]) {
+        self.connectivity.extend_from_slice(point_ids);
+        self.offsets.push(self.connectivity.len() as i64);
+    }
+
+    /// the number of cells
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// if there are no cells
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+/// Describes the points and cells of a `PolyData` mesh
+///
+/// `verts` are cells made of single points (such as particles), `lines` are
+/// polylines (such as streamlines), and `strips` / `polys` are triangle strips
+/// and polygons that make up surfaces.
+///
+/// ## Encoding Type
+///
+/// This type carries type level information on what kind of encoding to use with the mesh.
+/// See [`Mesh3D`](crate::Mesh3D) for more information.
+///
+/// ## Example
+///
+/// ```
+/// // three particles
+/// let points = vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]];
+/// let mut mesh = vtk::PolyMesh::<f64, vtk::Binary>::new(points);
+///
+/// for i in 0..3 {
+///     mesh.verts.push(&[i]);
+/// }
+///
+/// let domain = vtk::PolyData::new(mesh);
+/// assert_eq!(domain.spans.number_of_verts, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolyMesh<NUM, Encoding> {
+    pub points: Vec<[NUM; 3]>,
+    pub verts: CellArray,
+    pub lines: CellArray,
+    pub strips: CellArray,
+    pub polys: CellArray,
+    _marker: PhantomData<Encoding>,
+}
+
+impl<NUM, Encoding> PolyMesh<NUM, Encoding> {
+    /// Constructor for a mesh of points without any cells. Encoding can easily
+    /// be specified with a turbofish or type inference in later code.
+    pub fn new(points: Vec<[NUM; 3]>) -> PolyMesh<NUM, Encoding> {
+        Self {
+            points,
+            verts: CellArray::default(),
+            lines: CellArray::default(),
+            strips: CellArray::default(),
+            polys: CellArray::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// swap encodings for this type. This does not change any
+    /// of the underlying data
+    pub fn change_encoding<T>(self) -> PolyMesh<NUM, T> {
+        let PolyMesh {
+            points,
+            verts,
+            lines,
+            strips,
+            polys,
+            _marker,
+        } = self;
+
+        PolyMesh {
+            points,
+            verts,
+            lines,
+            strips,
+            polys,
+            _marker: PhantomData::<T>,
+        }
+    }
+
+    /// the name of each section of cells in the file along with the cells in the section
+    fn sections(&self) -> [(&'static str, &CellArray); 4] {
+        [
+            ("Verts", &self.verts),
+            ("Lines", &self.lines),
+            ("Strips", &self.strips),
+            ("Polys", &self.polys),
+        ]
+    }
+}
+
+impl<T, V, NUM> PartialEq<PolyMesh<NUM, V>> for PolyMesh<NUM, T>
+where
+    NUM: PartialEq,
+{
+    fn eq(&self, other: &PolyMesh<NUM, V>) -> bool {
+        self.points == other.points
+            && self.verts == other.verts
+            && self.lines == other.lines
+            && self.strips == other.strips
+            && self.polys == other.polys
+    }
+}
+
+/// Describes the size of a `PolyData` domain with the number of points and
+/// the number of cells in each section
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolySpans {
+    pub number_of_points: usize,
+    pub number_of_verts: usize,
+    pub number_of_lines: usize,
+    pub number_of_strips: usize,
+    pub number_of_polys: usize,
+}
+
+/// Format the spans as `"points verts lines strips polys"`
+impl std::fmt::Display for PolySpans {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.number_of_points,
+            self.number_of_verts,
+            self.number_of_lines,
+            self.number_of_strips,
+            self.number_of_polys
+        )
+    }
+}
+
+impl ParseSpan for PolySpans {
+    /// parse the spans from a string in the form `"points verts lines strips polys"`,
+    /// returning `None` if there are not 5 `usize` values
+    fn from_str(extent: &str) -> Option<Self> {
+        let mut split = extent.split_ascii_whitespace();
+        let mut next = || split.next()?.parse().ok();
+
+        Some(PolySpans {
+            number_of_points: next()?,
+            number_of_verts: next()?,
+            number_of_lines: next()?,
+            number_of_strips: next()?,
+            number_of_polys: next()?,
+        })
+    }
+
+    fn read_spans(xml_bytes: &[u8]) -> IResult<'_, Self> {
+        let (rest, sizes) = parse::read_piece_sizes(
+            xml_bytes,
+            &[
+                b"NumberOfPoints",
+                b"NumberOfVerts",
+                b"NumberOfLines",
+                b"NumberOfStrips",
+                b"NumberOfPolys",
+            ],
+        )?;
+
+        let spans = PolySpans {
+            number_of_points: sizes[0],
+            number_of_verts: sizes[1],
+            number_of_lines: sizes[2],
+            number_of_strips: sizes[3],
+            number_of_polys: sizes[4],
+        };
+
+        Ok((rest, spans))
+    }
+
+    /// cell data arrays hold a value for every vert, line, strip and poly, so their spans have
+    /// a point for every cell
+    fn cell_spans(&self) -> Self {
+        PolySpans {
+            number_of_points: self.number_of_verts
+                + self.number_of_lines
+                + self.number_of_strips
+                + self.number_of_polys,
+            ..self.clone()
+        }
+    }
+}
+
+/// the offset of the next appended array. `offsets` is expected to hold an offset for every size
+/// of [`mesh_appended_sizes`](Domain::mesh_appended_sizes), which is checked here because the
+/// number of arrays depends on which sections have cells
+fn next_offset<'a>(offsets: &mut impl Iterator<Item = &'a i64>) -> Result<i64, Error> {
+    offsets.next().copied().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "an appended array of the poly data mesh has no offset",
+        )
+        .into()
+    })
+}
+
+impl<NUM> Domain<Binary> for PolyData<NUM, Binary>
+where
+    NUM: Numeric,
+{
+    // only write the headers here
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        offsets: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        let mut offsets = offsets.iter();
+
+        write_vtk::open_element(writer, "Points")?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "Points",
+            next_offset(&mut offsets)?,
+            3,
+            NUM::as_precision(),
+        )?;
+        write_vtk::close_element(writer, "Points")?;
+
+        // sections without any cells are not written
+        for (section, cells) in self.mesh.sections() {
+            if cells.is_empty() {
+                continue;
+            }
+
+            write_vtk::open_element(writer, section)?;
+            write_vtk::write_appended_dataarray_header(
+                writer,
+                "connectivity",
+                next_offset(&mut offsets)?,
+                1,
+                Precision::Int64,
+            )?;
+            write_vtk::write_appended_dataarray_header(
+                writer,
+                "offsets",
+                next_offset(&mut offsets)?,
+                1,
+                Precision::Int64,
+            )?;
+            write_vtk::close_element(writer, section)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        self.mesh.points.write_binary(writer, byte_order)?;
+
+        for (_, cells) in self.mesh.sections() {
+            if cells.is_empty() {
+                continue;
+            }
+
+            cells.connectivity.write_binary(writer, byte_order)?;
+            cells.offsets.write_binary(writer, byte_order)?;
+        }
+
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn mesh_appended_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![std::mem::size_of::<NUM>() * 3 * self.mesh.points.len()];
+
+        for (_, cells) in self.mesh.sections() {
+            if cells.is_empty() {
+                continue;
+            }
+
+            sizes.push(std::mem::size_of::<i64>() * cells.connectivity.len());
+            sizes.push(std::mem::size_of::<i64>() * cells.offsets.len());
+        }
+
+        sizes
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::PolyData
+    }
+
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        piece_attributes(&self.spans)
+    }
+}
+
+impl<NUM> Domain<Base64> for PolyData<NUM, Base64>
+where
+    NUM: Numeric,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+        self.mesh
+            .points
+            .write_base64(writer, "Points", byte_order)?;
+        write_vtk::close_element(writer, "Points")?;
+
+        for (section, cells) in self.mesh.sections() {
+            if cells.is_empty() {
+                continue;
+            }
+
+            write_vtk::open_element(writer, section)?;
+            cells
+                .connectivity
+                .write_base64(writer, "connectivity", byte_order)?;
+            cells.offsets.write_base64(writer, "offsets", byte_order)?;
+            write_vtk::close_element(writer, section)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::PolyData
+    }
+
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        piece_attributes(&self.spans)
+    }
+}
+
+impl<NUM> Domain<Ascii> for PolyData<NUM, Ascii>
+where
+    NUM: Numeric,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+        self.mesh.points.write_ascii(writer, "Points")?;
+        write_vtk::close_element(writer, "Points")?;
+
+        for (section, cells) in self.mesh.sections() {
+            if cells.is_empty() {
+                continue;
+            }
+
+            write_vtk::open_element(writer, section)?;
+            cells.connectivity.write_ascii(writer, "connectivity")?;
+            cells.offsets.write_ascii(writer, "offsets")?;
+            write_vtk::close_element(writer, section)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::PolyData
+    }
+
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        piece_attributes(&self.spans)
+    }
+}
+
+fn piece_attributes(spans: &PolySpans) -> Vec<(&'static str, String)> {
+    vec![
+        ("NumberOfPoints", spans.number_of_points.to_string()),
+        ("NumberOfVerts", spans.number_of_verts.to_string()),
+        ("NumberOfLines", spans.number_of_lines.to_string()),
+        ("NumberOfStrips", spans.number_of_strips.to_string()),
+        ("NumberOfPolys", spans.number_of_polys.to_string()),
+    ]
+}
+
+impl<T> ParseMesh for PolyMesh<f64, T> {
+    type Visitor = PolyMeshVisitor;
+}
+
+#[doc(hidden)]
+pub struct PolyMeshVisitor {
+    points: parse::PartialDataArrayBuffered,
+    verts: Option<CellArrayVisitor>,
+    lines: Option<CellArrayVisitor>,
+    strips: Option<CellArrayVisitor>,
+    polys: Option<CellArrayVisitor>,
+}
+
+/// partially parsed connectivity and offsets of a single section of cells
+struct CellArrayVisitor {
+    connectivity: parse::PartialDataArrayBuffered,
+    offsets: parse::PartialDataArrayBuffered,
+}
+
+impl CellArrayVisitor {
+    /// read the section if there are any cells in it. Sections without any
+    /// cells may be left out of the file
+    fn read_headers<'a>(
+        buffer: &'a [u8],
+        section: &[u8],
+        number_of_cells: usize,
+    ) -> IResult<'a, Option<Self>> {
+        if number_of_cells == 0 {
+            return Ok((buffer, None));
+        }
+
+        let (rest, mut cells) = parse::read_section_arrays(buffer, section)?;
+        let connectivity = cells.take(b"connectivity", 0)?;
+        let offsets = cells.take(b"offsets", number_of_cells)?;
+
+        let visitor = Self {
+            connectivity: parse::PartialDataArrayBuffered::new(connectivity, 0),
+            offsets: parse::PartialDataArrayBuffered::new(offsets, number_of_cells),
+        };
+
+        Ok((rest, Some(visitor)))
+    }
+
+    /// the cells of the section, which must match the `NumberOf*` attribute of the section
+    fn finish(visitor: Option<Self>, number_of_cells: usize) -> Result<CellArray, ParseError> {
+        let visitor = match visitor {
+            Some(visitor) => visitor,
+            None => return Ok(CellArray::default()),
+        };
+
+        let offsets = visitor.offsets.into_buffer();
+        check_length(&offsets, number_of_cells).map_err(|e| e.with_array_name("offsets"))?;
+        let offsets: Vec<i64> = offsets
+            .into_vec()
+            .map_err(|e| e.with_array_name("offsets"))?;
+
+        // the last offset is the end of the last cell in `connectivity`
+        let connectivity = visitor.connectivity.into_buffer();
+        let connectivity_len = offsets
+            .last()
+            .map_or(0, |&end| usize::try_from(end).unwrap_or(usize::MAX));
+        check_length(&connectivity, connectivity_len)
+            .map_err(|e| e.with_array_name("connectivity"))?;
+
+        let connectivity = connectivity
+            .into_vec()
+            .map_err(|e| e.with_array_name("connectivity"))?;
+
+        Ok(CellArray::new(connectivity, offsets))
+    }
+}
+
+impl Visitor<PolySpans> for PolyMeshVisitor {
+    type Output = PolyMesh<f64, Binary>;
+
+    fn read_headers<'a>(spans: &PolySpans, buffer: &'a [u8]) -> IResult<'a, Self> {
+        let points_len = 3 * spans.number_of_points;
+
+        let (rest, _) = parse::skip_to_section(buffer, b"Points")?;
+        let (rest, points) = parse::parse_next_dataarray_or_lazy(rest, points_len)?;
+
+        // every section is found from the start of the piece
+        let (_, verts) = CellArrayVisitor::read_headers(buffer, b"Verts", spans.number_of_verts)?;
+        let (_, lines) = CellArrayVisitor::read_headers(buffer, b"Lines", spans.number_of_lines)?;
+        let (_, strips) =
+            CellArrayVisitor::read_headers(buffer, b"Strips", spans.number_of_strips)?;
+        let (_, polys) = CellArrayVisitor::read_headers(buffer, b"Polys", spans.number_of_polys)?;
+
+        let visitor = Self {
+            points: parse::PartialDataArrayBuffered::new(points, points_len),
+            verts,
+            lines,
+            strips,
+            polys,
+        };
+
+        Ok((rest, visitor))
+    }
+
+    fn add_to_appended_reader<'a>(&'a self, buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {
+        self.points.append_to_reader_list(buffer);
+
+        for cells in [&self.verts, &self.lines, &self.strips, &self.polys]
+            .into_iter()
+            .flatten()
+        {
+            cells.connectivity.append_to_reader_list(buffer);
+            cells.offsets.append_to_reader_list(buffer);
+        }
+    }
+
+    fn finish(self, spans: &PolySpans) -> Result<Self::Output, ParseError> {
+        let points: Vec<[f64; 3]> = FromBuffer::from_buffer(self.points.into_buffer(), spans, 3)
+            .map_err(|e| e.with_array_name("Points"))?;
+
+        let mut mesh = PolyMesh::new(points);
+        mesh.verts = CellArrayVisitor::finish(self.verts, spans.number_of_verts)?;
+        mesh.lines = CellArrayVisitor::finish(self.lines, spans.number_of_lines)?;
+        mesh.strips = CellArrayVisitor::finish(self.strips, spans.number_of_strips)?;
+        mesh.polys = CellArrayVisitor::finish(self.polys, spans.number_of_polys)?;
+
+        Ok(mesh)
+    }
+}
@@ -0,0 +1,239 @@
+use super::Spans3D;
+use crate::prelude::*;
+use crate::Vector3D;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Full information on a 3D curvilinear computational domain. If you are writing
+/// a vtk file, this is a candidate type to store in the `domain` field
+/// of [VtkData](`crate::VtkData`)
+///
+/// Unlike [`Rectilinear3D`](crate::Rectilinear3D), the location of every point is stored
+/// explicitly, so stretched and body-fitted meshes can be described. This domain is written as
+/// a `StructuredGrid` (`.vts`).
+pub struct Structured3D<NUM, Encoding> {
+    pub spans: Spans3D,
+    pub mesh: StructuredMesh3D<NUM, Encoding>,
+}
+
+impl<NUM, Encoding> Structured3D<NUM, Encoding>
+where
+    NUM: Numeric,
+{
+    /// create a new domain from mesh information and span information.
+    pub fn new(
+        mesh: StructuredMesh3D<NUM, Encoding>,
+        spans: Spans3D,
+    ) -> Structured3D<NUM, Encoding> {
+        Self { mesh, spans }
+    }
+}
+
+// from impl is required for generic parsing
+impl<NUM, T> From<(StructuredMesh3D<NUM, T>, Spans3D)> for Structured3D<NUM, T>
+where
+    NUM: Numeric,
+{
+    fn from(x: (StructuredMesh3D<NUM, T>, Spans3D)) -> Self {
+        Self::new(x.0, x.1)
+    }
+}
+
+/// Describes the computational stencil for 3D curvilinear geometry
+///
+/// `points` has the shape `(3, nx, ny, nz)` where the first axis holds the X / Y / Z
+/// location of each point, in the same layout as a [`Vector3D`].
+///
+/// ## Encoding Type
+///
+/// This type carries type level information on what kind of encoding to use with the mesh.
+/// See [`Mesh3D`](crate::Mesh3D) for more information.
+#[derive(Debug, Clone)]
+pub struct StructuredMesh3D<NUM, Encoding> {
+    pub points: Vector3D<NUM>,
+    _marker: PhantomData<Encoding>,
+}
+
+impl<NUM, Encoding> StructuredMesh3D<NUM, Encoding> {
+    /// Constructor for the 3D structured mesh. Encoding can easily
+    /// be specified with a turbofish or type inference in later code.
+    pub fn new(points: Vector3D<NUM>) -> StructuredMesh3D<NUM, Encoding> {
+        Self {
+            points,
+            _marker: PhantomData,
+        }
+    }
+
+    /// swap encodings for this type. This does not change any
+    /// of the underlying data
+    pub fn change_encoding<T>(self) -> StructuredMesh3D<NUM, T> {
+        StructuredMesh3D {
+            points: self.points,
+            _marker: PhantomData::<T>,
+        }
+    }
+}
+
+impl<T, V, NUM> PartialEq<StructuredMesh3D<NUM, V>> for StructuredMesh3D<NUM, T>
+where
+    NUM: PartialEq,
+{
+    fn eq(&self, other: &StructuredMesh3D<NUM, V>) -> bool {
+        self.points == other.points
+    }
+}
+
+impl<NUM> Domain<Binary> for Structured3D<NUM, Binary>
+where
+    NUM: Numeric + num_traits::Zero,
+{
+    // only write the headers here
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        offsets: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+
+        let components = self.mesh.points.components();
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "Points",
+            offsets[0],
+            components,
+            NUM::as_precision(),
+        )?;
+
+        write_vtk::close_element(writer, "Points")?;
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        self.mesh.points.write_binary(writer, byte_order)?;
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn mesh_appended_sizes(&self) -> Vec<usize> {
+        vec![std::mem::size_of::<NUM>() * self.mesh.points.len()]
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::StructuredGrid
+    }
+}
+
+impl<NUM> Domain<Base64> for Structured3D<NUM, Base64>
+where
+    NUM: Numeric + num_traits::Zero,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+        self.mesh
+            .points
+            .write_base64(writer, "Points", byte_order)?;
+        write_vtk::close_element(writer, "Points")?;
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::StructuredGrid
+    }
+}
+
+impl<NUM> Domain<Ascii> for Structured3D<NUM, Ascii>
+where
+    NUM: Numeric + num_traits::Zero,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+        self.mesh.points.write_ascii(writer, "Points")?;
+        write_vtk::close_element(writer, "Points")?;
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::StructuredGrid
+    }
+}
+
+impl<T> ParseMesh for StructuredMesh3D<f64, T> {
+    type Visitor = StructuredMesh3DVisitor;
+}
+
+#[doc(hidden)]
+pub struct StructuredMesh3DVisitor {
+    points: parse::PartialDataArrayBuffered,
+}
+
+impl Visitor<Spans3D> for StructuredMesh3DVisitor {
+    type Output = StructuredMesh3D<f64, Binary>;
+
+    fn read_headers<'a>(spans: &Spans3D, buffer: &'a [u8]) -> IResult<'a, Self> {
+        let size_hint = 3 * spans.x_len() * spans.y_len() * spans.z_len();
+
+        let (rest, _) = parse::skip_to_section(buffer, b"Points")?;
+        let (rest, points) = parse::parse_next_dataarray_or_lazy(rest, size_hint)?;
+        let points = parse::PartialDataArrayBuffered::new(points, size_hint);
+
+        Ok((rest, Self { points }))
+    }
+
+    fn add_to_appended_reader<'a>(&'a self, buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {
+        self.points.append_to_reader_list(buffer);
+    }
+
+    fn finish(self, spans: &Spans3D) -> Result<Self::Output, ParseError> {
+        let components = self.points.components();
+        let points = self.points.into_buffer();
+        let points = Vector3D::from_buffer(points, spans, components)
+            .map_err(|e| e.with_array_name("Points"))?;
+
+        Ok(StructuredMesh3D::new(points))
+    }
+}
@@ -0,0 +1,141 @@
+use super::uniform_3d::{format_float_list, uniform_origin};
+use super::Spans2D;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Full information on a 2D computational domain with uniformly spaced points. If you are writing
+/// a vtk file, this is a candidate type to store in the `domain` field
+/// of [VtkData](`crate::VtkData`)
+///
+/// Since the location of every point can be computed from the origin and spacing, no coordinate
+/// arrays are stored in the file and it is written as `ImageData` (`.vti`) instead
+/// of a `RectilinearGrid`.
+pub struct Uniform2D<NUM> {
+    pub spans: Spans2D,
+    pub mesh: UniformMesh2D<NUM>,
+}
+
+impl<NUM> Uniform2D<NUM> {
+    /// create a new domain from mesh information and span information.
+    pub fn new(mesh: UniformMesh2D<NUM>, spans: Spans2D) -> Uniform2D<NUM> {
+        Self { mesh, spans }
+    }
+}
+
+// from impl is required for generic parsing
+impl<NUM> From<(UniformMesh2D<NUM>, Spans2D)> for Uniform2D<NUM> {
+    fn from(x: (UniformMesh2D<NUM>, Spans2D)) -> Self {
+        Self::new(x.0, x.1)
+    }
+}
+
+/// Describes the computational stencil for 2D uniform geometry
+///
+/// `origin` is the location of the first point described by the spans of the domain
+/// (`x_start`, `y_start`), and `spacing` is the distance between two neighboring
+/// points in each direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformMesh2D<NUM> {
+    pub origin: [NUM; 2],
+    pub spacing: [NUM; 2],
+}
+
+impl<NUM> UniformMesh2D<NUM> {
+    /// Constructor for the 2D uniform mesh
+    pub fn new(origin: [NUM; 2], spacing: [NUM; 2]) -> UniformMesh2D<NUM> {
+        Self { origin, spacing }
+    }
+}
+
+/// `ImageData` does not store any geometry arrays, so this domain is always written
+/// inline regardless of the encoding of the data arrays.
+impl<NUM> Domain<Ascii> for Uniform2D<NUM>
+where
+    NUM: Numeric + num_traits::Float,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::ImageData
+    }
+
+    fn grid_attributes(&self) -> Vec<(&'static str, String)> {
+        // the 2D domain is written as a single plane at z = 0 (with an extent of `1 1`)
+        let origin = [self.mesh.origin[0], self.mesh.origin[1], NUM::ZERO];
+        let spacing = [self.mesh.spacing[0], self.mesh.spacing[1], NUM::one()];
+        let starts = [self.spans.x_start, self.spans.y_start, 1];
+
+        // vtk places points at `Origin + index * Spacing`, so the origin written to the
+        // file is the location of extent index 0
+        let origin = uniform_origin(&origin, &spacing, &starts);
+
+        vec![
+            ("Origin", format_float_list(&origin)),
+            ("Spacing", format_float_list(&spacing)),
+        ]
+    }
+}
+
+impl ParseMesh for UniformMesh2D<f64> {
+    type Visitor = UniformMesh2DVisitor;
+}
+
+#[doc(hidden)]
+pub struct UniformMesh2DVisitor {
+    origin: Vec<f64>,
+    spacing: Vec<f64>,
+}
+
+impl Visitor<Spans2D> for UniformMesh2DVisitor {
+    type Output = UniformMesh2D<f64>;
+
+    fn read_headers<'a>(_spans: &Spans2D, buffer: &'a [u8]) -> IResult<'a, Self> {
+        let (rest, (origin, spacing)) = parse::read_origin_and_spacing(buffer, 2)?;
+        Ok((rest, Self { origin, spacing }))
+    }
+
+    fn read_piece_headers<'a>(
+        spans: &Spans2D,
+        dataset: &'a [u8],
+        _piece: &'a [u8],
+    ) -> IResult<'a, Self> {
+        // the origin and spacing are attributes of the dataset element, before every piece
+        Self::read_headers(spans, dataset)
+    }
+
+    fn add_to_appended_reader<'a>(&'a self, _: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {}
+
+    fn finish(self, spans: &Spans2D) -> Result<Self::Output, ParseError> {
+        let starts = [spans.x_start, spans.y_start];
+
+        let mut origin = [0.; 2];
+        let mut spacing = [0.; 2];
+
+        for i in 0..2 {
+            spacing[i] = self.spacing[i];
+            // undo the shift applied when writing `Origin` so that the origin is the first point
+            origin[i] = self.origin[i] + self.spacing[i] * starts[i] as f64;
+        }
+
+        Ok(UniformMesh2D::new(origin, spacing))
+    }
+}
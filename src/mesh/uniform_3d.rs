@@ -0,0 +1,166 @@
+use super::Spans3D;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Full information on a 3D computational domain with uniformly spaced points. If you are writing
+/// a vtk file, this is a candidate type to store in the `domain` field
+/// of [VtkData](`crate::VtkData`)
+///
+/// Since the location of every point can be computed from the origin and spacing, no coordinate
+/// arrays are stored in the file and it is written as `ImageData` (`.vti`) instead
+/// of a `RectilinearGrid`.
+pub struct Uniform3D<NUM> {
+    pub spans: Spans3D,
+    pub mesh: UniformMesh3D<NUM>,
+}
+
+impl<NUM> Uniform3D<NUM> {
+    /// create a new domain from mesh information and span information.
+    pub fn new(mesh: UniformMesh3D<NUM>, spans: Spans3D) -> Uniform3D<NUM> {
+        Self { mesh, spans }
+    }
+}
+
+// from impl is required for generic parsing
+impl<NUM> From<(UniformMesh3D<NUM>, Spans3D)> for Uniform3D<NUM> {
+    fn from(x: (UniformMesh3D<NUM>, Spans3D)) -> Self {
+        Self::new(x.0, x.1)
+    }
+}
+
+/// Describes the computational stencil for 3D uniform geometry
+///
+/// `origin` is the location of the first point described by the spans of the domain
+/// (`x_start`, `y_start`, `z_start`), and `spacing` is the distance between two neighboring
+/// points in each direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformMesh3D<NUM> {
+    pub origin: [NUM; 3],
+    pub spacing: [NUM; 3],
+}
+
+impl<NUM> UniformMesh3D<NUM> {
+    /// Constructor for the 3D uniform mesh
+    pub fn new(origin: [NUM; 3], spacing: [NUM; 3]) -> UniformMesh3D<NUM> {
+        Self { origin, spacing }
+    }
+}
+
+/// `ImageData` does not store any geometry arrays, so this domain is always written
+/// inline regardless of the encoding of the data arrays.
+impl<NUM> Domain<Ascii> for Uniform3D<NUM>
+where
+    NUM: Numeric + num_traits::Float,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::ImageData
+    }
+
+    fn grid_attributes(&self) -> Vec<(&'static str, String)> {
+        let starts = [self.spans.x_start, self.spans.y_start, self.spans.z_start];
+
+        // vtk places points at `Origin + index * Spacing`, so the origin written to the
+        // file is the location of extent index 0
+        let origin = uniform_origin(&self.mesh.origin, &self.mesh.spacing, &starts);
+
+        vec![
+            ("Origin", format_float_list(&origin)),
+            ("Spacing", format_float_list(&self.mesh.spacing)),
+        ]
+    }
+}
+
+/// compute the vtk `Origin` attribute (location of extent index 0) from the location
+/// of the first point in the spans
+pub(crate) fn uniform_origin<NUM>(origin: &[NUM], spacing: &[NUM], starts: &[usize]) -> Vec<NUM>
+where
+    NUM: num_traits::Float,
+{
+    origin
+        .iter()
+        .zip(spacing)
+        .zip(starts)
+        .map(|((origin, spacing), start)| {
+            *origin - *spacing * NUM::from(*start).expect("span start fits in a float")
+        })
+        .collect()
+}
+
+/// format floats into a space separated list for an element attribute
+pub(crate) fn format_float_list<NUM: Numeric>(values: &[NUM]) -> String {
+    values
+        .iter()
+        .map(|value| {
+            let mut buffer = String::new();
+            value.push_ascii(&mut buffer);
+            buffer
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl ParseMesh for UniformMesh3D<f64> {
+    type Visitor = UniformMesh3DVisitor;
+}
+
+#[doc(hidden)]
+pub struct UniformMesh3DVisitor {
+    origin: Vec<f64>,
+    spacing: Vec<f64>,
+}
+
+impl Visitor<Spans3D> for UniformMesh3DVisitor {
+    type Output = UniformMesh3D<f64>;
+
+    fn read_headers<'a>(_spans: &Spans3D, buffer: &'a [u8]) -> IResult<'a, Self> {
+        let (rest, (origin, spacing)) = parse::read_origin_and_spacing(buffer, 3)?;
+        Ok((rest, Self { origin, spacing }))
+    }
+
+    fn read_piece_headers<'a>(
+        spans: &Spans3D,
+        dataset: &'a [u8],
+        _piece: &'a [u8],
+    ) -> IResult<'a, Self> {
+        // the origin and spacing are attributes of the dataset element, before every piece
+        Self::read_headers(spans, dataset)
+    }
+
+    fn add_to_appended_reader<'a>(&'a self, _: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {}
+
+    fn finish(self, spans: &Spans3D) -> Result<Self::Output, ParseError> {
+        let starts = [spans.x_start, spans.y_start, spans.z_start];
+
+        let mut origin = [0.; 3];
+        let mut spacing = [0.; 3];
+
+        for i in 0..3 {
+            spacing[i] = self.spacing[i];
+            // undo the shift applied when writing `Origin` so that the origin is the first point
+            origin[i] = self.origin[i] + self.spacing[i] * starts[i] as f64;
+        }
+
+        Ok(UniformMesh3D::new(origin, spacing))
+    }
+}
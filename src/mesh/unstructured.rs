@@ -0,0 +1,510 @@
+use crate::array::check_length;
+use crate::prelude::*;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Full information on a computational domain made of arbitrary cells. If you are writing
+/// a vtk file, this is a candidate type to store in the `domain` field
+/// of [VtkData](`crate::VtkData`)
+///
+/// This domain is written as an `UnstructuredGrid` (`.vtu`). Since there is no structured
+/// topology, data arrays for this domain should be stored as flat containers (`Vec<f64>`) with
+/// one value (or one value per component) per point.
+pub struct Unstructured<NUM, Encoding> {
+    pub spans: UnstructuredSpans,
+    pub mesh: UnstructuredMesh<NUM, Encoding>,
+}
+
+impl<NUM, Encoding> Unstructured<NUM, Encoding>
+where
+    NUM: Numeric,
+{
+    /// create a new domain from mesh information. The number of points and cells is
+    /// taken from the mesh.
+    pub fn new(mesh: UnstructuredMesh<NUM, Encoding>) -> Unstructured<NUM, Encoding> {
+        let spans = UnstructuredSpans::new(mesh.points.len(), mesh.types.len());
+        Self { mesh, spans }
+    }
+}
+
+// from impl is required for generic parsing
+impl<NUM, T> From<(UnstructuredMesh<NUM, T>, UnstructuredSpans)> for Unstructured<NUM, T>
+where
+    NUM: Numeric,
+{
+    fn from(x: (UnstructuredMesh<NUM, T>, UnstructuredSpans)) -> Self {
+        Self {
+            mesh: x.0,
+            spans: x.1,
+        }
+    }
+}
+
+/// The type of a single cell in an unstructured mesh. The discriminant of each variant is
+/// the identifier VTK uses for the cell type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CellType {
+    Vertex = 1,
+    PolyVertex = 2,
+    Line = 3,
+    PolyLine = 4,
+    Triangle = 5,
+    TriangleStrip = 6,
+    Polygon = 7,
+    Pixel = 8,
+    Quad = 9,
+    Tetra = 10,
+    Voxel = 11,
+    Hexahedron = 12,
+    Wedge = 13,
+    Pyramid = 14,
+}
+
+impl CellType {
+    /// get the cell type from the identifier VTK uses for it, if it is a known cell type
+    pub fn from_id(id: u8) -> Option<Self> {
+        let cell_type = match id {
+            1 => Self::Vertex,
+            2 => Self::PolyVertex,
+            3 => Self::Line,
+            4 => Self::PolyLine,
+            5 => Self::Triangle,
+            6 => Self::TriangleStrip,
+            7 => Self::Polygon,
+            8 => Self::Pixel,
+            9 => Self::Quad,
+            10 => Self::Tetra,
+            11 => Self::Voxel,
+            12 => Self::Hexahedron,
+            13 => Self::Wedge,
+            14 => Self::Pyramid,
+            _ => return None,
+        };
+
+        Some(cell_type)
+    }
+
+    /// the identifier VTK uses for this cell type
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Describes the points and cells of an unstructured mesh
+///
+/// The cells are stored the same way they are written to the file: the point indices of every cell
+/// are concatenated in `connectivity`, `offsets` contains the index in `connectivity` one past
+/// the end of each cell, and `types` contains the type of each cell. [`UnstructuredMesh::push_cell`]
+/// keeps these three arrays consistent.
+///
+/// ## Encoding Type
+///
+/// This type carries type level information on what kind of encoding to use with the mesh.
+/// See [`Mesh3D`](crate::Mesh3D) for more information.
+#[derive(Debug, Clone)]
+pub struct UnstructuredMesh<NUM, Encoding> {
+    pub points: Vec<[NUM; 3]>,
+    pub connectivity: Vec<i64>,
+    pub offsets: Vec<i64>,
+    pub types: Vec<CellType>,
+    _marker: PhantomData<Encoding>,
+}
+
+impl<NUM, Encoding> UnstructuredMesh<NUM, Encoding> {
+    /// Constructor for the unstructured mesh. Encoding can easily
+    /// be specified with a turbofish or type inference in later code.
+    pub fn new(
+        points: Vec<[NUM; 3]>,
+        connectivity: Vec<i64>,
+        offsets: Vec<i64>,
+        types: Vec<CellType>,
+    ) -> UnstructuredMesh<NUM, Encoding> {
+        Self {
+            points,
+            connectivity,
+            offsets,
+            types,
+            _marker: PhantomData,
+        }
+    }
+
+    /// add a cell made of the points at indices `point_ids` to the mesh
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use vtk::{CellType, UnstructuredMesh};
+    ///
+    /// let points = vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [1., 1., 0.]];
+    /// let mut mesh = UnstructuredMesh::<f64, vtk::Binary>::new(points, vec![], vec![], vec![]);
+    ///
+    /// mesh.push_cell(CellType::Triangle, &[0, 1, 2]);
+    /// mesh.push_cell(CellType::Triangle, &[1, 3, 2]);
+    ///
+    /// assert_eq!(mesh.offsets, vec![3, 6]);
+    /// ```
+    pub fn push_cell(&mut self, cell_type: CellType, point_ids: &[i64]) {
+        self.connectivity.extend_from_slice(point_ids);
+        self.offsets.push(self.connectivity.len() as i64);
+        self.types.push(cell_type);
+    }
+
+    /// swap encodings for this type. This does not change any
+    /// of the underlying data
+    pub fn change_encoding<T>(self) -> UnstructuredMesh<NUM, T> {
+        let UnstructuredMesh {
+            points,
+            connectivity,
+            offsets,
+            types,
+            _marker,
+        } = self;
+
+        UnstructuredMesh {
+            points,
+            connectivity,
+            offsets,
+            types,
+            _marker: PhantomData::<T>,
+        }
+    }
+
+    fn type_ids(&self) -> Vec<u8> {
+        self.types.iter().map(|cell_type| cell_type.id()).collect()
+    }
+}
+
+impl<T, V, NUM> PartialEq<UnstructuredMesh<NUM, V>> for UnstructuredMesh<NUM, T>
+where
+    NUM: PartialEq,
+{
+    fn eq(&self, other: &UnstructuredMesh<NUM, V>) -> bool {
+        self.points == other.points
+            && self.connectivity == other.connectivity
+            && self.offsets == other.offsets
+            && self.types == other.types
+    }
+}
+
+/// Describes the size of an unstructured domain.
+///
+/// Unstructured data has no extents, so instead the number of points and cells
+/// are used to describe the domain.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnstructuredSpans {
+    pub number_of_points: usize,
+    pub number_of_cells: usize,
+}
+
+impl UnstructuredSpans {
+    /// create the spans from the number of points and cells in the domain
+    pub fn new(number_of_points: usize, number_of_cells: usize) -> Self {
+        Self {
+            number_of_points,
+            number_of_cells,
+        }
+    }
+}
+
+/// Format the spans as `"number_of_points number_of_cells"`
+impl std::fmt::Display for UnstructuredSpans {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.number_of_points, self.number_of_cells)
+    }
+}
+
+impl ParseSpan for UnstructuredSpans {
+    /// parse the spans from a string in the form `"number_of_points number_of_cells"`,
+    /// returning `None` if there are not 2 `usize` values
+    fn from_str(extent: &str) -> Option<Self> {
+        let mut split = extent.split_ascii_whitespace();
+        let mut next = || split.next()?.parse().ok();
+
+        Some(UnstructuredSpans {
+            number_of_points: next()?,
+            number_of_cells: next()?,
+        })
+    }
+
+    fn read_spans(xml_bytes: &[u8]) -> IResult<'_, Self> {
+        let (rest, sizes) =
+            parse::read_piece_sizes(xml_bytes, &[b"NumberOfPoints", b"NumberOfCells"])?;
+
+        Ok((rest, UnstructuredSpans::new(sizes[0], sizes[1])))
+    }
+
+    /// cell data arrays hold a value for every cell, so their spans have a point for every cell
+    fn cell_spans(&self) -> Self {
+        UnstructuredSpans::new(self.number_of_cells, self.number_of_cells)
+    }
+}
+
+impl<NUM> Domain<Binary> for Unstructured<NUM, Binary>
+where
+    NUM: Numeric,
+{
+    // only write the headers here
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        offsets: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "Points",
+            offsets[0],
+            3,
+            NUM::as_precision(),
+        )?;
+        write_vtk::close_element(writer, "Points")?;
+
+        write_vtk::open_element(writer, "Cells")?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "connectivity",
+            offsets[1],
+            1,
+            Precision::Int64,
+        )?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "offsets",
+            offsets[2],
+            1,
+            Precision::Int64,
+        )?;
+        write_vtk::write_appended_dataarray_header(
+            writer,
+            "types",
+            offsets[3],
+            1,
+            Precision::UInt8,
+        )?;
+        write_vtk::close_element(writer, "Cells")?;
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        self.mesh.points.write_binary(writer, byte_order)?;
+        self.mesh.connectivity.write_binary(writer, byte_order)?;
+        self.mesh.offsets.write_binary(writer, byte_order)?;
+        self.mesh.type_ids().write_binary(writer, byte_order)?;
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn mesh_appended_sizes(&self) -> Vec<usize> {
+        vec![
+            std::mem::size_of::<NUM>() * 3 * self.mesh.points.len(),
+            std::mem::size_of::<i64>() * self.mesh.connectivity.len(),
+            std::mem::size_of::<i64>() * self.mesh.offsets.len(),
+            std::mem::size_of::<u8>() * self.mesh.types.len(),
+        ]
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::UnstructuredGrid
+    }
+
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        piece_attributes(&self.spans)
+    }
+}
+
+impl<NUM> Domain<Base64> for Unstructured<NUM, Base64>
+where
+    NUM: Numeric,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+        self.mesh
+            .points
+            .write_base64(writer, "Points", byte_order)?;
+        write_vtk::close_element(writer, "Points")?;
+
+        write_vtk::open_element(writer, "Cells")?;
+        self.mesh
+            .connectivity
+            .write_base64(writer, "connectivity", byte_order)?;
+        self.mesh
+            .offsets
+            .write_base64(writer, "offsets", byte_order)?;
+        self.mesh
+            .type_ids()
+            .write_base64(writer, "types", byte_order)?;
+        write_vtk::close_element(writer, "Cells")?;
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::UnstructuredGrid
+    }
+
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        piece_attributes(&self.spans)
+    }
+}
+
+impl<NUM> Domain<Ascii> for Unstructured<NUM, Ascii>
+where
+    NUM: Numeric,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        write_vtk::open_element(writer, "Points")?;
+        self.mesh.points.write_ascii(writer, "Points")?;
+        write_vtk::close_element(writer, "Points")?;
+
+        write_vtk::open_element(writer, "Cells")?;
+        self.mesh.connectivity.write_ascii(writer, "connectivity")?;
+        self.mesh.offsets.write_ascii(writer, "offsets")?;
+        self.mesh.type_ids().write_ascii(writer, "types")?;
+        write_vtk::close_element(writer, "Cells")?;
+
+        Ok(())
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        _: &mut EventWriter<W>,
+        _byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn span_string(&self) -> String {
+        self.spans.to_string()
+    }
+
+    fn grid_type(&self) -> GridType {
+        GridType::UnstructuredGrid
+    }
+
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        piece_attributes(&self.spans)
+    }
+}
+
+fn piece_attributes(spans: &UnstructuredSpans) -> Vec<(&'static str, String)> {
+    vec![
+        ("NumberOfPoints", spans.number_of_points.to_string()),
+        ("NumberOfCells", spans.number_of_cells.to_string()),
+    ]
+}
+
+impl<T> ParseMesh for UnstructuredMesh<f64, T> {
+    type Visitor = UnstructuredMeshVisitor;
+}
+
+#[doc(hidden)]
+pub struct UnstructuredMeshVisitor {
+    points: parse::PartialDataArrayBuffered,
+    connectivity: parse::PartialDataArrayBuffered,
+    offsets: parse::PartialDataArrayBuffered,
+    types: parse::PartialDataArrayBuffered,
+}
+
+impl Visitor<UnstructuredSpans> for UnstructuredMeshVisitor {
+    type Output = UnstructuredMesh<f64, Binary>;
+
+    fn read_headers<'a>(spans: &UnstructuredSpans, buffer: &'a [u8]) -> IResult<'a, Self> {
+        let points_len = 3 * spans.number_of_points;
+        let cells_len = spans.number_of_cells;
+
+        let (rest, _) = parse::skip_to_section(buffer, b"Points")?;
+        let (_, points) = parse::parse_next_dataarray_or_lazy(rest, points_len)?;
+
+        let (rest, mut cells) = parse::read_section_arrays(buffer, b"Cells")?;
+        let connectivity = cells.take(b"connectivity", 0)?;
+        let offsets = cells.take(b"offsets", cells_len)?;
+        let types = cells.take(b"types", cells_len)?;
+
+        let visitor = Self {
+            points: parse::PartialDataArrayBuffered::new(points, points_len),
+            connectivity: parse::PartialDataArrayBuffered::new(connectivity, 0),
+            offsets: parse::PartialDataArrayBuffered::new(offsets, cells_len),
+            types: parse::PartialDataArrayBuffered::new(types, cells_len),
+        };
+
+        Ok((rest, visitor))
+    }
+
+    fn add_to_appended_reader<'a>(&'a self, buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {
+        self.points.append_to_reader_list(buffer);
+        self.connectivity.append_to_reader_list(buffer);
+        self.offsets.append_to_reader_list(buffer);
+        self.types.append_to_reader_list(buffer);
+    }
+
+    fn finish(self, spans: &UnstructuredSpans) -> Result<Self::Output, ParseError> {
+        let points: Vec<[f64; 3]> = FromBuffer::from_buffer(self.points.into_buffer(), spans, 3)
+            .map_err(|e| e.with_array_name("Points"))?;
+
+        let offsets = self.offsets.into_buffer();
+        check_length(&offsets, spans.number_of_cells).map_err(|e| e.with_array_name("offsets"))?;
+        let offsets: Vec<i64> = offsets
+            .into_vec()
+            .map_err(|e| e.with_array_name("offsets"))?;
+
+        // the last offset is the end of the last cell in `connectivity`
+        let connectivity = self.connectivity.into_buffer();
+        let connectivity_len = offsets
+            .last()
+            .map_or(0, |&end| usize::try_from(end).unwrap_or(usize::MAX));
+        check_length(&connectivity, connectivity_len)
+            .map_err(|e| e.with_array_name("connectivity"))?;
+        let connectivity = connectivity
+            .into_vec()
+            .map_err(|e| e.with_array_name("connectivity"))?;
+
+        let types = self.types.into_buffer();
+        check_length(&types, spans.number_of_cells).map_err(|e| e.with_array_name("types"))?;
+        let types = types
+            .into_vec::<u8>()
+            .map_err(|e| e.with_array_name("types"))?
+            .into_iter()
+            .map(CellType::from_id)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                ParseError::from_nom(
+                    parse::NomError::nom(b"types".as_ref(), nom::error::ErrorKind::Verify),
+                    "unknown cell type in the `types` array of the unstructured mesh",
+                )
+            })?;
+
+        Ok(UnstructuredMesh::new(points, connectivity, offsets, types))
+    }
+}
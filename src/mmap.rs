@@ -0,0 +1,89 @@
+//! Reading files through a memory map, with the `mmap` feature
+use crate::prelude::*;
+
+use std::path::Path;
+
+/// The contents of a file, mapped into memory instead of being read.
+///
+/// Only the parts of the file that are accessed are read from disk, so parsing a file with a
+/// [`ParseArray`](crate::ParseArray) type that only contains some of its arrays (or whose
+/// fields are marked `#[vtk(lazy)]`) skips the appended data of every other array.
+///
+/// The file must not be modified or truncated by another process while it is mapped. The
+/// contents would change underneath the parser, and reading past the end of a truncated file
+/// aborts the process.
+///
+/// ```no_run
+/// #[derive(vtk::ParseArray)]
+/// #[vtk_parse(spans = "vtk::Spans3D")]
+/// pub struct Fields {
+///     rho: vtk::Scalar3D<f64>,
+///     #[vtk(lazy)]
+///     velocity: vtk::LazyArray,
+/// }
+///
+/// let file = vtk::MappedFile::open("./data/output.vtr".as_ref()).unwrap();
+/// let vtk: vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, Fields> = file.parse().unwrap();
+///
+/// // the velocity is only read from disk here
+/// let velocity: vtk::Vector3D<f64> = vtk
+///     .data
+///     .velocity
+///     .load_as(&file, &vtk.domain.spans)
+///     .unwrap();
+/// ```
+pub struct MappedFile {
+    map: memmap2::Mmap,
+}
+
+impl MappedFile {
+    /// map the file at `path` into memory
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let file = std::fs::File::open(path)?;
+
+        // SAFETY: the map is only ever read, and the documentation of `MappedFile` requires
+        // that the file is not modified while it is mapped
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+
+        Ok(Self { map })
+    }
+
+    /// parse the mapped file, see [`read_vtk`](crate::read_vtk)
+    pub fn parse<DOMAIN, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
+        &self,
+    ) -> Result<VtkData<DOMAIN, D>, Error>
+    where
+        D: ParseArray<Visitor = ArrayVisitor>,
+        ArrayVisitor: Visitor<SPAN, Output = D>,
+        MESH: ParseMesh<Visitor = MeshVisitor>,
+        MeshVisitor: Visitor<SPAN, Output = MESH>,
+        SPAN: ParseSpan,
+        DOMAIN: From<(MESH, SPAN)>,
+    {
+        parse::parse_xml_document(&self.map)
+    }
+}
+
+impl std::ops::Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+/// read in and parse an entire vtk file for a given path through a memory map, see
+/// [`MappedFile`]
+pub fn read_vtk_mmap<DOMAIN, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
+    path: &Path,
+) -> Result<VtkData<DOMAIN, D>, Error>
+where
+    D: ParseArray<Visitor = ArrayVisitor>,
+    ArrayVisitor: Visitor<SPAN, Output = D>,
+    MESH: ParseMesh<Visitor = MeshVisitor>,
+    MeshVisitor: Visitor<SPAN, Output = MESH>,
+    SPAN: ParseSpan,
+    DOMAIN: From<(MESH, SPAN)>,
+{
+    MappedFile::open(path)?.parse()
+}
@@ -0,0 +1,320 @@
+//! Multiblock (`.vtm`) datasets, which group the files of several blocks into a tree
+//!
+//! Multi-zone domains with blocks of different resolutions are written one file per block. A
+//! [`MultiBlock`] lists the files by name, with blocks nested within other blocks, so that the
+//! domain can be opened as a single `vtkMultiBlockDataSet`. Every file of a `.vtm` file is read
+//! back with [`read_vtm`].
+use crate::prelude::*;
+
+use std::path::Path;
+
+use nom::bytes::complete::take_till;
+
+/// A node of the tree of a multiblock dataset
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block<T> {
+    /// a single dataset, which is the path of its file in a [`MultiBlock`]
+    DataSet { name: String, data: T },
+    /// a block that contains other blocks
+    MultiBlock { name: String, blocks: Vec<Block<T>> },
+}
+
+impl<T> Block<T> {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::DataSet { name, .. } => name,
+            Self::MultiBlock { name, .. } => name,
+        }
+    }
+
+    /// the dataset of the block, if it is not a block of other blocks
+    pub fn data(&self) -> Option<&T> {
+        match self {
+            Self::DataSet { data, .. } => Some(data),
+            Self::MultiBlock { .. } => None,
+        }
+    }
+
+    /// the blocks nested within the block, which is empty for a dataset
+    pub fn blocks(&self) -> &[Block<T>] {
+        match self {
+            Self::DataSet { .. } => &[],
+            Self::MultiBlock { blocks, .. } => blocks,
+        }
+    }
+
+    /// replace the dataset of every block within the tree by the result of `f`, stopping at the
+    /// first error
+    pub fn try_map<U, E, F>(self, f: &mut F) -> Result<Block<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let block = match self {
+            Self::DataSet { name, data } => Block::DataSet {
+                name,
+                data: f(data)?,
+            },
+            Self::MultiBlock { name, blocks } => Block::MultiBlock {
+                name,
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| block.try_map(f))
+                    .collect::<Result<_, _>>()?,
+            },
+        };
+
+        Ok(block)
+    }
+}
+
+/// The `.vtm` file of a dataset whose blocks are written to separate files.
+///
+/// ```
+/// // two zones of the fluid, and a solid
+/// let fluid = vtk::MultiBlock::new()
+///     .with_dataset("inlet", "case/inlet.vtr")
+///     .with_dataset("channel", "case/channel.vtr");
+///
+/// let mut file = Vec::new();
+/// vtk::MultiBlock::new()
+///     .with_block("fluid", fluid)
+///     .with_dataset("solid", "case/solid.vtr")
+///     .write(&mut file)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MultiBlock {
+    blocks: Vec<Block<String>>,
+}
+
+impl MultiBlock {
+    /// a dataset without any blocks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add the dataset `name` that is written to the file `file`. The path of the file is
+    /// relative to the `.vtm` file
+    pub fn with_dataset(mut self, name: &str, file: &str) -> Self {
+        self.blocks.push(Block::DataSet {
+            name: name.to_string(),
+            data: file.to_string(),
+        });
+        self
+    }
+
+    /// add the blocks of `block` nested within a block named `name`
+    pub fn with_block(mut self, name: &str, block: MultiBlock) -> Self {
+        self.blocks.push(Block::MultiBlock {
+            name: name.to_string(),
+            blocks: block.blocks,
+        });
+        self
+    }
+
+    /// the top level blocks of the dataset, in the order they were added
+    pub fn blocks(&self) -> &[Block<String>] {
+        &self.blocks
+    }
+
+    /// write the `.vtm` file to `writer`
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut writer = EventWriter::new(writer);
+
+        writer.write(XmlEvent::StartDocument {
+            version: xml::common::XmlVersion::Version10,
+            encoding: None,
+            standalone: None,
+        })?;
+
+        write_vtk::start_element(
+            &mut writer,
+            "VTKFile",
+            &[
+                ("type", "vtkMultiBlockDataSet"),
+                ("version", "1.0"),
+                ("byte_order", ByteOrder::LittleEndian.to_str()),
+                ("header_type", "UInt64"),
+            ],
+        )?;
+        write_vtk::open_element(&mut writer, "vtkMultiBlockDataSet")?;
+
+        write_blocks(&mut writer, &self.blocks)?;
+
+        write_vtk::close_element(&mut writer, "vtkMultiBlockDataSet")?;
+        write_vtk::close_element(&mut writer, "VTKFile")?;
+
+        Ok(())
+    }
+}
+
+/// write every block of a level of the tree, along with the blocks nested within them
+fn write_blocks<W: Write>(
+    writer: &mut EventWriter<W>,
+    blocks: &[Block<String>],
+) -> Result<(), Error> {
+    for (index, block) in blocks.iter().enumerate() {
+        let index = index.to_string();
+
+        match block {
+            Block::DataSet { name, data } => {
+                write_vtk::start_element(
+                    writer,
+                    "DataSet",
+                    &[("index", &index), ("name", name), ("file", data)],
+                )?;
+                write_vtk::close_element(writer, "DataSet")?;
+            }
+            Block::MultiBlock { name, blocks } => {
+                write_vtk::start_element(writer, "Block", &[("index", &index), ("name", name)])?;
+                write_blocks(writer, blocks)?;
+                write_vtk::close_element(writer, "Block")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// read a `.vtm` file and every file of its blocks (relative to the `.vtm` file) with
+/// [`read_vtk`](crate::read_vtk).
+///
+/// Every block is read into the same `VtkData<GEOMETRY, D>`, so every file must contain the
+/// same type of dataset (such as rectilinear grids) with the arrays of `D`. A multiblock file
+/// that mixes dataset types, or blocks with different arrays, returns the error of the first
+/// block that can not be read. Use [`parse_vtm`] to read the tree of such a file, and read
+/// each block with its own type (or with [`read_vtk_dynamic`](crate::read_vtk_dynamic)).
+///
+/// ```no_run
+/// #[derive(vtk::ParseArray)]
+/// #[vtk_parse(spans = "vtk::Spans3D")]
+/// pub struct Fields {
+///     rho: vtk::Scalar3D<f64>,
+/// }
+///
+/// let blocks: Vec<vtk::Block<vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, Fields>>> =
+///     vtk::read_vtm("./data/case.vtm".as_ref()).unwrap();
+///
+/// for block in &blocks {
+///     println!("{} contains {} blocks", block.name(), block.blocks().len());
+/// }
+/// ```
+pub fn read_vtm<GEOMETRY, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
+    path: &Path,
+) -> Result<Vec<Block<VtkData<GEOMETRY, D>>>, Error>
+where
+    D: ParseArray<Visitor = ArrayVisitor>,
+    ArrayVisitor: Visitor<SPAN, Output = D>,
+    MESH: ParseMesh<Visitor = MeshVisitor>,
+    MeshVisitor: Visitor<SPAN, Output = MESH>,
+    SPAN: ParseSpan,
+    GEOMETRY: From<(MESH, SPAN)>,
+{
+    let bytes = std::fs::read(path)?;
+    let description = parse_vtm(&bytes)?;
+
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut read_file = |file: String| parse::read_and_parse(&directory.join(file));
+
+    description
+        .blocks
+        .into_iter()
+        .map(|block| block.try_map(&mut read_file))
+        .collect()
+}
+
+/// read the tree of a `.vtm` file, without reading any of its blocks.
+///
+/// Datasets without a `file` (the empty blocks that vtk writes) are not part of the tree
+pub fn parse_vtm(bytes: &[u8]) -> Result<MultiBlock, Error> {
+    let xml = parse::xml_elements(bytes);
+
+    let (_, blocks) = read_blocks(xml)
+        .map_err(|e| ParseError::from_nom_in(e, bytes, "could not read the multiblock file"))?;
+
+    Ok(MultiBlock { blocks })
+}
+
+fn read_blocks(xml: &[u8]) -> IResult<'_, Vec<Block<String>>> {
+    let (mut rest, _) = parse::skip_to_section(xml, b"vtkMultiBlockDataSet")?;
+
+    // the name, the blocks read so far and the opening element of every block that is not
+    // closed yet, starting with the whole dataset
+    let mut open_blocks = vec![(String::new(), Vec::new(), rest)];
+
+    while let Ok((element_start, _)) = parse::take_until_consume(rest, b"<") {
+        let (element_end, element) = take_till(|c| c == b'>')(element_start)?;
+        rest = element_end;
+
+        if let Some(attributes) = element.strip_prefix(b"DataSet") {
+            let (_, attributes) = parse::read_element_attributes(attributes)?;
+            let name = block_name(&attributes);
+
+            if let Some(file) = attributes.get(b"file") {
+                let data = String::from_utf8_lossy(file).into_owned();
+                open_blocks
+                    .last_mut()
+                    .unwrap()
+                    .1
+                    .push(Block::DataSet { name, data });
+            }
+        } else if let Some(attributes) = element.strip_prefix(b"Block") {
+            let (_, element_attributes) = parse::read_element_attributes(attributes)?;
+            let name = block_name(&element_attributes);
+
+            // an empty block may be closed within its opening tag
+            if attributes.ends_with(b"/") {
+                let blocks = Vec::new();
+                open_blocks
+                    .last_mut()
+                    .unwrap()
+                    .1
+                    .push(Block::MultiBlock { name, blocks });
+            } else {
+                open_blocks.push((name, Vec::new(), element));
+            }
+        } else if element.starts_with(b"/Block") {
+            // the dataset itself is never closed by a `</Block>`
+            if open_blocks.len() == 1 {
+                return Err(parse::NomError::nom(element, nom::error::ErrorKind::Tag));
+            }
+
+            let (name, blocks, _) = open_blocks.pop().unwrap();
+            open_blocks
+                .last_mut()
+                .unwrap()
+                .1
+                .push(Block::MultiBlock { name, blocks });
+        } else if element.starts_with(b"/vtkMultiBlockDataSet") {
+            break;
+        }
+    }
+
+    // every block must be closed before the end of the dataset
+    if open_blocks.len() != 1 {
+        let (name, _, element) = open_blocks.pop().unwrap();
+        let block = if name.is_empty() {
+            let attributes = element.strip_prefix(b"Block").unwrap_or(element);
+            let (_, attributes) = parse::read_element_attributes(attributes)?;
+            let index = attributes.get(b"index").unwrap_or_default();
+            String::from_utf8_lossy(index).into_owned()
+        } else {
+            name
+        };
+
+        let kind = parse::NomErrorKind::UnclosedBlock { block };
+        return Err(parse::NomError::err(element, kind));
+    }
+
+    let (_, blocks, _) = open_blocks.pop().unwrap();
+
+    Ok((rest, blocks))
+}
+
+/// the `name` of a block, which is empty if the block is not named
+fn block_name(attributes: &parse::ElementAttributes) -> String {
+    attributes
+        .get(b"name")
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .unwrap_or_default()
+}
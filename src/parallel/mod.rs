@@ -0,0 +1,283 @@
+//! Datasets decomposed into pieces that are written to separate files, such as the output of
+//! each rank of an MPI program
+//!
+//! Each rank writes its own part of the domain as a [`ParallelPiece`], which records the
+//! `Extent` of the part along with the `WholeExtent` of the dataset. A single `.pvtr` file
+//! written by a [`ParallelRectilinear`] then lists every piece so that the dataset can be opened
+//! as a whole. The pieces of a `.pvtr` file are read back and assembled with [`read_pvtr`].
+use crate::prelude::*;
+use crate::{Rectilinear3D, Spans3D};
+
+mod stitch;
+
+pub use stitch::{parse_pvtr, read_pvtr, ParallelDataset, Stitch};
+
+/// A domain that is only a piece of a larger dataset spanning `whole_spans`.
+///
+/// The `Extent` of the piece is written from the spans of `domain`, which should be the
+/// location of the piece within `whole_spans`. Neighbouring pieces share the points on their
+/// boundary, so the end of one piece is the start of the next.
+///
+/// ```
+/// // the first of two ranks splitting 8 points in x
+/// let whole_spans = vtk::Spans3D::new(8, 4, 4);
+/// let spans = vtk::Spans3D { x_end: 4, ..whole_spans.clone() };
+///
+/// let mesh = vtk::Mesh3D::<f64, vtk::Binary>::new(
+///     vec![0., 1., 2., 3.],
+///     vec![0., 1., 2., 3.],
+///     vec![0., 1., 2., 3.],
+/// );
+/// let domain = vtk::ParallelPiece::new(vtk::Rectilinear3D::new(mesh, spans), whole_spans);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParallelPiece<DOMAIN> {
+    pub domain: DOMAIN,
+    pub whole_spans: Spans3D,
+}
+
+impl<DOMAIN> ParallelPiece<DOMAIN> {
+    pub fn new(domain: DOMAIN, whole_spans: Spans3D) -> Self {
+        Self {
+            domain,
+            whole_spans,
+        }
+    }
+}
+
+impl<DOMAIN, Encoding> Domain<Encoding> for ParallelPiece<DOMAIN>
+where
+    DOMAIN: Domain<Encoding>,
+{
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        offsets: &[i64],
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        self.domain.write_mesh_header(writer, offsets, byte_order)
+    }
+
+    fn write_mesh_appended<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error> {
+        self.domain.write_mesh_appended(writer, byte_order)
+    }
+
+    fn span_string(&self) -> String {
+        self.domain.span_string()
+    }
+
+    fn whole_span_string(&self) -> String {
+        self.whole_spans.to_string()
+    }
+
+    fn mesh_appended_sizes(&self) -> Vec<usize> {
+        self.domain.mesh_appended_sizes()
+    }
+
+    fn grid_type(&self) -> GridType {
+        self.domain.grid_type()
+    }
+
+    fn grid_attributes(&self) -> Vec<(&'static str, String)> {
+        self.domain.grid_attributes()
+    }
+
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        self.domain.piece_attributes()
+    }
+}
+
+/// The declaration of an array in a parallel file, see
+/// [`DataArray::array_declarations`](crate::DataArray::array_declarations)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayDeclaration {
+    pub name: String,
+    pub precision: Precision,
+    pub components: usize,
+    /// if the array is written to the `<CellData>` of each piece
+    pub cell: bool,
+}
+
+impl ArrayDeclaration {
+    pub fn new(name: &str, precision: Precision, components: usize, cell: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            precision,
+            components,
+            cell,
+        }
+    }
+}
+
+/// The `.pvtr` file of a rectilinear dataset whose pieces are written to separate files.
+///
+/// ```
+/// #[derive(vtk::DataArray)]
+/// pub struct Fields {
+///     rho: vtk::Scalar3D<f64>,
+/// }
+///
+/// let whole_spans = vtk::Spans3D::new(8, 4, 4);
+/// let spans = vtk::Spans3D { x_end: 4, ..whole_spans.clone() };
+/// let mesh = vtk::Mesh3D::<f64, vtk::Binary>::new(vec![0.; 4], vec![0.; 4], vec![0.; 4]);
+/// let domain = vtk::ParallelPiece::new(vtk::Rectilinear3D::new(mesh, spans.clone()), whole_spans);
+/// let data = Fields { rho: vtk::Scalar3D::new(ndarray::Array3::zeros((4, 4, 4))) };
+///
+/// // every rank knows the spans of every other rank
+/// let mut file = Vec::new();
+/// vtk::ParallelRectilinear::new(&domain, &data)
+///     .with_piece(spans, "output_0.vtr")
+///     .with_piece(vtk::Spans3D { x_start: 4, x_end: 8, ..domain.whole_spans.clone() }, "output_1.vtr")
+///     .write(&mut file)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParallelRectilinear {
+    whole_spans: Spans3D,
+    coordinates: Precision,
+    arrays: Vec<ArrayDeclaration>,
+    pieces: Vec<(Spans3D, String)>,
+}
+
+impl ParallelRectilinear {
+    /// a dataset with the `WholeExtent` and coordinate type of `domain` (the piece of any rank),
+    /// and the arrays of `data`
+    pub fn new<NUM, EncMesh, D, EncArray>(
+        domain: &ParallelPiece<Rectilinear3D<NUM, EncMesh>>,
+        data: &D,
+    ) -> Self
+    where
+        NUM: Numeric,
+        D: DataArray<EncArray>,
+    {
+        Self {
+            whole_spans: domain.whole_spans.clone(),
+            coordinates: NUM::as_precision(),
+            arrays: data.array_declarations(),
+            pieces: Vec::new(),
+        }
+    }
+
+    /// add the piece spanning `spans` that is written to the file `source`. The path of the
+    /// file is relative to the `.pvtr` file
+    pub fn with_piece(mut self, spans: Spans3D, source: &str) -> Self {
+        self.pieces.push((spans, source.to_string()));
+        self
+    }
+
+    pub fn whole_spans(&self) -> &Spans3D {
+        &self.whole_spans
+    }
+
+    /// the type of the coordinates of every piece
+    pub fn coordinates(&self) -> Precision {
+        self.coordinates
+    }
+
+    pub fn arrays(&self) -> &[ArrayDeclaration] {
+        &self.arrays
+    }
+
+    /// the spans and source file of every piece, in the order they were added
+    pub fn pieces(&self) -> &[(Spans3D, String)] {
+        &self.pieces
+    }
+
+    /// write the `.pvtr` file to `writer`
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut writer = EventWriter::new(writer);
+
+        writer.write(XmlEvent::StartDocument {
+            version: xml::common::XmlVersion::Version10,
+            encoding: None,
+            standalone: None,
+        })?;
+
+        let whole_extent = self.whole_spans.to_string();
+
+        write_vtk::start_element(
+            &mut writer,
+            "VTKFile",
+            &[
+                ("type", "PRectilinearGrid"),
+                ("version", "1.0"),
+                ("byte_order", ByteOrder::LittleEndian.to_str()),
+                ("header_type", "UInt64"),
+            ],
+        )?;
+        write_vtk::start_element(
+            &mut writer,
+            "PRectilinearGrid",
+            &[("WholeExtent", &whole_extent), ("GhostLevel", "0")],
+        )?;
+
+        write_vtk::open_element(&mut writer, "PPointData")?;
+        self.write_declarations(&mut writer, false)?;
+        write_vtk::close_element(&mut writer, "PPointData")?;
+
+        if self.arrays.iter().any(|array| array.cell) {
+            write_vtk::open_element(&mut writer, "PCellData")?;
+            self.write_declarations(&mut writer, true)?;
+            write_vtk::close_element(&mut writer, "PCellData")?;
+        }
+
+        write_vtk::open_element(&mut writer, "PCoordinates")?;
+        for axis in ["X", "Y", "Z"] {
+            let declaration = ArrayDeclaration::new(axis, self.coordinates, 1, false);
+            write_declaration(&mut writer, &declaration)?;
+        }
+        write_vtk::close_element(&mut writer, "PCoordinates")?;
+
+        for (spans, source) in &self.pieces {
+            let extent = spans.to_string();
+            write_vtk::start_element(
+                &mut writer,
+                "Piece",
+                &[("Extent", &extent), ("Source", source)],
+            )?;
+            write_vtk::close_element(&mut writer, "Piece")?;
+        }
+
+        write_vtk::close_element(&mut writer, "PRectilinearGrid")?;
+        write_vtk::close_element(&mut writer, "VTKFile")?;
+
+        Ok(())
+    }
+
+    /// declare either the point or the cell arrays of the dataset
+    fn write_declarations<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        cell: bool,
+    ) -> Result<(), Error> {
+        for array in self.arrays.iter().filter(|array| array.cell == cell) {
+            write_declaration(writer, array)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_declaration<W: Write>(
+    writer: &mut EventWriter<W>,
+    array: &ArrayDeclaration,
+) -> Result<(), Error> {
+    let components = array.components.to_string();
+
+    write_vtk::start_element(
+        writer,
+        "PDataArray",
+        &[
+            ("type", array.precision.to_str()),
+            ("Name", &array.name),
+            ("NumberOfComponents", &components),
+        ],
+    )?;
+    write_vtk::close_element(writer, "PDataArray")?;
+
+    Ok(())
+}
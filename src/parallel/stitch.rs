@@ -0,0 +1,394 @@
+//! Reading the pieces of a `.pvtr` file and assembling them into a single domain
+use super::{ArrayDeclaration, ParallelRectilinear};
+use crate::prelude::*;
+use crate::{Mesh3D, Rectilinear3D, Scalar3D, Spans3D, Vector3D};
+
+use ndarray::s;
+use nom::bytes::complete::take_till;
+use std::path::Path;
+
+/// Every piece of a parallel rectilinear dataset, read by [`read_pvtr`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParallelDataset<D> {
+    /// the `WholeExtent` of the dataset
+    pub whole_spans: Spans3D,
+    /// the contents of each piece file, in the order the pieces are listed
+    pub pieces: Vec<VtkData<Rectilinear3D<f64, Binary>, D>>,
+}
+
+impl<D> ParallelDataset<D> {
+    /// the mesh of the whole dataset, assembled from the coordinates of every piece
+    pub fn domain(&self) -> Rectilinear3D<f64, Binary> {
+        let whole = &self.whole_spans;
+        let mut x_locations = vec![0.; whole.x_len()];
+        let mut y_locations = vec![0.; whole.y_len()];
+        let mut z_locations = vec![0.; whole.z_len()];
+
+        for piece in &self.pieces {
+            let spans = &piece.domain.spans;
+            let mesh = &piece.domain.mesh;
+            let (x, y, z) = offset(whole, spans);
+
+            x_locations[x..x + mesh.x_locations.len()].copy_from_slice(&mesh.x_locations);
+            y_locations[y..y + mesh.y_locations.len()].copy_from_slice(&mesh.y_locations);
+            z_locations[z..z + mesh.z_locations.len()].copy_from_slice(&mesh.z_locations);
+        }
+
+        let mesh = Mesh3D::new(x_locations, y_locations, z_locations);
+        Rectilinear3D::new(mesh, whole.clone())
+    }
+
+    /// assemble a `<PointData>` array of the whole dataset from the array of each piece
+    ///
+    /// Neighbouring pieces share the points on their boundary, which are only placed once. An
+    /// error is returned if the dataset has no pieces.
+    ///
+    /// ```no_run
+    /// #[derive(vtk::ParseArray)]
+    /// #[vtk_parse(spans = "vtk::Spans3D")]
+    /// pub struct Fields {
+    ///     rho: vtk::Scalar3D<f64>,
+    ///     velocity: vtk::Vector3D<f64>,
+    /// }
+    ///
+    /// let dataset: vtk::ParallelDataset<Fields> =
+    ///     vtk::read_pvtr("./data/output.pvtr".as_ref()).unwrap();
+    ///
+    /// let domain = dataset.domain();
+    /// let rho = dataset.stitch(|data| &data.rho).unwrap();
+    /// let velocity = dataset.stitch(|data| &data.velocity).unwrap();
+    /// ```
+    pub fn stitch<A, F>(&self, array: F) -> Result<A, Error>
+    where
+        A: Stitch,
+        F: Fn(&D) -> &A,
+    {
+        self.stitch_with_spans(array, |spans| spans.clone())
+    }
+
+    /// assemble a `<CellData>` array of the whole dataset from the array of each piece, see
+    /// [`stitch`](ParallelDataset::stitch)
+    pub fn stitch_cells<A, F>(&self, array: F) -> Result<A, Error>
+    where
+        A: Stitch,
+        F: Fn(&D) -> &A,
+    {
+        self.stitch_with_spans(array, Spans3D::cell_spans)
+    }
+
+    fn stitch_with_spans<A, F, S>(&self, array: F, spans: S) -> Result<A, Error>
+    where
+        A: Stitch,
+        F: Fn(&D) -> &A,
+        S: Fn(&Spans3D) -> Spans3D,
+    {
+        let whole = spans(&self.whole_spans);
+        let mut pieces = self
+            .pieces
+            .iter()
+            .map(|piece| (array(&piece.data), spans(&piece.domain.spans)));
+
+        let (first, first_spans) = pieces.next().ok_or_else(|| ParseError::IncompletePieces {
+            whole_extent: self.whole_spans.to_string(),
+        })?;
+
+        let mut stitched = A::empty(&whole, first, &first_spans);
+        stitched.insert(&whole, first, &first_spans);
+
+        for (piece, piece_spans) in pieces {
+            stitched.insert(&whole, piece, &piece_spans);
+        }
+
+        Ok(stitched)
+    }
+}
+
+/// Arrays that can be assembled from the arrays of the pieces of a [`ParallelDataset`]
+pub trait Stitch: Sized {
+    /// an array spanning `whole`, with the same number of components as the array `piece`
+    /// spanning `spans`. The values are overwritten by [`insert`](Stitch::insert)
+    fn empty(whole: &Spans3D, piece: &Self, spans: &Spans3D) -> Self;
+
+    /// copy the values of the array `piece` spanning `spans` to their location in this array,
+    /// which spans `whole`
+    fn insert(&mut self, whole: &Spans3D, piece: &Self, spans: &Spans3D);
+}
+
+impl<NUM: Numeric> Stitch for Scalar3D<NUM> {
+    fn empty(whole: &Spans3D, _piece: &Self, _spans: &Spans3D) -> Self {
+        let shape = (whole.x_len(), whole.y_len(), whole.z_len());
+        Scalar3D::new(Array3::from_elem(shape, NUM::ZERO))
+    }
+
+    fn insert(&mut self, whole: &Spans3D, piece: &Self, spans: &Spans3D) {
+        let (x, y, z) = offset(whole, spans);
+        let (nx, ny, nz) = piece.dim();
+
+        self.slice_mut(s![x..x + nx, y..y + ny, z..z + nz])
+            .assign(&**piece);
+    }
+}
+
+impl<NUM: Numeric> Stitch for Vector3D<NUM> {
+    fn empty(whole: &Spans3D, piece: &Self, _spans: &Spans3D) -> Self {
+        let shape = (piece.dim().0, whole.x_len(), whole.y_len(), whole.z_len());
+        Vector3D::new(Array4::from_elem(shape, NUM::ZERO))
+    }
+
+    fn insert(&mut self, whole: &Spans3D, piece: &Self, spans: &Spans3D) {
+        let (x, y, z) = offset(whole, spans);
+        let (_, nx, ny, nz) = piece.dim();
+
+        self.slice_mut(s![.., x..x + nx, y..y + ny, z..z + nz])
+            .assign(&**piece);
+    }
+}
+
+/// values in the order of the file: the components of each point next to each other, with the
+/// x index changing fastest, then y, then z
+impl<NUM: Numeric> Stitch for Vec<NUM> {
+    fn empty(whole: &Spans3D, piece: &Self, spans: &Spans3D) -> Self {
+        let components = piece.len() / tuples(spans).max(1);
+        vec![NUM::ZERO; tuples(whole) * components]
+    }
+
+    fn insert(&mut self, whole: &Spans3D, piece: &Self, spans: &Spans3D) {
+        let (x, y, z) = offset(whole, spans);
+        let components = piece.len() / tuples(spans).max(1);
+        let row = spans.x_len() * components;
+
+        // every row of the piece along x is contiguous in the whole array
+        for (index, values) in piece.chunks(row).enumerate() {
+            let (j, k) = (index % spans.y_len(), index / spans.y_len());
+            let start = ((z + k) * whole.y_len() + y + j) * whole.x_len() + x;
+
+            self[start * components..start * components + row].copy_from_slice(values);
+        }
+    }
+}
+
+fn tuples(spans: &Spans3D) -> usize {
+    spans.x_len() * spans.y_len() * spans.z_len()
+}
+
+/// the index of the first point of `spans` within `whole`
+fn offset(whole: &Spans3D, spans: &Spans3D) -> (usize, usize, usize) {
+    (
+        spans.x_start - whole.x_start,
+        spans.y_start - whole.y_start,
+        spans.z_start - whole.z_start,
+    )
+}
+
+/// read a `.pvtr` file and every piece it lists with [`read_vtk`](crate::read_vtk)
+///
+/// The source of each piece is relative to the directory of the `.pvtr` file. The extent of
+/// every piece file must match the extent of its `<Piece>` in the `.pvtr` file, and the pieces
+/// must cover the whole extent of the dataset.
+pub fn read_pvtr<D, ArrayVisitor>(path: &Path) -> Result<ParallelDataset<D>, Error>
+where
+    D: ParseArray<Visitor = ArrayVisitor>,
+    ArrayVisitor: Visitor<Spans3D, Output = D>,
+{
+    let bytes = std::fs::read(path)?;
+    let description = parse_pvtr(&bytes)?;
+    check_coverage(&description)?;
+
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut pieces = Vec::with_capacity(description.pieces.len());
+
+    for (spans, source) in &description.pieces {
+        let piece: VtkData<Rectilinear3D<f64, Binary>, D> =
+            parse::read_and_parse::<_, _, _, Mesh3D<f64, Binary>, _, _>(&directory.join(source))?;
+
+        if &piece.domain.spans != spans {
+            return Err(ParseError::InvalidExtent {
+                extent: piece.domain.spans.to_string(),
+                offset: None,
+            }
+            .into());
+        }
+
+        pieces.push(piece);
+    }
+
+    Ok(ParallelDataset {
+        whole_spans: description.whole_spans,
+        pieces,
+    })
+}
+
+/// read the contents of a `.pvtr` file, without reading any of its pieces
+pub fn parse_pvtr(bytes: &[u8]) -> Result<ParallelRectilinear, Error> {
+    let xml = parse::xml_elements(bytes);
+
+    let (_, description) = describe(xml)
+        .map_err(|e| ParseError::from_nom_in(e, bytes, "could not read the parallel file"))?;
+
+    Ok(description)
+}
+
+fn describe(xml: &[u8]) -> IResult<'_, ParallelRectilinear> {
+    let (_, whole_spans) = parse::find_extent::<Spans3D>(xml)?;
+
+    let mut arrays = Vec::new();
+    for (section, cell) in [(&b"PPointData"[..], false), (&b"PCellData"[..], true)] {
+        if let Ok((_, contents)) = parse::find_section(xml, section) {
+            for (name, precision, components) in declarations(contents)? {
+                arrays.push(ArrayDeclaration::new(&name, precision, components, cell));
+            }
+        }
+    }
+
+    // the coordinates are all of the same type, which is assumed to be `Float64` if missing
+    let coordinates = match parse::find_section(xml, b"PCoordinates") {
+        Ok((_, contents)) => declarations(contents)?
+            .first()
+            .map(|(_, precision, _)| *precision)
+            .unwrap_or(Precision::Float64),
+        Err(_) => Precision::Float64,
+    };
+
+    let mut pieces = Vec::new();
+    let mut rest = xml;
+    while let Ok((piece_start, _)) = parse::take_until_consume(rest, b"<Piece") {
+        let (piece_end, element) = take_till(|c| c == b'>')(piece_start)?;
+        let (_, attributes) = parse::read_element_attributes(element)?;
+
+        let spans = parse::parse_extent(attributes.require(b"Extent")?)?;
+        let source = String::from_utf8_lossy(attributes.require(b"Source")?).into_owned();
+        pieces.push((spans, source));
+
+        rest = piece_end;
+    }
+
+    let description = ParallelRectilinear {
+        whole_spans,
+        coordinates,
+        arrays,
+        pieces,
+    };
+
+    Ok((xml, description))
+}
+
+/// the name, type and number of components of every `<PDataArray>` of a section
+fn declarations(mut section: &[u8]) -> Result<Vec<(String, Precision, usize)>, parse::NomErr<'_>> {
+    let mut declarations = Vec::new();
+
+    while let Ok((array_start, _)) = parse::take_until_consume(section, b"<PDataArray") {
+        let (array_end, element) = take_till(|c| c == b'>')(array_start)?;
+        let (_, attributes) = parse::read_element_attributes(element)?;
+
+        let type_name = attributes.require(b"type")?;
+        let precision = Precision::from_bytes(type_name)
+            .ok_or_else(|| parse::NomError::nom(type_name, nom::error::ErrorKind::Tag))?;
+
+        let name = attributes
+            .get(b"Name")
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .unwrap_or_default();
+
+        let components = match attributes.get(b"NumberOfComponents") {
+            Some(components) => parse::parse_number(components, &name)?,
+            None => 1,
+        };
+
+        declarations.push((name, precision, components));
+        section = array_end;
+    }
+
+    Ok(declarations)
+}
+
+/// check that every piece is within the whole extent, and that the cells of the pieces cover
+/// every cell of the whole extent exactly once.
+///
+/// Neighbouring pieces share the points on their boundary, so the pieces are compared by the
+/// cells between their points. Only the extents are compared, so nothing is allocated for the
+/// whole extent.
+fn check_coverage(description: &ParallelRectilinear) -> Result<(), ParseError> {
+    let whole = &description.whole_spans;
+    let invalid = |spans: &Spans3D| ParseError::InvalidExtent {
+        extent: spans.to_string(),
+        offset: None,
+    };
+
+    let whole_cells = cell_ranges(whole, whole);
+    let whole_volume = volume(&whole_cells).ok_or_else(|| invalid(whole))?;
+    let mut covered: u128 = 0;
+
+    for (idx, (spans, _)) in description.pieces.iter().enumerate() {
+        let inside = spans.x_start >= whole.x_start
+            && spans.x_end <= whole.x_end
+            && spans.y_start >= whole.y_start
+            && spans.y_end <= whole.y_end
+            && spans.z_start >= whole.z_start
+            && spans.z_end <= whole.z_end;
+
+        if !inside
+            || spans.x_end < spans.x_start
+            || spans.y_end < spans.y_start
+            || spans.z_end < spans.z_start
+        {
+            return Err(invalid(spans));
+        }
+
+        let cells = cell_ranges(whole, spans);
+
+        if let Some((other, _)) = description.pieces[..idx]
+            .iter()
+            .find(|(other, _)| overlap(&cells, &cell_ranges(whole, other)))
+        {
+            return Err(ParseError::OverlappingPieces {
+                first: other.to_string(),
+                second: spans.to_string(),
+            });
+        }
+
+        // the pieces do not overlap and are inside the whole extent, so their volume is at
+        // most the volume of the whole extent
+        covered += volume(&cells).ok_or_else(|| invalid(spans))?;
+    }
+
+    if covered != whole_volume {
+        return Err(ParseError::IncompletePieces {
+            whole_extent: whole.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// the half open ranges of the cells of `spans` in each direction. A direction with a single
+/// point in the whole extent has a single cell
+fn cell_ranges(whole: &Spans3D, spans: &Spans3D) -> [(usize, usize); 3] {
+    let range = |start: usize, end: usize, whole_start: usize, whole_end: usize| {
+        if whole_start == whole_end {
+            (start, start + 1)
+        } else {
+            (start, end)
+        }
+    };
+
+    [
+        range(spans.x_start, spans.x_end, whole.x_start, whole.x_end),
+        range(spans.y_start, spans.y_end, whole.y_start, whole.y_end),
+        range(spans.z_start, spans.z_end, whole.z_start, whole.z_end),
+    ]
+}
+
+/// the number of cells in the ranges
+fn volume(ranges: &[(usize, usize); 3]) -> Option<u128> {
+    ranges.iter().try_fold(1u128, |volume, (start, end)| {
+        volume.checked_mul((end - start) as u128)
+    })
+}
+
+/// whether the ranges share any cell
+fn overlap(first: &[(usize, usize); 3], second: &[(usize, usize); 3]) -> bool {
+    first
+        .iter()
+        .zip(second)
+        .all(|(first, second)| first.0.max(second.0) < first.1.min(second.1))
+}
@@ -3,65 +3,323 @@
 //! most of the time you will not need to interact with this file,
 //! instead derive `ParseDataArray`
 
+use crate::compression;
 use crate::prelude::*;
 use crate::utils;
-use nom::bytes::complete::{tag, take, take_till, take_until};
+use crate::{Compressor, FieldData, FieldValue, LazyArray};
+use nom::bytes::complete::{tag, take_till, take_until};
 
-use std::fmt;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io::Read;
 
-type NomErr<'a> = nom::Err<nom::error::Error<&'a [u8]>>;
+pub(crate) type NomErr<'a> = nom::Err<NomError<'a>>;
+
+/// The result of the nom parsers of this crate
+pub type IResult<'a, O> = nom::IResult<&'a [u8], O, NomError<'a>>;
+
+/// The error type of the nom parsers of this crate.
+///
+/// `input` is the part of the file where the error occured, and `kind` describes what went wrong.
+/// Errors are converted to a [`ParseError`] once parsing has stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NomError<'a> {
+    pub input: &'a [u8],
+    pub kind: NomErrorKind,
+}
+
+/// What caused a [`NomError`], see the variants of [`ParseError`] for a description of each
+#[derive(Debug, Clone, PartialEq)]
+pub enum NomErrorKind {
+    MissingAttribute {
+        attribute: String,
+        array: String,
+    },
+    InvalidNumber {
+        value: String,
+        array: String,
+    },
+    UnexpectedName {
+        expected: String,
+        found: String,
+    },
+    MissingArray {
+        array: String,
+    },
+    InvalidBase64 {
+        array: String,
+    },
+    InvalidExtent {
+        extent: String,
+    },
+    UnrepresentableValue {
+        array: String,
+        value: String,
+        precision: Precision,
+    },
+    UnclosedBlock {
+        block: String,
+    },
+    /// any other malformed input, as described by a nom combinator
+    Nom(nom::error::ErrorKind),
+}
+
+impl<'a> NomError<'a> {
+    /// an error of the given `kind` at `input`
+    pub fn new(input: &'a [u8], kind: NomErrorKind) -> Self {
+        Self { input, kind }
+    }
+
+    /// an error of the given `kind` at `input`, wrapped in a `nom::Err` so that it can be
+    /// returned from a parser
+    pub(crate) fn err(input: &'a [u8], kind: NomErrorKind) -> NomErr<'a> {
+        nom::Err::Error(Self::new(input, kind))
+    }
+
+    /// an untyped error at `input`
+    pub(crate) fn nom(input: &'a [u8], kind: nom::error::ErrorKind) -> NomErr<'a> {
+        Self::err(input, NomErrorKind::Nom(kind))
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a [u8]> for NomError<'a> {
+    fn from_error_kind(input: &'a [u8], kind: nom::error::ErrorKind) -> Self {
+        Self::new(input, NomErrorKind::Nom(kind))
+    }
+
+    fn append(_input: &'a [u8], _kind: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
 
 /// An error caused from parsing the vtk files
+///
+/// `offset` is the position of the error in bytes from the start of the file, if it is known.
 #[derive(Debug, thiserror::Error)]
-pub struct ParseError {
-    nom_reason: Vec<u8>,
-    nom_code: nom::error::ErrorKind,
-    extra_info: &'static str,
+pub enum ParseError {
+    /// a required attribute (such as `type` or `format`) of an element is missing. `array` is
+    /// the `Name` of the array, or empty if the element is not a named array
+    #[error("the `{attribute}` attribute of the array `{array}` is missing{}", at(.offset))]
+    MissingAttribute {
+        attribute: String,
+        array: String,
+        offset: Option<usize>,
+    },
+    /// an attribute or an ascii value could not be read as a number
+    #[error("`{value}` in the array `{array}` is not a valid number{}", at(.offset))]
+    InvalidNumber {
+        value: String,
+        array: String,
+        offset: Option<usize>,
+    },
+    /// the number of values of an array does not match the spans of the file
+    #[error("the array `{array}` has {found} values, but {expected} were expected from the spans of the file")]
+    ArrayLength {
+        array: String,
+        expected: usize,
+        found: usize,
+    },
+    /// the next array does not have the expected `Name`
+    #[error("expected the array `{expected}` but found `{found}`{}", at(.offset))]
+    UnexpectedName {
+        expected: String,
+        found: String,
+        offset: Option<usize>,
+    },
+    /// there is no array with this `Name` in the section it is read from
+    #[error("the array `{array}` was not found in the file{}", at(.offset))]
+    MissingArray {
+        array: String,
+        offset: Option<usize>,
+    },
+    /// the contents of a `binary` array (inline, or in a base64 appended section) are not valid
+    /// base64, or are shorter than their header describes
+    #[error("the array `{array}` is not valid base64{}", at(.offset))]
+    InvalidBase64 {
+        array: String,
+        offset: Option<usize>,
+    },
+    /// a value of an array does not fit in the element type of the container it is read into,
+    /// such as `300` read into a `u8` or `1.5` read into an `i32`
+    #[error("the value `{value}` of the array `{array}` can not be read as {}", .precision.to_str())]
+    UnrepresentableValue {
+        array: String,
+        value: String,
+        precision: Precision,
+    },
+    /// the `WholeExtent` (or `Extent`) of the file could not be read
+    #[error("`{extent}` is not a valid extent{}", at(.offset))]
+    InvalidExtent {
+        extent: String,
+        offset: Option<usize>,
+    },
+    /// a `<Block>` of a multiblock (`.vtm`) file is not closed before the end of the dataset.
+    /// `block` is the `name` of the block, or its `index` if it is not named
+    #[error("the block `{block}` of the multiblock file is never closed{}", at(.offset))]
+    UnclosedBlock {
+        block: String,
+        offset: Option<usize>,
+    },
+    /// the header of a compressed appended array does not describe the blocks that follow it,
+    /// or a block could not be decompressed
+    #[error("the compressed array `{array}` is invalid: {reason}")]
+    InvalidCompression { array: String, reason: &'static str },
+    /// the pieces of a parallel file do not cover its `WholeExtent`
+    #[error("the pieces of the parallel file do not cover the whole extent `{whole_extent}`")]
+    IncompletePieces { whole_extent: String },
+    /// two pieces of a parallel file contain the same cells
+    #[error("the pieces `{first}` and `{second}` of the parallel file overlap")]
+    OverlappingPieces { first: String, second: String },
+    /// any other malformed input
+    #[error("{}", display_nom(.extra_info, .nom_reason, .nom_code, .offset))]
+    Nom {
+        nom_reason: Vec<u8>,
+        nom_code: nom::error::ErrorKind,
+        extra_info: &'static str,
+        offset: Option<usize>,
+    },
+}
+
+fn at(offset: &Option<usize>) -> String {
+    match offset {
+        Some(offset) => format!(" (at byte {})", offset),
+        None => String::new(),
+    }
+}
+
+fn display_nom(
+    extra_info: &str,
+    nom_reason: &[u8],
+    nom_code: &nom::error::ErrorKind,
+    offset: &Option<usize>,
+) -> String {
+    match std::str::from_utf8(nom_reason) {
+        Ok(string_representation) => format!(
+            "reason:{}{} \tnom_reason:{} \t errorcode:{:?}",
+            extra_info,
+            at(offset),
+            string_representation,
+            nom_code
+        ),
+        Err(_) => format!(
+            "reason:{}{} \t <nom reason omitted> \t errorcode:{:?} (could not convert nom bytes to string- fallback)",
+            extra_info,
+            at(offset),
+            nom_code
+        ),
+    }
 }
 
 impl ParseError {
     pub fn from_nom(x: NomErr, extra_info: &'static str) -> Self {
+        Self::from_nom_error(x, None, extra_info)
+    }
+
+    /// like [`from_nom`](Self::from_nom), with the `offset` of the error in bytes from the start
+    /// of `document`
+    pub fn from_nom_in(x: NomErr, document: &[u8], extra_info: &'static str) -> Self {
+        Self::from_nom_error(x, Some(document), extra_info)
+    }
+
+    fn from_nom_error(x: NomErr, document: Option<&[u8]>, extra_info: &'static str) -> Self {
         let error = match x {
             nom::Err::Incomplete(_) => unreachable!(),
             nom::Err::Error(e) => e,
             nom::Err::Failure(e) => e,
         };
-        Self {
-            nom_reason: error.input.to_vec(),
-            nom_code: error.code,
-            extra_info,
+
+        // the error may point to a buffer other than the document, such as decoded base64
+        let offset = document.and_then(|document| {
+            let start = document.as_ptr() as usize;
+            let position = error.input.as_ptr() as usize;
+            (start..=start + document.len())
+                .contains(&position)
+                .then(|| position - start)
+        });
+
+        match error.kind {
+            NomErrorKind::MissingAttribute { attribute, array } => Self::MissingAttribute {
+                attribute,
+                array,
+                offset,
+            },
+            NomErrorKind::InvalidNumber { value, array } => Self::InvalidNumber {
+                value,
+                array,
+                offset,
+            },
+            NomErrorKind::UnexpectedName { expected, found } => Self::UnexpectedName {
+                expected,
+                found,
+                offset,
+            },
+            NomErrorKind::MissingArray { array } => Self::MissingArray { array, offset },
+            NomErrorKind::InvalidBase64 { array } => Self::InvalidBase64 { array, offset },
+            NomErrorKind::InvalidExtent { extent } => Self::InvalidExtent { extent, offset },
+            NomErrorKind::UnrepresentableValue {
+                array,
+                value,
+                precision,
+            } => Self::UnrepresentableValue {
+                array,
+                value,
+                precision,
+            },
+            NomErrorKind::UnclosedBlock { block } => Self::UnclosedBlock { block, offset },
+            NomErrorKind::Nom(nom_code) => Self::Nom {
+                nom_reason: error.input.to_vec(),
+                nom_code,
+                extra_info,
+                offset,
+            },
+        }
+    }
+
+    /// set the name of the array of an [`ArrayLength`](Self::ArrayLength),
+    /// [`UnrepresentableValue`](Self::UnrepresentableValue) or
+    /// [`InvalidCompression`](Self::InvalidCompression) error, which is not known by
+    /// [`FromBuffer`](crate::FromBuffer) or the decompression of the array
+    pub fn with_array_name(self, name: &str) -> Self {
+        match self {
+            Self::UnrepresentableValue {
+                value, precision, ..
+            } => Self::UnrepresentableValue {
+                array: name.to_string(),
+                value,
+                precision,
+            },
+            Self::ArrayLength {
+                expected, found, ..
+            } => Self::ArrayLength {
+                array: name.to_string(),
+                expected,
+                found,
+            },
+            Self::InvalidBase64 { offset, .. } => Self::InvalidBase64 {
+                array: name.to_string(),
+                offset,
+            },
+            Self::InvalidCompression { reason, .. } => Self::InvalidCompression {
+                array: name.to_string(),
+                reason,
+            },
+            other => other,
         }
     }
 }
+
 impl<'a> From<NomErr<'a>> for ParseError {
     fn from(x: NomErr<'a>) -> Self {
         ParseError::from_nom(x, "Caused by From Impl")
     }
 }
 
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match String::from_utf8(self.nom_reason.clone()) {
-            Ok(string_representation) => {
-                write!(
-                    f,
-                    "reason:{} \tnom_reason:{} \t errorcode:{:?}",
-                    self.extra_info, string_representation, self.nom_code
-                )
-            }
-            Err(_) => {
-                write!(
-                    f,
-                    "reason:{} \t <nom reason omitted> \t errorcode:{:?} (could not convert nom bytes to string- fallback)",
-                    self.extra_info, self.nom_code
-                )
-            }
-        }
-    }
-}
-
-/// read in and parse an entire vtk file for a given path
+/// read in and parse an entire vtk file for a given path.
+///
+/// The whole file is read into memory and dropped once it is parsed, so the arrays of
+/// [`LazyArray`](crate::LazyArray) fields have to be loaded from a second read of the file.
+/// With the `mmap` feature, [`read_vtk_mmap`](crate::read_vtk_mmap) only reads the parts of the
+/// file that are parsed.
 pub fn read_and_parse<GEOMETRY, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
     path: &std::path::Path,
 ) -> Result<VtkData<GEOMETRY, D>, Error>
@@ -92,33 +350,208 @@ where
     SPAN: ParseSpan,
     DOMAIN: From<(MESH, SPAN)>,
 {
-    let (rest, spans) = find_extent::<SPAN>(i).map_err(|e: NomErr| {
-        ParseError::from_nom(
+    // the arrays are found from the elements only, so sections that are missing from the file
+    // are not searched for through the appended data
+    let xml = xml_elements(i);
+
+    parse_elements(i, xml, &i[xml.len()..])
+}
+
+/// read in and parse every `<Piece>` of a vtk file for a given path, such as a file written
+/// by [`write_vtk_pieces`](crate::write_vtk_pieces). Each piece is read with its own `Extent`
+pub fn read_and_parse_pieces<GEOMETRY, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
+    path: &std::path::Path,
+) -> Result<Vec<VtkData<GEOMETRY, D>>, Error>
+where
+    D: ParseArray<Visitor = ArrayVisitor>,
+    ArrayVisitor: Visitor<SPAN, Output = D>,
+    MESH: ParseMesh<Visitor = MeshVisitor>,
+    MeshVisitor: Visitor<SPAN, Output = MESH>,
+    SPAN: ParseSpan,
+    GEOMETRY: From<(MESH, SPAN)>,
+{
+    let mut file = std::fs::File::open(path)?;
+    let mut buffer = Vec::with_capacity(1024 * 1024 * 3);
+    file.read_to_end(&mut buffer)?;
+
+    parse_xml_pieces(&buffer)
+}
+
+#[doc(hidden)]
+pub fn parse_xml_pieces<DOMAIN, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
+    i: &[u8],
+) -> Result<Vec<VtkData<DOMAIN, D>>, Error>
+where
+    D: ParseArray<Visitor = ArrayVisitor>,
+    ArrayVisitor: Visitor<SPAN, Output = D>,
+    MESH: ParseMesh<Visitor = MeshVisitor>,
+    MeshVisitor: Visitor<SPAN, Output = MESH>,
+    SPAN: ParseSpan,
+    DOMAIN: From<(MESH, SPAN)>,
+{
+    let xml = xml_elements(i);
+    let appended = &i[xml.len()..];
+
+    let mut piece_starts = Vec::new();
+    let mut rest = xml;
+    while let Ok((after_piece, _)) = take_until_consume(rest, b"<Piece") {
+        piece_starts.push(xml.len() - after_piece.len() - b"<Piece".len());
+        rest = after_piece;
+    }
+
+    // a file with a single piece is parsed like any other file
+    if piece_starts.len() <= 1 {
+        return parse_elements(i, xml, appended).map(|data| vec![data]);
+    }
+
+    let (attributes, field_data) = parse_file_elements(i, &xml[..piece_starts[0]])?;
+    let piece_ends = piece_starts.iter().skip(1).copied().chain([xml.len()]);
+
+    // each piece is parsed from its own elements within the file, so errors are reported at
+    // their position in the file. The offsets of its appended arrays are relative to the
+    // appended section of the whole file
+    piece_starts
+        .iter()
+        .zip(piece_ends)
+        .map(|(&start, end)| {
+            let elements = PieceElements::Piece {
+                dataset: &xml[..piece_starts[0]],
+                piece: &xml[start..end],
+            };
+            parse_piece(i, elements, appended, attributes, field_data.clone())
+        })
+        .collect()
+}
+
+/// parse a file from its XML `elements` and its `appended` section. Errors are reported
+/// relative to `document`, the bytes of the whole file
+fn parse_elements<DOMAIN, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
+    document: &[u8],
+    xml: &[u8],
+    appended: &[u8],
+) -> Result<VtkData<DOMAIN, D>, Error>
+where
+    D: ParseArray<Visitor = ArrayVisitor>,
+    ArrayVisitor: Visitor<SPAN, Output = D>,
+    MESH: ParseMesh<Visitor = MeshVisitor>,
+    MeshVisitor: Visitor<SPAN, Output = MESH>,
+    SPAN: ParseSpan,
+    DOMAIN: From<(MESH, SPAN)>,
+{
+    let (attributes, field_data) = parse_file_elements(document, xml)?;
+    parse_piece(
+        document,
+        PieceElements::File(xml),
+        appended,
+        attributes,
+        field_data,
+    )
+}
+
+/// the XML elements that a piece is parsed from
+#[derive(Clone, Copy)]
+enum PieceElements<'a> {
+    /// every element of a file with a single piece
+    File(&'a [u8]),
+    /// a piece of a file with several pieces, and the elements of the file before the first piece
+    Piece { dataset: &'a [u8], piece: &'a [u8] },
+}
+
+/// read the attributes of the `<VTKFile>` element and the field data of the dataset from the
+/// XML elements `xml`, which are part of `document`
+fn parse_file_elements(document: &[u8], xml: &[u8]) -> Result<(FileAttributes, FieldData), Error> {
+    let i = document;
+
+    let (_, attributes) = read_file_attributes(xml).map_err(|e: NomErr| {
+        ParseError::from_nom_in(
             e,
-            "Error in parsing find_extent for the WholeExtent span information",
+            i,
+            "unsupported attributes of the <VTKFile> element. The appended data may be compressed with a compressor whose cargo feature is not enabled",
         )
     })?;
 
-    let (rest, location_visitor) =
-        MeshVisitor::read_headers(&spans, rest).map_err(|e: NomErr| {
-            ParseError::from_nom(e, "could not read the values of the coordinate arrays")
-        })?;
+    let (_, field_data) = read_field_data(xml, attributes).map_err(|e: NomErr| {
+        ParseError::from_nom_in(e, i, "could not read the arrays of the <FieldData> element")
+    })?;
+
+    Ok((attributes, field_data))
+}
+
+/// parse the mesh and arrays of a single piece from its XML `elements`, which are part of
+/// `document`, and the `appended` section of the file
+fn parse_piece<DOMAIN, SPAN, D, MESH, ArrayVisitor, MeshVisitor>(
+    document: &[u8],
+    elements: PieceElements<'_>,
+    appended: &[u8],
+    attributes: FileAttributes,
+    field_data: FieldData,
+) -> Result<VtkData<DOMAIN, D>, Error>
+where
+    D: ParseArray<Visitor = ArrayVisitor>,
+    ArrayVisitor: Visitor<SPAN, Output = D>,
+    MESH: ParseMesh<Visitor = MeshVisitor>,
+    MeshVisitor: Visitor<SPAN, Output = MESH>,
+    SPAN: ParseSpan,
+    DOMAIN: From<(MESH, SPAN)>,
+{
+    let i = document;
+
+    let xml = match elements {
+        PieceElements::File(xml) => xml,
+        PieceElements::Piece { piece, .. } => piece,
+    };
+
+    let (rest, spans) = SPAN::read_spans(xml).map_err(|e: NomErr| {
+        ParseError::from_nom_in(
+            e,
+            i,
+            "Error in parsing the span information (such as the WholeExtent) of the file",
+        )
+    })?;
+
+    // the dataset element of a file with a single piece is part of its elements
+    let dataset = match elements {
+        PieceElements::File(xml) => xml,
+        PieceElements::Piece { dataset, .. } => dataset,
+    };
+
+    // both visitors find their own sections of the piece, since the order of the
+    // sections depends on the program that wrote the file
+    let location_visitor = MeshVisitor::read_piece_headers(&spans, dataset, rest);
+    let (_, location_visitor) = location_visitor.map_err(|e: NomErr| {
+        ParseError::from_nom_in(e, i, "could not read the values of the coordinate arrays")
+    })?;
 
-    let (rest, array_visitor) = ArrayVisitor::read_headers(&spans, rest).map_err(|e: NomErr| {
-        ParseError::from_nom(e, "could not read the values of the data arrays. Attributes of <DataArray> Elements may be in an unexpected order")
+    let array_visitor = ArrayVisitor::read_piece_headers(&spans, dataset, rest);
+    let (_, array_visitor) = array_visitor.map_err(|e: NomErr| {
+        ParseError::from_nom_in(e, i, "could not read the values of the data arrays. Attributes of <DataArray> Elements may be in an unexpected order")
     })?;
 
     let mut reader_buffer = Vec::new();
     location_visitor.add_to_appended_reader(&mut reader_buffer);
     array_visitor.add_to_appended_reader(&mut reader_buffer);
 
-    read_appended_array_buffers(reader_buffer, rest)?;
+    read_appended_array_buffers(reader_buffer, appended, attributes)?;
 
     let data: D = array_visitor.finish(&spans)?;
     let mesh: MESH = location_visitor.finish(&spans)?;
     let domain = DOMAIN::from((mesh, spans));
 
-    Ok(VtkData { domain, data })
+    Ok(VtkData {
+        domain,
+        data,
+        field_data,
+    })
+}
+
+/// the XML elements of a file, up to the start of the `<AppendedData>` section.
+///
+/// Searching only these bytes avoids reading through the (possibly very large) appended data
+pub(crate) fn xml_elements(bytes: &[u8]) -> &[u8] {
+    match take_until::<_, _, NomError>("<AppendedData")(bytes) {
+        Ok((_, elements)) => elements,
+        Err(_) => bytes,
+    }
 }
 
 #[allow(dead_code)]
@@ -134,41 +567,125 @@ fn print_n_chars(i: &[u8], chars: usize) {
     }
 }
 
-pub(crate) fn find_extent<SPAN: ParseSpan>(i: &[u8]) -> IResult<&[u8], SPAN> {
+pub(crate) fn find_extent<SPAN: ParseSpan>(i: &[u8]) -> IResult<'_, SPAN> {
     let (start_extent, _xml_header_info) = take_until("WholeExtent")(i)?;
     let (extent_string_start, _whole_extent_header) = tag("WholeExtent=\"")(start_extent)?;
-    let (rest_of_document, extent_string) = take_till(|c| c == b'\"')(extent_string_start)?;
+    let (extent_end, extent_string) = take_till(|c| c == b'\"')(extent_string_start)?;
+    let (rest_of_document, _) = tag("\"")(extent_end)?;
 
-    let spans = SPAN::from_str(std::str::from_utf8(extent_string).unwrap());
+    let spans = parse_extent(extent_string)?;
 
     Ok((rest_of_document, spans))
 }
 
+/// find the `Extent` of the first `<Piece>` of a file, which is only a part of the `WholeExtent`
+/// for the pieces of a parallel dataset. Pieces without an `Extent` use the `WholeExtent`.
+///
+/// The remaining bytes start after the `WholeExtent`, like [`find_extent`]. The elements of a
+/// single piece (of a file with several pieces) have no `WholeExtent`, and are read from the
+/// start.
+pub(crate) fn find_piece_extent<SPAN: ParseSpan>(i: &[u8]) -> IResult<'_, SPAN> {
+    let (rest, whole_extent) = match take_until::<_, _, NomError>("WholeExtent")(i) {
+        Ok(_) => {
+            let (rest, whole_extent) = find_extent(i)?;
+            (rest, Some(whole_extent))
+        }
+        Err(_) => (i, None),
+    };
+
+    let piece_extent = match take_until_consume(rest, b"<Piece") {
+        Ok((piece_start, _)) => {
+            let (_, element) = take_till(|c| c == b'>')(piece_start)?;
+            let (_, attributes) = read_element_attributes(element)?;
+
+            attributes.get(b"Extent").map(parse_extent).transpose()?
+        }
+        Err(_) => None,
+    };
+
+    match piece_extent.or(whole_extent) {
+        Some(extent) => Ok((rest, extent)),
+        // without either extent, the error is that of the missing `WholeExtent`
+        None => find_extent(i),
+    }
+}
+
+/// parse the value of a `WholeExtent` or `Extent` attribute
+pub(crate) fn parse_extent<SPAN: ParseSpan>(extent: &[u8]) -> Result<SPAN, NomErr<'_>> {
+    std::str::from_utf8(extent)
+        .ok()
+        .and_then(SPAN::from_str)
+        .ok_or_else(|| {
+            NomError::err(
+                extent,
+                NomErrorKind::InvalidExtent {
+                    extent: String::from_utf8_lossy(extent).into_owned(),
+                },
+            )
+        })
+}
+
 /// Parse a data array (if its inline) or return the offset in the appended section
 pub fn parse_dataarray_or_lazy<'a>(
     xml_bytes: &'a [u8],
     expected_data: &[u8],
     size_hint: usize,
-) -> IResult<&'a [u8], PartialDataArray> {
-    let (mut rest, header) = read_dataarray_header(xml_bytes, expected_data)?;
-    let lazy_array = match header {
-        DataArrayHeader::AppendedBinary { offset, components } => {
-            PartialDataArray::AppendedBinary { offset, components }
-        }
-        DataArrayHeader::InlineAscii { components } => {
-            let (after_dataarray, parsed_data) = parse_ascii_inner_dataarray(rest, size_hint)?;
+) -> IResult<'a, PartialDataArray> {
+    parse_dataarray_inner(xml_bytes, Some(expected_data), size_hint)
+}
+
+/// Parse the next data array regardless of its `Name`.
+///
+/// The arrays of the mesh (such as the `<Coordinates>` of a rectilinear grid) are identified by
+/// their position in their section, and vtk gives them arbitrary names such as `Array 0x55d5c8a0`
+pub(crate) fn parse_next_dataarray_or_lazy(
+    xml_bytes: &[u8],
+    size_hint: usize,
+) -> IResult<'_, PartialDataArray> {
+    parse_dataarray_inner(xml_bytes, None, size_hint)
+}
+
+fn parse_dataarray_inner<'a>(
+    xml_bytes: &'a [u8],
+    expected_data: Option<&[u8]>,
+    size_hint: usize,
+) -> IResult<'a, PartialDataArray> {
+    let (mut rest, description) = read_dataarray_element(xml_bytes, expected_data)?;
+    let lazy_array = match description.header {
+        DataArrayHeader::AppendedBinary {
+            offset,
+            components,
+            precision,
+        } => PartialDataArray::AppendedBinary {
+            name: description.name,
+            offset,
+            components,
+            precision,
+        },
+        DataArrayHeader::InlineAscii {
+            components,
+            precision,
+        } => {
+            let (after_dataarray, parsed_data) =
+                parse_ascii_inner_dataarray(rest, size_hint, precision, &description.name)?;
             rest = after_dataarray;
             PartialDataArray::Parsed {
                 buffer: parsed_data,
                 components,
             }
         }
-        DataArrayHeader::InlineBase64 { components } => {
-            let (after_dataarray, parsed_data) = parse_base64_inner_dataarray(rest, size_hint)?;
+        DataArrayHeader::InlineBase64 {
+            components,
+            precision,
+        } => {
+            // the bytes can only be decoded once the byte order of the file is known
+            let (after_dataarray, encoded) = read_base64_inner_dataarray(rest, &description.name)?;
             rest = after_dataarray;
-            PartialDataArray::Parsed {
-                buffer: parsed_data,
+            PartialDataArray::InlineBase64 {
+                name: description.name,
+                encoded,
                 components,
+                precision,
             }
         }
     };
@@ -177,47 +694,103 @@ pub fn parse_dataarray_or_lazy<'a>(
 }
 
 /// cycle through buffers (and their offsets) and read the binary information from the
-/// <AppendedBinary> section in order
+/// `<AppendedData>` section
+///
+/// Every array starts with a header of `header_type` integers (as described by `attributes`):
+/// the number of bytes in the array for uncompressed files, or the block sizes of the array for
+/// compressed files. Each array is found directly at its offset, so the buffers can be read in
+/// any order. The offsets of a section with `encoding="base64"` index into its base64 text.
+///
+/// Buffers of inline base64 arrays are decoded here as well, since their values depend on the
+/// `byte_order` and `compressor` of the file.
 pub fn read_appended_array_buffers(
     mut buffers: Vec<RefMut<'_, OffsetBuffer>>,
     bytes: &[u8],
+    attributes: FileAttributes,
 ) -> Result<(), ParseError> {
-    // if we have any binary data:
-    if buffers.len() > 0 {
-        //we have some data to read - first organize all of the data by the offsets
-        buffers.sort_unstable_by_key(|x| x.offset);
-
-        let mut iterator = buffers.iter_mut().peekable();
-        let (mut appended_data, _) = crate::parse::setup_appended_read(bytes)?;
-
-        loop {
-            if let Some(current_offset_buffer) = iterator.next() {
-                // get the number of bytes to read based on the next element's offset
-                let reading_offset = iterator
-                    .peek()
-                    .map(|offset_buffer| {
-                        let diff = offset_buffer.offset - current_offset_buffer.offset;
-                        crate::parse::AppendedArrayLength::Known((diff) as usize)
-                    })
-                    .unwrap_or(crate::parse::AppendedArrayLength::UntilEnd);
-
-                let (remaining_appended_data, _) = crate::parse::parse_appended_binary(
-                    appended_data,
-                    reading_offset,
-                    &mut current_offset_buffer.buffer,
-                )?;
-
-                appended_data = remaining_appended_data
-            } else {
-                // there are not more elements in the array - lets leave
-                break;
+    // if we have no appended arrays there may not be an appended section at all, so it is
+    // only found once it is needed
+    let mut appended_section = None;
+
+    for offset_buffer in buffers.iter_mut() {
+        let precision = offset_buffer.precision;
+
+        if let Some(encoded) = offset_buffer.inline_base64.take() {
+            let array = decode_base64_array(&encoded, attributes)
+                .map_err(|e| e.with_array_name(&offset_buffer.name))?;
+
+            offset_buffer
+                .buffer
+                .extend_from_bytes(&array, precision, attributes.byte_order);
+            continue;
+        }
+
+        let (appended_data, encoding) = match appended_section {
+            Some(appended_section) => appended_section,
+            None => *appended_section.insert(find_appended_start(bytes)?),
+        };
+
+        let array_start = usize::try_from(offset_buffer.offset)
+            .ok()
+            .and_then(|offset| appended_data.get(offset..))
+            .ok_or_else(|| {
+                appended_error("the offset of an array is outside of the appended section")
+            })?;
+
+        let array = match (encoding, attributes.compressor) {
+            (AppendedEncoding::Base64, _) => {
+                decode_base64_array(array_start, attributes).map(Cow::Owned)
+            }
+            (AppendedEncoding::Raw, Some(compressor)) => compression::decompress(
+                array_start,
+                compressor,
+                attributes.header_type,
+                attributes.byte_order,
+            )
+            .map(Cow::Owned),
+            (AppendedEncoding::Raw, None) => {
+                read_raw_array(array_start, attributes.header_type, attributes.byte_order)
+                    .map(Cow::Borrowed)
             }
         }
+        .map_err(|e| e.with_array_name(&offset_buffer.name))?;
+
+        offset_buffer
+            .buffer
+            .extend_from_bytes(&array, precision, attributes.byte_order);
     }
 
     Ok(())
 }
 
+/// the bytes of an uncompressed appended array, following its length header.
+///
+/// The length is read from the file, so it is checked against the bytes that follow it before
+/// it is used
+fn read_raw_array(
+    array_start: &[u8],
+    header_type: Precision,
+    byte_order: ByteOrder,
+) -> Result<&[u8], ParseError> {
+    let too_long = || appended_error("an array is longer than the appended section");
+
+    let header_bytes = header_type.size_of();
+    let header = array_start.get(..header_bytes).ok_or_else(too_long)?;
+    let length = utils::bytes_to_uint(header, header_type, byte_order);
+
+    let array_end = usize::try_from(length)
+        .ok()
+        .and_then(|length| header_bytes.checked_add(length))
+        .filter(|array_end| *array_end <= array_start.len())
+        .ok_or_else(too_long)?;
+
+    Ok(&array_start[header_bytes..array_end])
+}
+
+fn appended_error(reason: &'static str) -> ParseError {
+    ParseError::from_nom(NomError::nom(&[], nom::error::ErrorKind::Verify), reason)
+}
+
 /// read through a DataArray header and consume up to the ending `>` character of the
 /// header.
 ///
@@ -229,114 +802,698 @@ pub fn read_appended_array_buffers(
 ///
 /// ### `expected_data`
 ///
-/// byte string of the expected `Name` attribute for this `DataArray`
+/// byte string of the expected `Name` attribute for this `DataArray`. An error is returned if
+/// the next `DataArray` has a different name, use [`read_section_arrays`] to find arrays by name
 ///
 /// ## Returns
 ///
 /// A tuple of the remaining data in the string (not parsed) and the floating point data that
 /// was contained in the DataArray
-/// Currently expects the attributes of the header to be in this format:
+/// The attributes of the header may be in any order, and `NumberOfComponents` defaults to `1`
+/// if it is missing (as vtk writes it):
+///
+/// ```ignore
+/// <DataArray type="type here" Name="name here" NumberOfComponents="components" format="format here" offset="offset, if appended format"> ...
+/// ```
+pub fn read_dataarray_header<'a>(
+    xml_bytes: &'a [u8],
+    expected_data: &[u8],
+) -> IResult<'a, DataArrayHeader> {
+    let (rest, description) = read_dataarray_element(xml_bytes, Some(expected_data))?;
+    Ok((rest, description.header))
+}
+
+/// read the header of the next `DataArray`, checking its `Name` if `expected_data` is given
+fn read_dataarray_element<'a>(
+    xml_bytes: &'a [u8],
+    expected_data: Option<&[u8]>,
+) -> IResult<'a, DataArrayDescription> {
+    let (rest, description) = describe_dataarray(xml_bytes)?;
+
+    if let Some(expected_data) = expected_data {
+        if description.name.as_bytes() != expected_data {
+            return Err(NomError::err(
+                xml_bytes,
+                NomErrorKind::UnexpectedName {
+                    expected: String::from_utf8_lossy(expected_data).into_owned(),
+                    found: description.name,
+                },
+            ));
+        }
+    }
+
+    Ok((rest, description))
+}
+
+/// Everything that is known about a `DataArray` from the attributes of its element
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataArrayDescription {
+    /// the `Name` of the array, which is empty if the element has no name
+    pub name: String,
+    /// how the values of the array are stored
+    pub header: DataArrayHeader,
+    /// the `RangeMin` and `RangeMax` that vtk writes for every array. For arrays with more than
+    /// one component this is the range of the magnitude of each tuple
+    pub range: Option<(f64, f64)>,
+    /// the `ComponentName0` to `ComponentNameN` of the array, if any component is named
+    pub component_names: Vec<Option<String>>,
+}
+
+/// read the next `DataArray` element, consuming up to the ending `>` character of the element.
+///
+/// Unlike [`read_dataarray_header`] this returns every attribute that is understood, regardless
+/// of the `Name` of the array
+pub fn describe_dataarray(xml_bytes: &[u8]) -> IResult<'_, DataArrayDescription> {
+    let (array_start, _) = take_until_consume(xml_bytes, b"<DataArray")?;
+    let (element_end, element) = take_till(|c| c == b'>')(array_start)?;
+    let (rest, _) = tag(">")(element_end)?;
+
+    let (_, attributes) = read_element_attributes(element)?;
+
+    // the type of each element in the array
+    let type_name = attributes.require(b"type")?;
+    let precision = Precision::from_bytes(type_name)
+        .ok_or_else(|| NomError::nom(type_name, nom::error::ErrorKind::Tag))?;
+
+    let name = attributes
+        .get(b"Name")
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .unwrap_or_default();
+
+    // vtk only writes the number of components of arrays with more than one component
+    let components = match attributes.get(b"NumberOfComponents") {
+        Some(num_components_str) => parse_number(num_components_str, &name)?,
+        None => 1,
+    };
+
+    let range = match (attributes.get(b"RangeMin"), attributes.get(b"RangeMax")) {
+        (Some(min), Some(max)) => Some((parse_number(min, &name)?, parse_number(max, &name)?)),
+        _ => None,
+    };
+
+    let component_names = (0..components)
+        .map(|component| {
+            let attribute = format!("ComponentName{}", component);
+            attributes
+                .get(attribute.as_bytes())
+                .map(|name| String::from_utf8_lossy(name).into_owned())
+        })
+        .collect::<Vec<_>>();
+
+    let component_names = if component_names.iter().any(Option::is_some) {
+        component_names
+    } else {
+        Vec::new()
+    };
+
+    let format_name = attributes.require(b"format")?;
+
+    let header = match format_name {
+        b"appended" => {
+            // we also need the offset header so we know when to start reading
+            let offset = parse_number(attributes.require(b"offset")?, &name)?;
+
+            DataArrayHeader::AppendedBinary {
+                offset,
+                components,
+                precision,
+            }
+        }
+        b"binary" => {
+            // we have base64 encoded data here
+            DataArrayHeader::InlineBase64 {
+                components,
+                precision,
+            }
+        }
+        b"ascii" => {
+            // plain ascii data here
+            DataArrayHeader::InlineAscii {
+                components,
+                precision,
+            }
+        }
+        // the format must be one of appended/binary/ascii
+        _ => return Err(NomError::nom(format_name, nom::error::ErrorKind::Tag)),
+    };
+
+    let description = DataArrayDescription {
+        name,
+        header,
+        range,
+        component_names,
+    };
+
+    Ok((rest, description))
+}
+
+/// The attributes of the `<VTKFile>` element that describe how the arrays in the appended
+/// section are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileAttributes {
+    /// the compressor of every binary (appended or inline base64) array, if the file is
+    /// compressed
+    pub compressor: Option<Compressor>,
+    /// the type of the integers in the headers of binary arrays (`UInt32` or `UInt64`)
+    pub header_type: Precision,
+    /// the byte order of every binary (base64 or appended) array and header
+    pub byte_order: ByteOrder,
+    /// the `type` of the dataset, if it is one that can be read by this crate
+    pub grid_type: Option<GridType>,
+}
+
+impl Default for FileAttributes {
+    fn default() -> Self {
+        // vtk uses 32 bit headers if no `header_type` is specified
+        Self {
+            compressor: None,
+            header_type: Precision::UInt32,
+            byte_order: ByteOrder::LittleEndian,
+            grid_type: None,
+        }
+    }
+}
+
+/// read the `compressor`, `header_type` and `byte_order` attributes of the `<VTKFile>` element.
+///
+/// Files without a `<VTKFile>` element use the default attributes. An error is returned if
+/// the compressor is unknown (or its cargo feature is not enabled).
+pub fn read_file_attributes(xml_bytes: &[u8]) -> IResult<'_, FileAttributes> {
+    let mut attributes = FileAttributes::default();
+
+    let element_start = match take_until_consume(xml_bytes, b"<VTKFile") {
+        Ok((element_start, _)) => element_start,
+        Err(_) => return Ok((xml_bytes, attributes)),
+    };
+    let (_, element) = take_till(|c| c == b'>')(element_start)?;
+    let (_, element_attributes) = read_element_attributes(element)?;
+
+    if let Some(compressor) = element_attributes.get(b"compressor") {
+        let compressor = Compressor::from_bytes(compressor)
+            .ok_or_else(|| NomError::nom(compressor, nom::error::ErrorKind::Tag))?;
+
+        attributes.compressor = Some(compressor);
+    }
+
+    if let Some(header_type) = element_attributes.get(b"header_type") {
+        attributes.header_type = match header_type {
+            b"UInt32" => Precision::UInt32,
+            b"UInt64" => Precision::UInt64,
+            _ => return Err(NomError::nom(header_type, nom::error::ErrorKind::Tag)),
+        };
+    }
+
+    if let Some(byte_order) = element_attributes.get(b"byte_order") {
+        attributes.byte_order = ByteOrder::from_bytes(byte_order)
+            .ok_or_else(|| NomError::nom(byte_order, nom::error::ErrorKind::Tag))?;
+    }
+
+    attributes.grid_type = element_attributes
+        .get(b"type")
+        .and_then(GridType::from_bytes);
+
+    Ok((xml_bytes, attributes))
+}
+
+pub(crate) fn take_until_consume<'a>(input: &'a [u8], until_str: &[u8]) -> IResult<'a, ()> {
+    let (non_consumed, _other) = take_until(until_str)(input)?;
+    let (consumed, _format_header) = tag(until_str)(non_consumed)?;
+    Ok((consumed, ()))
+}
+
+/// reads the data inside of two `"` (or two `'`) characters, consuming the quotes in the process
+fn read_inside_quotes(i: &[u8]) -> IResult<'_, &[u8]> {
+    let quote = match i.first() {
+        Some(b'\'') => "'",
+        _ => "\"",
+    };
+
+    let (after_quote, _quote_char) = tag(quote)(i)?;
+    let (after_inner, inner_data) = take_till(|c| c == quote.as_bytes()[0])(after_quote)?;
+    let (after_quote, _quote_char) = tag(quote)(after_inner)?;
+    Ok((after_quote, inner_data))
+}
+
+/// The attributes of a single element, by name
+#[derive(Debug, Clone)]
+pub(crate) struct ElementAttributes<'a> {
+    element: &'a [u8],
+    attributes: HashMap<&'a [u8], &'a [u8]>,
+}
+
+impl<'a> ElementAttributes<'a> {
+    /// the value of the attribute `name`, if the element has one
+    pub(crate) fn get(&self, name: &[u8]) -> Option<&'a [u8]> {
+        self.attributes.get(name).copied()
+    }
+
+    /// the value of the attribute `name`, or an error if the element does not have it
+    pub(crate) fn require(&self, name: &[u8]) -> Result<&'a [u8], NomErr<'a>> {
+        self.get(name).ok_or_else(|| {
+            let array = self.get(b"Name").unwrap_or_default();
+
+            NomError::err(
+                self.element,
+                NomErrorKind::MissingAttribute {
+                    attribute: String::from_utf8_lossy(name).into_owned(),
+                    array: String::from_utf8_lossy(array).into_owned(),
+                },
+            )
+        })
+    }
+}
+
+/// read every `name="value"` pair of an element into a map.
+///
+/// `element` should only contain the attributes of one element (up to, but not including, the
+/// closing `>`). The attributes may be in any order and be separated by any whitespace
+pub(crate) fn read_element_attributes(element: &[u8]) -> IResult<'_, ElementAttributes<'_>> {
+    use nom::character::complete::multispace0;
+
+    let mut attributes = HashMap::new();
+    let mut rest = element;
+
+    loop {
+        let (name_start, _) = multispace0(rest)?;
+
+        // the end of the element, which may be self closing
+        if name_start.is_empty() || name_start == b"/" {
+            break;
+        }
+
+        let (name_end, name) =
+            nom::bytes::complete::take_till1(|c: u8| c == b'=' || c.is_ascii_whitespace())(
+                name_start,
+            )?;
+        let (equals, _) = multispace0(name_end)?;
+        let (value_start, _) = tag("=")(equals)?;
+        let (value_start, _) = multispace0(value_start)?;
+        let (value_end, value) = read_inside_quotes(value_start)?;
+
+        attributes.insert(name, value);
+        rest = value_end;
+    }
+
+    Ok((
+        &[],
+        ElementAttributes {
+            element,
+            attributes,
+        },
+    ))
+}
+
+/// find the value of the attribute `name` within the attributes of a single element.
+///
+/// `element` should only contain the attributes of one element (up to, but not including, the
+/// closing `>`) so that attributes of the following elements are not matched
+pub(crate) fn find_attribute<'a>(element: &'a [u8], name: &[u8]) -> IResult<'a, &'a [u8]> {
+    let (_, attributes) = read_element_attributes(element)?;
+    let value = attributes.require(name)?;
+    Ok((&[], value))
+}
+
+/// skip past the opening tag of the first `section` element (such as `PointData` or
+/// `Coordinates`) of a piece.
+///
+/// vtk writes the sections of a piece in a different order than this crate (the `<PointData>`
+/// and `<CellData>` come before the arrays of the mesh), so every visitor should find the
+/// sections it reads from the start of the piece
+pub fn skip_to_section<'a>(xml_bytes: &'a [u8], section: &[u8]) -> IResult<'a, ()> {
+    let mut element = b"<".to_vec();
+    element.extend_from_slice(section);
+
+    let (element_start, _) = take_until_consume(xml_bytes, &element)?;
+    let (element_end, _) = take_till(|c| c == b'>')(element_start)?;
+    let (rest, _) = tag(">")(element_end)?;
+
+    Ok((rest, ()))
+}
+
+/// The `<DataArray>` elements of a single section (such as `<PointData>`), indexed by their
+/// `Name` attribute.
+///
+/// The arrays are only parsed once they are taken, so arrays that are not needed (or not known
+/// by the reader) are skipped, and the arrays may be taken in any order.
+#[derive(Debug)]
+pub struct SectionArrays<'a> {
+    section: &'a [u8],
+    arrays: HashMap<&'a [u8], &'a [u8]>,
+}
+
+impl<'a> SectionArrays<'a> {
+    /// parse the array named `name`, or return an error if there is no such array in the
+    /// section. The `size_hint` is the expected number of values in the array
+    pub fn take(&mut self, name: &[u8], size_hint: usize) -> Result<PartialDataArray, NomErr<'a>> {
+        let array_start = self.arrays.remove(name).ok_or_else(|| {
+            NomError::err(
+                self.section,
+                NomErrorKind::MissingArray {
+                    array: String::from_utf8_lossy(name).into_owned(),
+                },
+            )
+        })?;
+
+        let (_, array) = parse_next_dataarray_or_lazy(array_start, size_hint)?;
+        Ok(array)
+    }
+
+    /// read the attributes of the array named `name` without taking it
+    pub fn describe(&self, name: &[u8]) -> Result<DataArrayDescription, NomErr<'a>> {
+        let array_start = self.arrays.get(name).ok_or_else(|| {
+            NomError::err(
+                self.section,
+                NomErrorKind::MissingArray {
+                    array: String::from_utf8_lossy(name).into_owned(),
+                },
+            )
+        })?;
+
+        let (_, description) = describe_dataarray(array_start)?;
+        Ok(description)
+    }
+
+    /// read the attributes of the array named `name` into a [`LazyArray`](crate::LazyArray),
+    /// without taking it. The array is located by its offset from `file`, which starts at the
+    /// beginning of the file but may end before the section (such as the elements before the
+    /// first piece).
+    pub fn lazy(&self, name: &[u8], file: &[u8]) -> Result<LazyArray, NomErr<'a>> {
+        let description = self.describe(name)?;
+
+        // `describe` found the array, so it is in the section
+        let array_start = self.arrays[name];
+        let header_offset = (array_start.as_ptr() as usize)
+            .checked_sub(file.as_ptr() as usize)
+            .ok_or_else(|| NomError::nom(array_start, nom::error::ErrorKind::Eof))?;
+
+        Ok(LazyArray::new(description, header_offset))
+    }
+
+    /// the names of the arrays that have not been taken, in the order they appear in the file
+    pub fn names(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        let mut arrays: Vec<_> = self.arrays.iter().collect();
+        arrays.sort_by_key(|(_, array_start)| array_start.as_ptr());
+        arrays.into_iter().map(|(name, _)| *name)
+    }
+}
+
+/// find the first `section` element of a piece (see [`skip_to_section`]) and index its arrays
+/// by name.
+///
+/// The remaining bytes start after the end of the section. Arrays without a `Name` attribute
+/// can not be taken, and are ignored.
+pub fn read_section_arrays<'a>(
+    xml_bytes: &'a [u8],
+    section: &[u8],
+) -> IResult<'a, SectionArrays<'a>> {
+    let (rest, contents) = find_section(xml_bytes, section)?;
+
+    let mut arrays = HashMap::new();
+    let mut remaining = contents;
+
+    while let Ok((array_start, _)) = take_until::<_, _, NomError>("<DataArray")(remaining) {
+        let (after_tag, _) = tag("<DataArray")(array_start)?;
+        let (_, element) = take_till(|c| c == b'>')(after_tag)?;
+
+        if let Ok((_, name)) = find_attribute(element, b"Name") {
+            arrays.entry(name).or_insert(array_start);
+        }
+
+        remaining = after_tag;
+    }
+
+    Ok((
+        rest,
+        SectionArrays {
+            section: contents,
+            arrays,
+        },
+    ))
+}
+
+/// find the first `section` element and return everything between its opening and closing tags.
+///
+/// The remaining bytes start after the end of the section
+pub(crate) fn find_section<'a>(xml_bytes: &'a [u8], section: &[u8]) -> IResult<'a, &'a [u8]> {
+    let mut opening_tag = b"<".to_vec();
+    opening_tag.extend_from_slice(section);
+
+    let (element_start, _) = take_until_consume(xml_bytes, &opening_tag)?;
+    let (element_end, element) = take_till(|c| c == b'>')(element_start)?;
+    let (section_start, _) = tag(">")(element_end)?;
+
+    // a self closing section has no contents
+    if element.ends_with(b"/") {
+        return Ok((section_start, &[]));
+    }
+
+    let mut closing_tag = b"</".to_vec();
+    closing_tag.extend_from_slice(section);
+    let (rest, contents) = take_until(closing_tag.as_slice())(section_start)?;
+
+    Ok((rest, contents))
+}
+
+/// parse a whitespace separated list of floats from an attribute value such as
+/// `Origin="0 0 0"`
+pub(crate) fn parse_float_list(value: &[u8]) -> IResult<'_, Vec<f64>> {
+    let mut floats = Vec::new();
+    parse_ascii_values(value, &mut floats, "")?;
+
+    Ok((&[], floats))
+}
+
+/// read integer attributes (such as `NumberOfPoints`) from the first `<Piece>` element,
+/// in the order of `names`
+///
+/// The `<Piece>` element is consumed up to and including its closing `>`.
+pub(crate) fn read_piece_sizes<'a>(
+    xml_bytes: &'a [u8],
+    names: &[&[u8]],
+) -> IResult<'a, Vec<usize>> {
+    let (piece_start, _) = take_until_consume(xml_bytes, b"<Piece")?;
+    let (element_end, element) = take_till(|c| c == b'>')(piece_start)?;
+    let (rest, _) = tag(">")(element_end)?;
+
+    let (_, attributes) = read_element_attributes(element)?;
+    let mut sizes = Vec::with_capacity(names.len());
+
+    for name in names {
+        let value = attributes.require(name)?;
+
+        let size = std::str::from_utf8(value)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| NomError::nom(value, nom::error::ErrorKind::Digit))?;
+
+        sizes.push(size);
+    }
+
+    Ok((rest, sizes))
+}
+
+/// read the `Origin` and `Spacing` attributes of the first `<ImageData>` element of `xml_bytes`,
+/// keeping the first `dimensions` values of each.
 ///
-/// ```ignore
-/// <DataArray name="name here" format="format here" offset="offset, if appended format"> ...
-/// ```
+/// Every attribute of the element is read, so they may be in any order. The element is consumed
+/// up to and including its closing `>`.
+pub(crate) fn read_origin_and_spacing(
+    xml_bytes: &[u8],
+    dimensions: usize,
+) -> IResult<'_, (Vec<f64>, Vec<f64>)> {
+    let (element_start, _) = take_until_consume(xml_bytes, b"<ImageData")?;
+    let (element_end, element) = take_till(|c| c == b'>')(element_start)?;
+    let (rest, _) = tag(">")(element_end)?;
+
+    let (_, attributes) = read_element_attributes(element)?;
+
+    let (_, mut origin) = parse_float_list(attributes.require(b"Origin")?)?;
+    let spacing = attributes.require(b"Spacing")?;
+    let (_, mut spacing) = parse_float_list(spacing)?;
+
+    if origin.len() < dimensions || spacing.len() < dimensions {
+        return Err(NomError::nom(element, nom::error::ErrorKind::Count));
+    }
+
+    origin.truncate(dimensions);
+    spacing.truncate(dimensions);
+
+    Ok((rest, (origin, spacing)))
+}
+
+/// read all of the arrays of the `<FieldData>` element that precedes the first `<Piece>`.
 ///
-/// also assumes NumberOfComponents=1 and type=Float64
-pub fn read_dataarray_header<'a>(
-    xml_bytes: &'a [u8],
-    expected_data: &[u8],
-) -> IResult<&'a [u8], DataArrayHeader> {
-    // grab the number of components as well
-    let (components_start, _) = take_until_consume(xml_bytes, b"NumberOfComponents=")?;
-    let (after_components, num_components_str) = read_inside_quotes(components_start)?;
-
-    let components = String::from_utf8(num_components_str.to_vec())
-        .unwrap()
-        .parse()
-        .unwrap();
+/// Only inline (`ascii` or `binary`) arrays are read, arrays stored in the appended section are
+/// skipped. If the file has no `<FieldData>` element an empty container is returned.
+pub(crate) fn read_field_data(
+    xml_bytes: &[u8],
+    attributes: FileAttributes,
+) -> IResult<'_, FieldData> {
+    let mut field_data = FieldData::new();
+
+    // only search the dataset header so that the element is not confused with anything in the
+    // pieces or the appended data
+    let header = match take_until::<_, _, NomError>("<Piece")(xml_bytes) {
+        Ok((_, header)) => header,
+        Err(_) => xml_bytes,
+    };
 
-    let (name_start, _) = take_until_consume(after_components, b"Name=")?;
-    let (after_quotes, name) = read_inside_quotes(name_start)?;
+    let section = match take_until_consume(header, b"<FieldData") {
+        Ok((section, _)) => section,
+        Err(_) => return Ok((xml_bytes, field_data)),
+    };
+    let (_, mut section) = take_until("</FieldData>")(section)?;
 
-    assert_eq!(name, expected_data);
+    while let Ok((array_start, _)) = take_until_consume(section, b"<DataArray") {
+        let (element_end, element) = take_till(|c| c == b'>')(array_start)?;
+        let (contents_start, _) = tag(">")(element_end)?;
 
-    let (format_start, _) = take_until_consume(after_quotes, b"format=")?;
-    let (mut after_format, format_name) = read_inside_quotes(format_start)?;
+        let (_, array_attributes) = read_element_attributes(element)?;
+        let type_name = array_attributes.require(b"type")?;
+        let name = array_attributes.require(b"Name")?;
+        let format = array_attributes.require(b"format")?;
+        let array_name = String::from_utf8_lossy(name).into_owned();
 
-    let header = match format_name {
-        b"appended" => {
-            // we also need the offset header so we know when to start reading
-            let (offset_start, _) = take_until_consume(after_format, b"offset=")?;
-            let (rest, offset) = read_inside_quotes(offset_start)?;
-            after_format = rest;
-
-            // TODO: better error handling for this
-            let offset_str = std::str::from_utf8(offset).unwrap();
-            let offset = offset_str.parse().expect(&format!(
-                "data array offset `{}` coult not be parsed as integer",
-                offset_str
-            ));
-            DataArrayHeader::AppendedBinary { offset, components }
-        }
-        b"binary" => {
-            // we have base64 encoded data here
-            DataArrayHeader::InlineBase64 { components }
-        }
-        b"ascii" => {
-            // plain ascii data here
-            DataArrayHeader::InlineAscii { components }
-        }
-        _ => {
-            // TODO: find a better way to make errors here
-            let (_, _): (&[u8], &[u8]) = tag(
-                "missing formatting header as appended/binary/ascii".as_bytes()
-            )("".as_bytes())?;
-            unreachable!()
+        // self closing arrays have no contents
+        let (rest, contents) = if element.ends_with(b"/") {
+            (contents_start, [].as_ref())
+        } else {
+            let (end_tag, contents) = take_until("</DataArray")(contents_start)?;
+            let (rest, _) = take_until_consume(end_tag, b">")?;
+            (rest, contents)
+        };
+        section = rest;
+
+        let is_string = type_name == b"String";
+
+        // strings are stored as the bytes of their characters
+        let precision = if is_string {
+            Precision::UInt8
+        } else {
+            Precision::from_bytes(type_name)
+                .ok_or_else(|| NomError::nom(type_name, nom::error::ErrorKind::Tag))?
+        };
+
+        let mut values = Buffer::with_capacity(precision, 0);
+
+        match format {
+            b"ascii" => parse_ascii_buffer(contents, &mut values, &array_name)?,
+            b"binary" if contents.is_empty() => (),
+            b"binary" => {
+                let (_, encoded) = read_base64_inner_dataarray(contents_start, &array_name)?;
+                let bytes = decode_base64_array(&encoded, attributes).map_err(|_| {
+                    NomError::err(
+                        contents,
+                        NomErrorKind::InvalidBase64 {
+                            array: array_name.clone(),
+                        },
+                    )
+                })?;
+
+                values.extend_from_bytes(&bytes, precision, attributes.byte_order);
+            }
+            _ => continue,
         }
-    };
 
-    let (rest, _) = take_until_consume(after_format, b">")?;
+        let unrepresentable = |error: ParseError| match error {
+            ParseError::UnrepresentableValue {
+                value, precision, ..
+            } => {
+                let array = array_name.clone();
+                let kind = NomErrorKind::UnrepresentableValue {
+                    array,
+                    value,
+                    precision,
+                };
+                NomError::err(contents, kind)
+            }
+            _ => NomError::nom(contents, nom::error::ErrorKind::Verify),
+        };
 
-    Ok((rest, header))
-}
+        let value = if is_string {
+            let codes = values.into_vec::<u8>().map_err(unrepresentable)?;
+            FieldValue::from_character_codes(&codes)
+        } else if precision.is_float() {
+            FieldValue::Float(values.into_vec().map_err(unrepresentable)?)
+        } else {
+            FieldValue::Int(values.into_vec().map_err(unrepresentable)?)
+        };
 
-fn take_until_consume<'a>(input: &'a [u8], until_str: &[u8]) -> IResult<&'a [u8], ()> {
-    let (non_consumed, _other) = take_until(until_str)(input)?;
-    let (consumed, _format_header) = tag(until_str)(non_consumed)?;
-    Ok((consumed, ()))
-}
+        field_data.insert(&array_name, value);
+    }
 
-/// reads the data inside of two `"` characters, consuming the quotes in the process
-fn read_inside_quotes<'a>(i: &'a [u8]) -> IResult<&'a [u8], &'a [u8]> {
-    let (after_quote, _quote_char) = tag("\"")(i)?;
-    let (after_inner, inner_data) = take_till(|c| c == b'"')(after_quote)?;
-    let (after_quote, _quote_char) = tag("\"")(after_inner)?;
-    Ok((after_quote, inner_data))
+    Ok((xml_bytes, field_data))
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 /// Describes what kind of information is in a header
 pub enum DataArrayHeader {
     /// Ascii information is contained directly within the `DataArray` elements
-    InlineAscii { components: usize },
+    InlineAscii {
+        components: usize,
+        precision: Precision,
+    },
     /// Base64 information is contained directly within the `DataArray` elements
-    InlineBase64 { components: usize },
+    InlineBase64 {
+        components: usize,
+        precision: Precision,
+    },
     /// Information is not stored inline, it is stored at a specified `offset`
     /// in the `AppendedData` section
-    AppendedBinary { offset: i64, components: usize },
+    AppendedBinary {
+        offset: i64,
+        components: usize,
+        precision: Precision,
+    },
+}
+
+impl DataArrayHeader {
+    /// the type of each element of the array
+    pub fn precision(&self) -> Precision {
+        match self {
+            Self::InlineAscii { precision, .. } => *precision,
+            Self::InlineBase64 { precision, .. } => *precision,
+            Self::AppendedBinary { precision, .. } => *precision,
+        }
+    }
+
+    /// the number of components of each tuple of the array
+    pub fn components(&self) -> usize {
+        match self {
+            Self::InlineAscii { components, .. } => *components,
+            Self::InlineBase64 { components, .. } => *components,
+            Self::AppendedBinary { components, .. } => *components,
+        }
+    }
 }
 
 #[derive(Debug)]
 /// Describes if the data for this array has already been parsed (regardless of format), or its offset
 /// in the `AppendedData` section
 pub enum PartialDataArray {
-    Parsed { buffer: Vec<f64>, components: usize },
-    AppendedBinary { offset: i64, components: usize },
+    Parsed {
+        buffer: Buffer,
+        components: usize,
+    },
+    AppendedBinary {
+        name: String,
+        offset: i64,
+        components: usize,
+        precision: Precision,
+    },
+    /// inline base64 text (without whitespace) that is decoded along with the appended arrays,
+    /// once the `byte_order` of the file is known
+    InlineBase64 {
+        name: String,
+        encoded: Vec<u8>,
+        components: usize,
+        precision: Precision,
+    },
 }
 
 impl PartialDataArray {
     /// unwrap the data as `PartailDataArray::Parsed` or panic
-    pub fn unwrap_parsed(self) -> Vec<f64> {
+    pub fn unwrap_parsed(self) -> Buffer {
         match self {
             Self::Parsed { buffer, .. } => buffer,
             _ => panic!("called unwrap_parsed on a PartialDataArray::AppendedBinary"),
@@ -347,7 +1504,7 @@ impl PartialDataArray {
     pub fn unwrap_appended(self) -> i64 {
         match self {
             Self::AppendedBinary { offset, .. } => offset,
-            _ => panic!("called unwrap_parsed on a PartialDataArray::AppendedBinary"),
+            _ => panic!("called unwrap_parsed on an array that has not been decoded"),
         }
     }
 
@@ -356,6 +1513,7 @@ impl PartialDataArray {
         match self {
             Self::AppendedBinary { components, .. } => *components,
             Self::Parsed { components, .. } => *components,
+            Self::InlineBase64 { components, .. } => *components,
         }
     }
 }
@@ -365,30 +1523,49 @@ impl PartialDataArray {
 ///
 /// Useful for implementing `traits::ParseDataArray`
 pub enum PartialDataArrayBuffered {
-    Parsed { buffer: Vec<f64>, components: usize },
+    Parsed { buffer: Buffer, components: usize },
     AppendedBinary(RefCell<OffsetBuffer>),
 }
 
-impl<'a> PartialDataArrayBuffered {
+impl PartialDataArrayBuffered {
     /// Construct a buffer associated with appended binary
     pub fn new(partial: PartialDataArray, size_hint: usize) -> Self {
         match partial {
             PartialDataArray::Parsed { buffer, components } => {
                 PartialDataArrayBuffered::Parsed { buffer, components }
             }
-            PartialDataArray::AppendedBinary { offset, components } => {
-                PartialDataArrayBuffered::AppendedBinary(RefCell::new(OffsetBuffer {
-                    offset,
-                    buffer: Vec::with_capacity(size_hint),
-                    components,
-                }))
-            }
+            PartialDataArray::AppendedBinary {
+                name,
+                offset,
+                components,
+                precision,
+            } => PartialDataArrayBuffered::AppendedBinary(RefCell::new(OffsetBuffer {
+                name,
+                offset,
+                buffer: Buffer::with_capacity(precision, size_hint),
+                components,
+                precision,
+                inline_base64: None,
+            })),
+            PartialDataArray::InlineBase64 {
+                name,
+                encoded,
+                components,
+                precision,
+            } => PartialDataArrayBuffered::AppendedBinary(RefCell::new(OffsetBuffer {
+                name,
+                offset: 0,
+                buffer: Buffer::with_capacity(precision, size_hint),
+                components,
+                precision,
+                inline_base64: Some(encoded),
+            })),
         }
     }
 
     /// Pull the data buffer from from each
     /// of the variants
-    pub fn into_buffer(self) -> Vec<f64> {
+    pub fn into_buffer(self) -> Buffer {
         match self {
             Self::Parsed { buffer, .. } => buffer,
             Self::AppendedBinary(offset_buffer) => offset_buffer.into_inner().buffer,
@@ -405,14 +1582,11 @@ impl<'a> PartialDataArrayBuffered {
 
     /// helper function to put the array in a vector so that we can read all the binary data in
     /// order
-    pub fn append_to_reader_list<'c, 'b>(&'c self, buffer: &'b mut Vec<RefMut<'c, OffsetBuffer>>) {
-        match self {
-            PartialDataArrayBuffered::AppendedBinary(offset_buffer) => {
-                buffer.push(offset_buffer.borrow_mut())
-            }
-            // if this is here then we have already read the data inline, and we dont need to worry
-            // about any appended data for this item
-            _ => (),
+    pub fn append_to_reader_list<'c>(&'c self, buffer: &mut Vec<RefMut<'c, OffsetBuffer>>) {
+        // if this is not appended then we have already read the data inline, and we dont need to worry
+        // about any appended data for this item
+        if let PartialDataArrayBuffered::AppendedBinary(offset_buffer) = self {
+            buffer.push(offset_buffer.borrow_mut())
         }
     }
 }
@@ -421,138 +1595,276 @@ impl<'a> PartialDataArrayBuffered {
 /// Helper struct describing the offset that the data should be read at
 /// and the buffer that will be used to read in the information
 pub struct OffsetBuffer {
+    /// the `Name` of the array, used to describe errors
+    pub name: String,
     pub offset: i64,
-    pub buffer: Vec<f64>,
+    pub buffer: Buffer,
     pub components: usize,
+    /// the type of each element in the appended section
+    pub precision: Precision,
+    /// the base64 text of an inline array, which is decoded instead of reading the appended
+    /// section at `offset`
+    pub inline_base64: Option<Vec<u8>>,
 }
 
 impl Eq for OffsetBuffer {}
 
-/// parse the values for a single inline ascii encoded array
+/// parse the values for a single inline ascii encoded array named `name`
 ///
 /// ensure that before calling this function you have verified
-/// that the data is base64 encoded with a call to `read_dataarray_header`
+/// that the data is ascii encoded with a call to `read_dataarray_header`
 fn parse_ascii_inner_dataarray<'a>(
     xml_bytes: &'a [u8],
     size_hint: usize,
-) -> IResult<&'a [u8], Vec<f64>> {
-    let (location_data_and_rest, _whitespace) =
-        take_till(|c: u8| c.is_ascii_digit() || c == b'.' || c == b'-')(xml_bytes)?;
-    let (rest_of_document, location_data) = take_till(|c| c == b'<')(location_data_and_rest)?;
-
-    let location_data_string =
-        std::str::from_utf8(location_data).expect("ascii data was not encoded as UTF-8");
-
-    let mut out = Vec::with_capacity(size_hint);
-
-    location_data_string
-        .trim_end()
-        .split_ascii_whitespace()
-        .for_each(|x| {
-            let num = x
-                .parse()
-                .expect(&format!("ascii number {} could not be parsed as such", x));
-            out.push(num);
-        });
+    precision: Precision,
+    name: &str,
+) -> IResult<'a, Buffer> {
+    let (rest_of_document, location_data) = take_till(|c| c == b'<')(xml_bytes)?;
+
+    let mut out = Buffer::with_capacity(precision, size_hint);
+    parse_ascii_buffer(location_data, &mut out, name)?;
 
     Ok((rest_of_document, out))
 }
 
-/// parse the values for a single inline base64 encoded array
+/// parse whitespace separated numbers into `buffer` as the kind of number that it holds, so
+/// that integers are not rounded by parsing them as floats
+fn parse_ascii_buffer<'a>(
+    values: &'a [u8],
+    buffer: &mut Buffer,
+    array: &str,
+) -> Result<(), NomErr<'a>> {
+    match buffer {
+        Buffer::Float(out) => parse_ascii_values(values, out, array),
+        Buffer::Int(out) => parse_ascii_values(values, out, array),
+        Buffer::UInt(out) => parse_ascii_values(values, out, array),
+    }
+}
+
+/// parse whitespace separated numbers into `out`, the values of the array named `array`
+fn parse_ascii_values<'a, T: std::str::FromStr>(
+    values: &'a [u8],
+    out: &mut Vec<T>,
+    array: &str,
+) -> Result<(), NomErr<'a>> {
+    for value in values
+        .split(|c| c.is_ascii_whitespace())
+        .filter(|value| !value.is_empty())
+    {
+        out.push(parse_number(value, array)?);
+    }
+
+    Ok(())
+}
+
+/// parse a single number, such as the value of an attribute of the array named `array`
+pub(crate) fn parse_number<'a, T: std::str::FromStr>(
+    value: &'a [u8],
+    array: &str,
+) -> Result<T, NomErr<'a>> {
+    std::str::from_utf8(value)
+        .ok()
+        .and_then(|value_str| value_str.parse().ok())
+        .ok_or_else(|| {
+            NomError::err(
+                value,
+                NomErrorKind::InvalidNumber {
+                    value: String::from_utf8_lossy(value).into_owned(),
+                    array: array.to_string(),
+                },
+            )
+        })
+}
+
+/// read the base64 text of the inline array named `name`, without any whitespace
 ///
 /// ensure that before calling this function you have verified
 /// that the data is base64 encoded with a call to `read_dataarray_header`
-fn parse_base64_inner_dataarray<'a>(
-    xml_bytes: &'a [u8],
-    size_hint: usize,
-) -> IResult<&'a [u8], Vec<f64>> {
+fn read_base64_inner_dataarray<'a>(xml_bytes: &'a [u8], name: &str) -> IResult<'a, Vec<u8>> {
     let (rest_of_document, base64_encoded_bytes) = take_until("</D")(xml_bytes)?;
-    let mut out = Vec::with_capacity(size_hint);
 
-    let numerical_bytes =
-        base64::decode(&base64_encoded_bytes).expect("could not decode base64 data array bytes");
+    let encoded: Vec<u8> = base64_encoded_bytes
+        .iter()
+        .copied()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+
+    // the values are only decoded once the byte order of the file is known, but invalid
+    // characters are found here where their position in the file is known
+    let is_base64 = |c: &u8| c.is_ascii_alphanumeric() || matches!(c, b'+' | b'/' | b'=');
+
+    if let Some(invalid) = base64_encoded_bytes
+        .iter()
+        .position(|c| !c.is_ascii_whitespace() && !is_base64(c))
+    {
+        return Err(NomError::err(
+            &base64_encoded_bytes[invalid..],
+            NomErrorKind::InvalidBase64 {
+                array: name.to_string(),
+            },
+        ));
+    }
 
-    // normally we start with idx = 0, but since paraview expects the first 8 bytes
-    // to be garbage information we need to skip the first 8 bytes before actually
-    // reading the data
-    let mut idx = 8;
-    let inc = 8;
+    Ok((rest_of_document, encoded))
+}
 
-    loop {
-        if let Some(byte_slice) = numerical_bytes.get(idx..idx + inc) {
-            if byte_slice.len() != 8 {
-                break;
-            }
+/// decode the bytes of a base64 array: an inline `binary` array, or an array in an appended
+/// section with `encoding="base64"`. `encoded` may contain more data after the array.
+///
+/// vtk encodes the header of an array and its values as two separate base64 blocks, while other
+/// writers (such as meshio) encode them together as a single stream, so both are tried. The
+/// length of the array is found from the `header_type` and the sizes in the header. Arrays of
+/// compressed files are decompressed after they are decoded.
+fn decode_base64_array(encoded: &[u8], attributes: FileAttributes) -> Result<Vec<u8>, ParseError> {
+    let width = attributes.header_type.size_of();
+    let header_value = |header: &[u8], idx: usize| {
+        let value = utils::bytes_to_uint(
+            &header[idx * width..(idx + 1) * width],
+            attributes.header_type,
+            attributes.byte_order,
+        );
+        usize::try_from(value).map_err(|_| invalid_base64())
+    };
 
-            let mut const_slice = [0; 8];
-            // copy in the slice to a fixed size array
-            // could use unsafe here if we really wanted to
-            byte_slice
-                .iter()
-                .enumerate()
-                .for_each(|(slice_index, value)| const_slice[slice_index] = *value);
+    let compressor = match attributes.compressor {
+        Some(compressor) => compressor,
+        None => {
+            let value_bytes = |header: &[u8]| header_value(header, 0);
+            let mut bytes = decode_separate_blocks(encoded, width, value_bytes)
+                .or_else(|e| decode_single_stream(encoded, width, value_bytes).map_err(|_| e))?;
 
-            let float = f64::from_le_bytes(const_slice);
-            out.push(float);
-        } else {
-            break;
+            bytes.drain(..width);
+            return Ok(bytes);
         }
+    };
 
-        idx += inc;
-    }
+    // the number of blocks is the first of three values that start every header, which are a
+    // multiple of 3 bytes and so are encoded the same way in a separate block or a single stream
+    let num_blocks = header_value(&decode_base64_prefix(encoded, 3 * width)?, 0)?;
+    let header_bytes = num_blocks
+        .checked_add(3)
+        .and_then(|values| values.checked_mul(width))
+        .ok_or_else(invalid_base64)?;
+
+    let compressed_bytes = |header: &[u8]| {
+        (3..num_blocks + 3).try_fold(0usize, |total, idx| {
+            total
+                .checked_add(header_value(header, idx)?)
+                .ok_or_else(invalid_base64)
+        })
+    };
+    let bytes = decode_separate_blocks(encoded, header_bytes, compressed_bytes).or_else(|e| {
+        decode_single_stream(encoded, header_bytes, compressed_bytes).map_err(|_| e)
+    })?;
 
-    Ok((rest_of_document, out))
+    compression::decompress(
+        &bytes,
+        compressor,
+        attributes.header_type,
+        attributes.byte_order,
+    )
 }
 
-/// skip to the appended data section so that we can read in the binary
-///
-/// Call this function after reading all of the information from the inline data arrays
-pub fn setup_appended_read<'a>(xml_bytes: &[u8]) -> IResult<&[u8], ()> {
-    // TODO: make this function return the type of encoding used in the appended section
-    let (appended_data_section, _) = take_until_consume(xml_bytes, b"AppendedData")?;
-    let (appended_start, _encoding_information) = take_until_consume(appended_data_section, b">_")?;
-    let (appended_start_no_garbage, _) = take(8usize)(appended_start)?;
-    Ok((appended_start_no_garbage, ()))
+/// decode a header of `header_bytes` bytes and the values after it, when they are encoded as two
+/// separate base64 blocks. `value_bytes` finds the number of bytes of the values from the header.
+fn decode_separate_blocks(
+    encoded: &[u8],
+    header_bytes: usize,
+    value_bytes: impl Fn(&[u8]) -> Result<usize, ParseError>,
+) -> Result<Vec<u8>, ParseError> {
+    let mut bytes = decode_base64_block(encoded, header_bytes)?;
+    let values = value_bytes(&bytes)?;
+
+    // the header is decoded first, so `encoded` is at least as long as `data_start`
+    let data_start = base64_length(header_bytes).ok_or_else(invalid_base64)?;
+    bytes.extend(decode_base64_block(&encoded[data_start..], values)?);
+
+    Ok(bytes)
 }
 
-pub enum AppendedArrayLength {
-    Known(usize),
-    UntilEnd,
+/// decode a header of `header_bytes` bytes and the values after it, when they are encoded
+/// together as a single base64 stream. `value_bytes` finds the number of bytes of the values from
+/// the header.
+fn decode_single_stream(
+    encoded: &[u8],
+    header_bytes: usize,
+    value_bytes: impl Fn(&[u8]) -> Result<usize, ParseError>,
+) -> Result<Vec<u8>, ParseError> {
+    let values = value_bytes(&decode_base64_prefix(encoded, header_bytes)?)?;
+    let bytes = header_bytes
+        .checked_add(values)
+        .ok_or_else(invalid_base64)?;
+
+    decode_base64_block(encoded, bytes)
 }
 
-/// read information from the appended data binary buffer
-pub fn parse_appended_binary<'a>(
-    xml_bytes: &'a [u8],
-    length: AppendedArrayLength,
-    parsed_bytes: &mut Vec<f64>,
-) -> IResult<&'a [u8], ()> {
-    let (rest, bytes) = match length {
-        AppendedArrayLength::Known(known_length) => {
-            let (rest_of_appended, current_bytes_slice) = take(known_length)(xml_bytes)?;
-            (rest_of_appended, current_bytes_slice)
-        }
-        AppendedArrayLength::UntilEnd => {
-            let (rest_of_appended, current_bytes_slice) =
-                take_until(b"</Appended".as_ref())(xml_bytes)?;
-            (rest_of_appended, current_bytes_slice)
-        }
-    };
+/// decode the block of base64 text at the start of `encoded` that holds `bytes` bytes. The length
+/// of the block is checked against `encoded` before it is decoded.
+fn decode_base64_block(encoded: &[u8], bytes: usize) -> Result<Vec<u8>, ParseError> {
+    let block = base64_length(bytes)
+        .and_then(|length| encoded.get(..length))
+        .ok_or_else(invalid_base64)?;
+
+    base64::decode(block)
+        .ok()
+        .filter(|decoded| decoded.len() == bytes)
+        .ok_or_else(invalid_base64)
+}
 
-    let mut idx = 0;
-    let inc = 8;
+/// decode the first `bytes` bytes of the base64 text at the start of `encoded`, which may
+/// continue with more bytes of the same stream
+fn decode_base64_prefix(encoded: &[u8], bytes: usize) -> Result<Vec<u8>, ParseError> {
+    let block = base64_length(bytes)
+        .and_then(|length| encoded.get(..length))
+        .ok_or_else(invalid_base64)?;
 
-    loop {
-        if let Some(byte_slice) = bytes.get(idx..idx + inc) {
-            let float = utils::bytes_to_float(byte_slice);
-            parsed_bytes.push(float);
-        } else {
-            break;
-        }
+    let mut decoded = base64::decode(block)
+        .ok()
+        .filter(|decoded| decoded.len() >= bytes)
+        .ok_or_else(invalid_base64)?;
+    decoded.truncate(bytes);
+
+    Ok(decoded)
+}
+
+/// the number of (padded) base64 characters that encode `bytes` bytes
+fn base64_length(bytes: usize) -> Option<usize> {
+    bytes.checked_add(2).map(|bytes| bytes / 3)?.checked_mul(4)
+}
 
-        idx += inc;
+fn invalid_base64() -> ParseError {
+    ParseError::InvalidBase64 {
+        array: String::new(),
+        offset: None,
     }
+}
 
-    Ok((rest, ()))
+/// the encoding of the `<AppendedData>` section
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AppendedEncoding {
+    Raw,
+    Base64,
+}
+
+/// skip to the first byte after the `_` that starts the appended data section. Offsets of
+/// appended arrays are relative to this byte.
+///
+/// vtk itself writes whitespace between the `<AppendedData>` element and the `_`
+fn find_appended_start(xml_bytes: &[u8]) -> IResult<'_, AppendedEncoding> {
+    let (appended_data_section, _) = take_until_consume(xml_bytes, b"<AppendedData")?;
+    let (element_end, element) = take_till(|c| c == b'>')(appended_data_section)?;
+    let (_, element_attributes) = read_element_attributes(element)?;
+
+    let encoding = match element_attributes.get(b"encoding") {
+        None | Some(b"raw") => AppendedEncoding::Raw,
+        Some(b"base64") => AppendedEncoding::Base64,
+        Some(encoding) => return Err(NomError::nom(encoding, nom::error::ErrorKind::Tag)),
+    };
+
+    let (after_element, _) = tag(">")(element_end)?;
+    let (underscore, _whitespace) = nom::character::complete::multispace0(after_element)?;
+    let (appended_start, _) = tag("_")(underscore)?;
+    Ok((appended_start, encoding))
 }
 
 #[cfg(test)]
@@ -560,7 +1872,6 @@ mod tests {
     use super::*;
     use crate::Array;
     use crate::Binary;
-    use crate::Mesh3D;
     use crate::Rectilinear3D;
     use crate::Spans3D;
     use crate::Visitor;
@@ -625,19 +1936,46 @@ mod tests {
         assert!(out.data.u.len() > 1000);
     }
 
+    /// the mesh and a field of 20 x 20 x 5 points, for files that are written by the tests
+    #[cfg(feature = "derive")]
+    fn generated_domain<E>(offset: f64) -> (Rectilinear3D<f64, E>, Vec<f64>) {
+        let locations = |n: usize| (0..n).map(|i| i as f64 / n as f64).collect::<Vec<_>>();
+        let mesh = crate::Mesh3D::new(locations(20), locations(20), locations(5));
+        let field = (0..20 * 20 * 5).map(|i| offset + i as f64 * 0.01).collect();
+
+        (Rectilinear3D::new(mesh, Spans3D::new(20, 20, 5)), field)
+    }
+
     #[test]
+    #[cfg(feature = "derive")]
     fn full_vtk_base64() {
-        let out = read_and_parse(std::path::Path::new("./static/base64.vtk"));
+        use crate as vtk;
+        #[derive(vtk::DataArray, Debug)]
+        #[vtk_write(encoding = "base64")]
+        pub struct SpanDataBase64 {
+            u: Vec<f64>,
+        }
+
+        let (domain, u) = generated_domain::<crate::Ascii>(2.);
+        let mut file = Vec::new();
+        crate::write_vtk(
+            &mut file,
+            crate::VtkData::new(domain, SpanDataBase64 { u: u.clone() }),
+        )
+        .unwrap();
+
+        let out = parse_xml_document(&file);
         dbg!(&out);
         let out: crate::VtkData<Domain, crate::helpers::SpanData> = out.unwrap();
         assert!(out.data.u.len() > 1000);
+        assert_eq!(out.data.u, u);
     }
 
     #[test]
     #[cfg(feature = "derive")]
     fn full_vtk_binary() {
         use crate as vtk;
-        #[derive(vtk::DataArray, vtk::ParseArray, Debug)]
+        #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
         #[vtk_parse(spans = "vtk::Spans3D")]
         pub struct SpanDataBinary {
             u: Vec<f64>,
@@ -645,12 +1983,21 @@ mod tests {
             w: Vec<f64>,
         }
 
-        let out = read_and_parse(std::path::Path::new("./static/binary.vtk"));
+        let (domain, u) = generated_domain::<Binary>(2.);
+        let (_, v) = generated_domain::<Binary>(1.2);
+        let (_, w) = generated_domain::<Binary>(1.4);
+        let data = SpanDataBinary { u, v, w };
+
+        let mut file = Vec::new();
+        crate::write_vtk(&mut file, crate::VtkData::new(domain, data.clone())).unwrap();
+
+        let out = parse_xml_document(&file);
         dbg!(&out);
         let out: crate::VtkData<Domain, SpanDataBinary> = out.unwrap();
         assert!(out.data.u.len() > 1000);
         assert!(out.data.v.len() > 1000);
         assert!(out.data.w.len() > 1000);
+        assert_eq!(out.data, data);
     }
 
     #[test]
@@ -670,7 +2017,13 @@ mod tests {
 
         let (rest, array_type) = out.unwrap();
 
-        assert_eq!(array_type, DataArrayHeader::InlineAscii { components: 1 });
+        assert_eq!(
+            array_type,
+            DataArrayHeader::InlineAscii {
+                components: 1,
+                precision: Precision::Float64
+            }
+        );
         assert_eq!(rest, b"");
     }
 
@@ -683,7 +2036,13 @@ mod tests {
 
         let (rest, array_type) = out.unwrap();
 
-        assert_eq!(array_type, DataArrayHeader::InlineBase64 { components: 1 });
+        assert_eq!(
+            array_type,
+            DataArrayHeader::InlineBase64 {
+                components: 1,
+                precision: Precision::Float64
+            }
+        );
         assert_eq!(rest, b"");
     }
 
@@ -699,12 +2058,125 @@ mod tests {
             array_type,
             DataArrayHeader::AppendedBinary {
                 offset: 99,
-                components: 3
+                components: 3,
+                precision: Precision::Float64
+            }
+        );
+        assert_eq!(rest, b"");
+    }
+
+    #[test]
+    fn reordered_array_header() {
+        let header = r#"<DataArray Name="u" format="ascii" type="Float64">"#;
+        let (rest, array_type) = read_dataarray_header(header.as_bytes(), b"u").unwrap();
+
+        assert_eq!(
+            array_type,
+            DataArrayHeader::InlineAscii {
+                components: 1,
+                precision: Precision::Float64
+            }
+        );
+        assert_eq!(rest, b"");
+    }
+
+    #[test]
+    fn multiline_array_header() {
+        let header =
+            "<DataArray\n\ttype='Int32'  Name = \"u\"\n offset=\"8\" format=\"appended\"\n/>";
+        let (rest, array_type) = read_dataarray_header(header.as_bytes(), b"u").unwrap();
+
+        assert_eq!(
+            array_type,
+            DataArrayHeader::AppendedBinary {
+                offset: 8,
+                components: 1,
+                precision: Precision::Int32
             }
         );
         assert_eq!(rest, b"");
     }
 
+    #[test]
+    fn describe_array_header() {
+        let header = r#"<DataArray type="Float32" Name="velocity" NumberOfComponents="3" ComponentName0="u" ComponentName2="w" format="binary" RangeMin="0" RangeMax="1.5e2">"#;
+        let (_, description) = describe_dataarray(header.as_bytes()).unwrap();
+
+        assert_eq!(
+            description,
+            DataArrayDescription {
+                name: "velocity".into(),
+                header: DataArrayHeader::InlineBase64 {
+                    components: 3,
+                    precision: Precision::Float32
+                },
+                range: Some((0., 150.)),
+                component_names: vec![Some("u".into()), None, Some("w".into())],
+            }
+        );
+
+        // optional attributes are not required
+        let header = r#"<DataArray type="Float64" format="ascii">"#;
+        let (_, description) = describe_dataarray(header.as_bytes()).unwrap();
+
+        assert_eq!(description.name, "");
+        assert_eq!(description.range, None);
+        assert!(description.component_names.is_empty());
+    }
+
+    #[test]
+    fn section_arrays_by_name() {
+        let input = r#"
+            <Piece Extent="0 1 0 1 0 1">
+            <PointData Scalars="b">
+                <DataArray type="Float64" Name="a" format="ascii">1 2</DataArray>
+                <DataArray type="Int32" format="ascii">3 4</DataArray>
+                <DataArray type="Float64" Name="b" format="appended" offset="16"/>
+            </PointData>
+            <CellData/>
+            <Coordinates>
+                <DataArray type="Float64" Name="c" format="ascii">5 6</DataArray>
+            </Coordinates>
+            "#;
+
+        let (_, mut arrays) = read_section_arrays(input.as_bytes(), b"PointData").unwrap();
+
+        let mut names: Vec<_> = arrays.names().collect();
+        names.sort();
+        assert_eq!(names, [b"a".as_ref(), b"b"]);
+
+        assert_eq!(arrays.take(b"b", 0).unwrap().unwrap_appended(), 16);
+        assert_eq!(
+            arrays.take(b"a", 2).unwrap().unwrap_parsed(),
+            Buffer::Float(vec![1., 2.])
+        );
+
+        // arrays can only be taken once, and not from other sections
+        assert!(arrays.take(b"a", 2).is_err());
+        assert!(arrays.take(b"c", 2).is_err());
+
+        let (_, arrays) = read_section_arrays(input.as_bytes(), b"CellData").unwrap();
+        assert_eq!(arrays.names().count(), 0);
+    }
+
+    #[test]
+    fn unexpected_array_name() {
+        let input = r#"<DataArray type="Float64" Name="Y" format="ascii">1 2</DataArray>"#;
+        let err = parse_dataarray_or_lazy(input.as_bytes(), b"X", 2).unwrap_err();
+
+        match ParseError::from_nom_in(err, input.as_bytes(), "") {
+            ParseError::UnexpectedName {
+                expected,
+                found,
+                offset,
+            } => {
+                assert_eq!((expected.as_str(), found.as_str()), ("X", "Y"));
+                assert_eq!(offset, Some(0));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
     #[test]
     fn base_64_encoded_array() {
         let values = [1.0, 2.0, 3.0, 4.0];
@@ -715,29 +2187,225 @@ mod tests {
             &values.as_slice(),
             "X",
             crate::Encoding::Base64,
+            ByteOrder::LittleEndian,
         )
         .unwrap();
 
         let string = String::from_utf8(output).unwrap();
-        let parsed_result = parse_dataarray_or_lazy(&string.as_bytes(), b"X", 4);
+        let parsed_result = parse_dataarray_or_lazy(string.as_bytes(), b"X", 4);
 
         dbg!(&parsed_result);
 
-        let out = parsed_result.unwrap();
+        let (rest, out) = parsed_result.unwrap();
+
+        // inline base64 arrays are decoded along with the appended arrays
+        let data = PartialDataArrayBuffered::new(out, 4);
+        let mut buffers = Vec::new();
+        data.append_to_reader_list(&mut buffers);
+        let attributes = FileAttributes {
+            header_type: Precision::UInt64,
+            ..FileAttributes::default()
+        };
+        read_appended_array_buffers(buffers, rest, attributes).unwrap();
+
+        assert_eq!(data.into_buffer().into_vec::<f64>().unwrap(), &values);
+    }
+
+    #[test]
+    fn base64_with_uint32_header() {
+        // the header and the array are encoded separately, and the header is `UInt32` when the
+        // file has no `header_type`
+        let values = [1.5f64, -2., 4.25];
+        let mut array = Vec::new();
+        values
+            .iter()
+            .for_each(|value| array.extend(value.to_le_bytes()));
+
+        let encoded = format!(
+            "{}{}",
+            base64::encode(((values.len() * 8) as u32).to_le_bytes()),
+            base64::encode(&array)
+        );
+        let decoded = decode_base64_array(encoded.as_bytes(), FileAttributes::default()).unwrap();
+
+        assert_eq!(decoded, array);
+    }
+
+    #[test]
+    fn big_endian_base64_array() {
+        // the header and the values are encoded separately, as vtk writes them
+        let values = [1.5f64, -2., 4.25];
+        let mut array = Vec::new();
+        values
+            .iter()
+            .for_each(|value| array.extend(value.to_be_bytes()));
+
+        let encoded = format!(
+            "{}{}",
+            base64::encode(((values.len() * 8) as u64).to_be_bytes()),
+            base64::encode(&array)
+        );
+        let attributes = FileAttributes {
+            header_type: Precision::UInt64,
+            byte_order: ByteOrder::BigEndian,
+            ..FileAttributes::default()
+        };
+        let decoded = decode_base64_array(encoded.as_bytes(), attributes).unwrap();
+
+        let mut out = Buffer::with_capacity(Precision::Float64, 0);
+        out.extend_from_bytes(&decoded, Precision::Float64, ByteOrder::BigEndian);
+        assert_eq!(out.into_vec::<f64>().unwrap(), values);
+    }
+
+    #[test]
+    fn base64_single_stream() {
+        // the header and the values are encoded together, for both header types
+        let values = [1.5f64, -2., 4.25];
+        let mut array = Vec::new();
+        values
+            .iter()
+            .for_each(|value| array.extend(value.to_le_bytes()));
+
+        let uint32 = [&((array.len() as u32).to_le_bytes())[..], &array].concat();
+        let decoded =
+            decode_base64_array(base64::encode(uint32).as_bytes(), FileAttributes::default());
+        assert_eq!(decoded.unwrap(), array);
+
+        // any text after the stream belongs to the next array
+        let uint64 = [&((array.len() as u64).to_le_bytes())[..], &array].concat();
+        let encoded = format!("{}AAAA", base64::encode(uint64));
+        let attributes = FileAttributes {
+            header_type: Precision::UInt64,
+            ..FileAttributes::default()
+        };
+        let decoded = decode_base64_array(encoded.as_bytes(), attributes).unwrap();
+        assert_eq!(decoded, array);
+    }
+
+    #[test]
+    fn truncated_base64_array() {
+        // the header describes more bytes than are encoded after it
+        let encoded = format!(
+            "{}{}",
+            base64::encode(64u64.to_le_bytes()),
+            base64::encode([0; 8])
+        );
+        let attributes = FileAttributes {
+            header_type: Precision::UInt64,
+            ..FileAttributes::default()
+        };
+
+        assert!(matches!(
+            decode_base64_array(encoded.as_bytes(), attributes),
+            Err(ParseError::InvalidBase64 { .. })
+        ));
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn compressed_base64_array() {
+        // the header of a compressed array and its blocks are encoded separately, and any text
+        // after the blocks belongs to the next array
+        let values: Vec<u8> = (0..100_000u32).map(|value| (value % 251) as u8).collect();
+        let compressed =
+            compression::compress(&values, Compressor::ZLib, ByteOrder::LittleEndian).unwrap();
+        let header_bytes = (3 + compressed[0] as usize) * 8;
+
+        let encoded = format!(
+            "{}{}AAAA",
+            base64::encode(&compressed[..header_bytes]),
+            base64::encode(&compressed[header_bytes..])
+        );
+        let attributes = FileAttributes {
+            compressor: Some(Compressor::ZLib),
+            header_type: Precision::UInt64,
+            ..FileAttributes::default()
+        };
+        let decoded = decode_base64_array(encoded.as_bytes(), attributes).unwrap();
+
+        assert_eq!(decoded, values);
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn compressed_base64_single_stream() {
+        // the header of 4 blocks is not a multiple of 3 bytes, so its last base64 characters
+        // also hold the start of the first block
+        let values: Vec<u8> = (0..100_000u32).map(|value| (value % 251) as u8).collect();
+        let compressed =
+            compression::compress(&values, Compressor::ZLib, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(compressed[0], 4);
+
+        let encoded = format!("{}AAAA", base64::encode(&compressed));
+        let attributes = FileAttributes {
+            compressor: Some(Compressor::ZLib),
+            header_type: Precision::UInt64,
+            ..FileAttributes::default()
+        };
+        let decoded = decode_base64_array(encoded.as_bytes(), attributes).unwrap();
+
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn base64_appended_section() {
+        // offsets of a base64 appended section index into its encoded text
+        let values = [1.0f64, 2.0, 3.0, 4.0];
+        let values2 = [5.0f64, 6.0, 7.0];
+
+        let encode = |values: &[f64]| {
+            let bytes: Vec<u8> = values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect();
+            let header = (bytes.len() as u32).to_le_bytes();
+            format!("{}{}", base64::encode(header), base64::encode(bytes))
+        };
+        let (array, array2) = (encode(&values), encode(&values2));
+
+        let file = format!(
+            r#"<DataArray type="Float64" Name="X" format="appended" offset="0"/>
+            <DataArray type="Float64" Name="Y" format="appended" offset="{}"/>
+            <AppendedData encoding="base64">
+                _{}{}
+            </AppendedData>"#,
+            array.len(),
+            array,
+            array2
+        );
+
+        let (rest, parsed_header_1) = parse_dataarray_or_lazy(file.as_bytes(), b"X", 4).unwrap();
+        let (rest, parsed_header_2) = parse_dataarray_or_lazy(rest, b"Y", 3).unwrap();
+
+        let data_1 = PartialDataArrayBuffered::new(parsed_header_1, 4);
+        let data_2 = PartialDataArrayBuffered::new(parsed_header_2, 3);
+
+        let mut buffers = Vec::new();
+        data_2.append_to_reader_list(&mut buffers);
+        data_1.append_to_reader_list(&mut buffers);
+        read_appended_array_buffers(buffers, rest, FileAttributes::default()).unwrap();
 
-        assert_eq!(out.1.unwrap_parsed(), &values);
+        assert_eq!(
+            values.as_ref(),
+            data_1.into_buffer().into_vec::<f64>().unwrap()
+        );
+        assert_eq!(
+            values2.as_ref(),
+            data_2.into_buffer().into_vec::<f64>().unwrap()
+        );
     }
 
     #[test]
     fn appended_array() {
         let values = [1.0f64, 2.0, 3.0, 4.0];
-        let values2 = [5.0f64, 6.0, 7.0, 8.0];
+        let values2 = [5.0f64, 6.0, 7.0, 0.0];
 
         let mut output = Vec::new();
         let mut event_writer = crate::EventWriter::new(&mut output);
 
-        let offset_1 = -8;
-        let offset_2 = -8 + (4 * 8);
+        // every array is preceded by a header with its number of bytes
+        let offset_1 = 0;
+        let offset_2 = 8 + (4 * 8);
 
         crate::write_appended_dataarray_header(
             &mut event_writer,
@@ -759,55 +2427,46 @@ mod tests {
         // write the data inside the appended section
         crate::write_vtk::appended_binary_header_start(&mut event_writer).unwrap();
 
-        // need to write a single garbage byte for things to work as expected - this
-        // is becasue of how paraview expects things
-        [100f64]
-            .as_ref()
-            .write_binary(&mut event_writer, false)
-            .unwrap();
-
-        values
-            .as_ref()
-            .write_binary(&mut event_writer, false)
-            .unwrap();
-        values2
-            .as_ref()
-            .write_binary(&mut event_writer, true)
-            .unwrap();
+        for array in [values, values2] {
+            event_writer
+                .inner_mut()
+                .write_all(&32u64.to_le_bytes())
+                .unwrap();
+            array
+                .as_ref()
+                .write_binary(&mut event_writer, ByteOrder::LittleEndian)
+                .unwrap();
+        }
 
         crate::write_vtk::appended_binary_header_end(&mut event_writer).unwrap();
 
         // now we can start parsing the data
-        let string_representation = String::from_utf8_lossy(&output);
-        println!(":: xml data - {} ", string_representation);
-
-        // write data array headers
         let (rest, parsed_header_1) = parse_dataarray_or_lazy(&output, b"X", 4).unwrap();
-        let (rest, parsed_header_2) = parse_dataarray_or_lazy(&rest, b"Y", 4).unwrap();
+        let (rest, parsed_header_2) = parse_dataarray_or_lazy(rest, b"Y", 4).unwrap();
 
-        let header_1 = parsed_header_1.unwrap_appended();
-        let header_2 = parsed_header_2.unwrap_appended();
+        let data_1 = PartialDataArrayBuffered::new(parsed_header_1, 4);
+        let data_2 = PartialDataArrayBuffered::new(parsed_header_2, 4);
 
-        let len_1 = AppendedArrayLength::Known((header_2 - header_1) as usize);
-        let len_2 = AppendedArrayLength::UntilEnd;
+        // read the arrays in the opposite order they are stored
+        let mut buffers = Vec::new();
+        data_2.append_to_reader_list(&mut buffers);
+        data_1.append_to_reader_list(&mut buffers);
 
-        let (rest, _) = setup_appended_read(rest).unwrap();
+        let attributes = FileAttributes {
+            compressor: None,
+            header_type: Precision::UInt64,
+            byte_order: ByteOrder::LittleEndian,
+            grid_type: None,
+        };
+        read_appended_array_buffers(buffers, rest, attributes).unwrap();
 
-        println!(
-            ":: xml data after queued movement- {} ",
-            string_representation
+        assert_eq!(
+            values.as_ref(),
+            data_1.into_buffer().into_vec::<f64>().unwrap()
+        );
+        assert_eq!(
+            values2.as_ref(),
+            data_2.into_buffer().into_vec::<f64>().unwrap()
         );
-
-        let mut data_1 = Vec::new();
-        let mut data_2 = Vec::new();
-
-        let (rest, _) = parse_appended_binary(rest, len_1, &mut data_1).unwrap();
-
-        let string_representation = String::from_utf8_lossy(&rest);
-        println!("between parses - {}", string_representation);
-        let (_rest, _) = parse_appended_binary(rest, len_2, &mut data_2).unwrap();
-
-        assert_eq!(values.as_ref(), data_1);
-        assert_eq!(values2.as_ref(), data_2);
     }
 }
@@ -1,6 +1,7 @@
 //! Common traits and types that are useful for working with `vtk`
 #![allow(unused_imports)]
 
+pub use crate::buffer::Buffer;
 pub use crate::data::VtkData;
 pub use crate::traits::{
     Array, DataArray, Domain, Encode, FromBuffer, Numeric, ParseArray, ParseMesh, ParseSpan,
@@ -10,11 +11,11 @@ pub use crate::EventWriter;
 
 pub(crate) use xml::writer::XmlEvent;
 
-pub(crate) use crate::write_vtk::Precision;
+pub(crate) use crate::write_vtk::{ByteOrder, GridType, Precision};
 
+pub(crate) use crate::parse::IResult;
 pub(crate) use crate::{Ascii, Base64, Binary};
 pub(crate) use crate::{Error, ParseError};
-pub(crate) use nom::IResult;
 pub(crate) use std::cell::{RefCell, RefMut};
 pub(crate) use std::io::Write;
 
@@ -0,0 +1,353 @@
+//! Writing files whose arrays are produced incrementally
+//!
+//! [`write_vtk`](crate::write_vtk) requires every array of a file to be in memory at once. A
+//! [`StreamWriter`] instead writes the headers of the file from a [`StreamSchema`] describing
+//! the arrays, after which the values of each array are written in chunks (such as one z-plane
+//! at a time) directly to the appended section of the file.
+use crate::prelude::*;
+use crate::write_vtk::{ArrayHeaders, ARRAY_HEADER_BYTES};
+
+/// The layout of a single array written by a [`StreamWriter`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamArray {
+    name: String,
+    precision: Precision,
+    components: usize,
+    tuples: usize,
+    cell: bool,
+}
+
+impl StreamArray {
+    /// an array of the `<PointData>` section with `tuples` points (`nx * ny * nz` for a
+    /// structured domain) of `components` values each
+    pub fn new(name: &str, precision: Precision, components: usize, tuples: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            precision,
+            components,
+            tuples,
+            cell: false,
+        }
+    }
+
+    /// place the array in the `<CellData>` section instead, `tuples` is then the number of cells
+    pub fn cell(mut self) -> Self {
+        self.cell = true;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// the total number of values of the array
+    pub fn len(&self) -> usize {
+        self.tuples * self.components
+    }
+
+    /// if the array does not contain any values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// the number of bytes of the array in the appended section, without its header
+    fn size(&self) -> usize {
+        self.len() * self.precision.size_of()
+    }
+}
+
+/// The arrays of a file written by a [`StreamWriter`], in the order their values are written
+///
+/// ```
+/// use vtk::{Precision, StreamArray, StreamSchema};
+///
+/// let points = 4 * 3 * 2;
+/// let cells = 3 * 2 * 1;
+///
+/// let schema = StreamSchema::new()
+///     .with_array(StreamArray::new("rho", Precision::Float64, 1, points))
+///     .with_array(StreamArray::new("velocity", Precision::Float32, 3, points))
+///     .with_array(StreamArray::new("rank", Precision::Int32, 1, cells).cell());
+///
+/// assert_eq!(schema.arrays().len(), 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamSchema {
+    arrays: Vec<StreamArray>,
+}
+
+impl StreamSchema {
+    /// a schema without any arrays
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add an array after every array that is already part of the schema
+    pub fn with_array(mut self, array: StreamArray) -> Self {
+        self.arrays.push(array);
+        self
+    }
+
+    pub fn arrays(&self) -> &[StreamArray] {
+        &self.arrays
+    }
+}
+
+/// Misuse of a [`StreamWriter`], where the values written do not match its [`StreamSchema`]
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum StreamError {
+    #[error("values were written to array `{found}` while `{expected}` is expected next")]
+    UnexpectedArray { expected: String, found: String },
+    #[error("array `{array}` is not part of the schema of the file, or was already written")]
+    UnknownArray { array: String },
+    #[error("array `{array}` is declared as {expected:?} but {found:?} values were written")]
+    Precision {
+        array: String,
+        expected: Precision,
+        found: Precision,
+    },
+    #[error("array `{array}` is declared with {expected} values but {found} values were written")]
+    TooManyValues {
+        array: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("array `{array}` is declared with {expected} values but only {found} were written")]
+    MissingValues {
+        array: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Write a file one chunk of an array at a time.
+///
+/// The mesh and field data of the file are written when the writer is created. The values of
+/// each array are then written with [`write_values`](StreamWriter::write_values), in the order
+/// of the arrays in the schema and in the order of the file: the x index changing fastest, then
+/// y, then z, with the components of each point next to each other. The file is complete once
+/// [`finish`](StreamWriter::finish) checks that every array received all of its values.
+///
+/// Every array is written to the appended section, the arrays can not be compressed since
+/// their offsets are written before any of the values are known.
+///
+/// ```
+/// use vtk::{Precision, StreamArray, StreamSchema, StreamWriter};
+///
+/// let (nx, ny, nz) = (4, 3, 2);
+/// let mesh = vtk::Mesh3D::<f64, vtk::Binary>::new(
+///     vec![0., 1., 2., 3.],
+///     vec![0., 1., 2.],
+///     vec![0., 1.],
+/// );
+/// let domain = vtk::Rectilinear3D::new(mesh, vtk::Spans3D::new(nx, ny, nz));
+///
+/// let schema = StreamSchema::new()
+///     .with_array(StreamArray::new("rho", Precision::Float64, 1, nx * ny * nz));
+///
+/// let mut writer = StreamWriter::new(Vec::new(), vtk::VtkData::new(domain, schema)).unwrap();
+///
+/// // one z-plane at a time
+/// for z in 0..nz {
+///     let plane = vec![z as f64; nx * ny];
+///     writer.write_values("rho", &plane).unwrap();
+/// }
+///
+/// let file: Vec<u8> = writer.finish().unwrap();
+/// ```
+pub struct StreamWriter<W: Write> {
+    appended: EventWriter<ArrayHeaders<W>>,
+    arrays: Vec<StreamArray>,
+    /// the index of the array that is currently being written
+    current: usize,
+    /// the number of values of the current array that were written
+    written: usize,
+    byte_order: ByteOrder,
+}
+
+impl<W: Write> StreamWriter<W> {
+    /// write the headers and mesh of the file described by `data` to `writer`
+    pub fn new<DOMAIN, EncMesh>(
+        writer: W,
+        data: VtkData<DOMAIN, StreamSchema>,
+    ) -> Result<Self, Error>
+    where
+        DOMAIN: Domain<EncMesh>,
+    {
+        Self::with_byte_order(writer, data, ByteOrder::LittleEndian)
+    }
+
+    /// write the headers and mesh of the file described by `data` to `writer`, with every binary
+    /// value in `byte_order` (see [`WriteOptions`](crate::WriteOptions)). Streamed arrays are
+    /// never compressed
+    pub fn with_byte_order<DOMAIN, EncMesh>(
+        writer: W,
+        data: VtkData<DOMAIN, StreamSchema>,
+        byte_order: ByteOrder,
+    ) -> Result<Self, Error>
+    where
+        DOMAIN: Domain<EncMesh>,
+    {
+        let mut writer = EventWriter::new(writer);
+        let arrays = data.data.arrays;
+
+        let mesh_sizes = data.domain.mesh_appended_sizes();
+        let array_sizes: Vec<usize> = arrays.iter().map(StreamArray::size).collect();
+
+        let offsets = write_vtk::appended_offsets(
+            mesh_sizes
+                .iter()
+                .chain(array_sizes.iter())
+                .map(|size| size + ARRAY_HEADER_BYTES),
+        );
+        let (mesh_offsets, array_offsets) = offsets.split_at(mesh_sizes.len());
+
+        write_vtk::write_file_start(
+            &mut writer,
+            &data.domain,
+            &data.domain.whole_span_string(),
+            &data.field_data,
+            None,
+            byte_order,
+        )?;
+        write_vtk::write_piece_start(&mut writer, &data.domain, mesh_offsets, byte_order)?;
+
+        write_vtk::open_element(&mut writer, "PointData")?;
+        write_headers(&mut writer, &arrays, array_offsets, false)?;
+        write_vtk::close_element(&mut writer, "PointData")?;
+
+        if arrays.iter().any(|array| array.cell) {
+            write_vtk::open_element(&mut writer, "CellData")?;
+            write_headers(&mut writer, &arrays, array_offsets, true)?;
+            write_vtk::close_element(&mut writer, "CellData")?;
+        }
+
+        write_vtk::close_element(&mut writer, "Piece")?;
+        write_vtk::close_element(&mut writer, data.domain.grid_type().to_str())?;
+        write_vtk::appended_binary_header_start(&mut writer)?;
+
+        let sizes = mesh_sizes.into_iter().chain(array_sizes).collect();
+        let mut appended =
+            EventWriter::new(ArrayHeaders::new(writer.into_inner(), sizes, byte_order));
+        data.domain.write_mesh_appended(&mut appended, byte_order)?;
+
+        Ok(Self {
+            appended,
+            arrays,
+            current: 0,
+            written: 0,
+            byte_order,
+        })
+    }
+
+    /// write the next `values` of the array `name`.
+    ///
+    /// Writing to the next array of the schema is only possible once every value of the
+    /// current array has been written. Nothing is written if the values do not fit the array.
+    pub fn write_values<T: Numeric>(&mut self, name: &str, values: &[T]) -> Result<(), Error> {
+        // move on to the next array once every value of the current one is written
+        while self.current < self.arrays.len()
+            && self.arrays[self.current].name != name
+            && self.written == self.arrays[self.current].len()
+        {
+            self.current += 1;
+            self.written = 0;
+        }
+
+        let array = match self.arrays.get(self.current) {
+            Some(array) if array.name == name => array,
+            Some(array) => {
+                let error = if self.arrays[self.current..].iter().any(|a| a.name == name) {
+                    StreamError::UnexpectedArray {
+                        expected: array.name.clone(),
+                        found: name.to_string(),
+                    }
+                } else {
+                    StreamError::UnknownArray {
+                        array: name.to_string(),
+                    }
+                };
+                return Err(error.into());
+            }
+            None => {
+                return Err(StreamError::UnknownArray {
+                    array: name.to_string(),
+                }
+                .into())
+            }
+        };
+
+        if T::as_precision() != array.precision {
+            return Err(StreamError::Precision {
+                array: array.name.clone(),
+                expected: array.precision,
+                found: T::as_precision(),
+            }
+            .into());
+        }
+
+        if self.written + values.len() > array.len() {
+            return Err(StreamError::TooManyValues {
+                array: array.name.clone(),
+                expected: array.len(),
+                found: self.written + values.len(),
+            }
+            .into());
+        }
+
+        let mut bytes = Vec::with_capacity(values.len() * array.precision.size_of());
+        for value in values {
+            value.extend_bytes(&mut bytes, self.byte_order);
+        }
+
+        self.appended.inner_mut().write_all(&bytes)?;
+        self.written += values.len();
+
+        Ok(())
+    }
+
+    /// check that every array received all of its values and complete the file, returning the
+    /// writer it was written to
+    pub fn finish(self) -> Result<W, Error> {
+        let mut written = self.written;
+
+        for array in self.arrays.iter().skip(self.current) {
+            if written != array.len() {
+                return Err(StreamError::MissingValues {
+                    array: array.name.clone(),
+                    expected: array.len(),
+                    found: written,
+                }
+                .into());
+            }
+            written = 0;
+        }
+
+        let mut inner = self.appended.into_inner().finish()?;
+        inner.write_all(b"</AppendedData></VTKFile>")?;
+
+        Ok(inner)
+    }
+}
+
+/// write the appended headers of either the point or the cell arrays of the schema
+fn write_headers<W: Write>(
+    writer: &mut EventWriter<W>,
+    arrays: &[StreamArray],
+    offsets: &[i64],
+    cell: bool,
+) -> Result<(), Error> {
+    for (array, offset) in arrays.iter().zip(offsets) {
+        if array.cell == cell {
+            write_vtk::write_appended_dataarray_header(
+                writer,
+                &array.name,
+                *offset,
+                array.components,
+                array.precision,
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,243 @@
+//! Inspecting the structure of a file without reading its arrays
+//!
+//! [`read_summary`] only reads a file up to its `<AppendedData>` section and describes every
+//! `DataArray` element from its attributes, so the size and contents of a large number of files
+//! can be checked cheaply before they are read in full.
+use crate::parse::{DataArrayDescription, FileAttributes, NomErr};
+use crate::prelude::*;
+use crate::Spans3D;
+
+use nom::bytes::complete::take_until;
+use std::io::Read;
+
+/// The sections of a piece whose arrays are described, in no particular order
+const SECTIONS: [&str; 9] = [
+    "PointData",
+    "CellData",
+    "Coordinates",
+    "Points",
+    "Cells",
+    "Verts",
+    "Lines",
+    "Strips",
+    "Polys",
+];
+
+/// The sizes of a piece, in the order they are reported
+const PIECE_SIZES: [&str; 6] = [
+    "NumberOfPoints",
+    "NumberOfCells",
+    "NumberOfVerts",
+    "NumberOfLines",
+    "NumberOfStrips",
+    "NumberOfPolys",
+];
+
+/// Everything that is known about a file from its XML elements
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSummary {
+    /// the `type` of the dataset, if it is one that can be read by this crate
+    pub grid_type: Option<GridType>,
+    /// how the binary arrays of the file are stored
+    pub attributes: FileAttributes,
+    /// the `WholeExtent` of the dataset, which only datasets with structured points have
+    pub whole_extent: Option<Spans3D>,
+    /// the arrays of the `<FieldData>` element of the dataset
+    pub field_data: Vec<DataArrayDescription>,
+    pub pieces: Vec<PieceSummary>,
+}
+
+/// The size and arrays of a single `<Piece>` of a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct PieceSummary {
+    /// the `Extent` of the piece, which only datasets with structured points have
+    pub extent: Option<Spans3D>,
+    /// every `NumberOfPoints`, `NumberOfCells` (or `NumberOfVerts` to `NumberOfPolys` for
+    /// `PolyData`) attribute of the piece
+    pub sizes: Vec<(String, usize)>,
+    /// every section containing arrays, in the order they appear in the file
+    pub sections: Vec<SectionSummary>,
+}
+
+impl PieceSummary {
+    /// the value of a size attribute of the piece, such as `NumberOfPoints`
+    pub fn size(&self, name: &str) -> Option<usize> {
+        self.sizes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, size)| *size)
+    }
+
+    /// the section with the name `name`, such as `PointData`
+    pub fn section(&self, name: &str) -> Option<&SectionSummary> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+}
+
+/// The arrays of a section of a piece, such as `<PointData>` or `<Coordinates>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionSummary {
+    pub name: String,
+    pub arrays: Vec<DataArrayDescription>,
+}
+
+/// describe a file from its XML elements, without reading its appended data
+///
+/// ```no_run
+/// let summary = vtk::read_summary("./data/output.vtr".as_ref()).unwrap();
+///
+/// for piece in &summary.pieces {
+///     for section in &piece.sections {
+///         for array in &section.arrays {
+///             println!("{}: {} {:?}", section.name, array.name, array.header);
+///         }
+///     }
+/// }
+/// ```
+pub fn read_summary(path: &std::path::Path) -> Result<FileSummary, Error> {
+    const APPENDED: &[u8] = b"<AppendedData";
+
+    let mut file = std::fs::File::open(path)?;
+    let mut buffer = Vec::new();
+    let mut chunk = vec![0; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+
+        // the start of the appended section may be split between two chunks
+        let search_start = buffer.len().saturating_sub(APPENDED.len());
+        buffer.extend_from_slice(&chunk[..read]);
+
+        if let Some(idx) = buffer[search_start..]
+            .windows(APPENDED.len())
+            .position(|window| window == APPENDED)
+        {
+            buffer.truncate(search_start + idx);
+            break;
+        }
+    }
+
+    parse_summary(&buffer)
+}
+
+/// describe the bytes of a file, see [`read_summary`]. Anything after the start of the
+/// `<AppendedData>` section is ignored
+pub fn parse_summary(bytes: &[u8]) -> Result<FileSummary, Error> {
+    let (_, summary) = summarize(bytes).map_err(|e| {
+        ParseError::from_nom_in(e, bytes, "could not read the elements of the file")
+    })?;
+
+    Ok(summary)
+}
+
+fn summarize(bytes: &[u8]) -> IResult<'_, FileSummary> {
+    let xml = parse::xml_elements(bytes);
+
+    let (_, attributes) = parse::read_file_attributes(xml)?;
+
+    // the dataset element and the field data, before any of the pieces
+    let header = match take_until::<_, _, parse::NomError>("<Piece")(xml) {
+        Ok((_, header)) => header,
+        Err(_) => xml,
+    };
+
+    let whole_extent = if contains(header, b"WholeExtent") {
+        Some(parse::find_extent::<Spans3D>(header)?.1)
+    } else {
+        None
+    };
+
+    let field_data = match parse::find_section(header, b"FieldData") {
+        Ok((_, contents)) => describe_arrays(contents)?,
+        Err(_) => Vec::new(),
+    };
+
+    let mut pieces = Vec::new();
+    let mut rest = xml;
+
+    while let Ok((piece_start, _)) = parse::take_until_consume(rest, b"<Piece") {
+        let (piece, summary) = summarize_piece(piece_start)?;
+        pieces.push(summary);
+        rest = piece;
+    }
+
+    let summary = FileSummary {
+        grid_type: attributes.grid_type,
+        attributes,
+        whole_extent,
+        field_data,
+        pieces,
+    };
+
+    Ok((bytes, summary))
+}
+
+/// `xml_bytes` starts within the `<Piece` element, the remaining bytes start after the piece
+fn summarize_piece(xml_bytes: &[u8]) -> IResult<'_, PieceSummary> {
+    let (element_end, element) = nom::bytes::complete::take_till(|c| c == b'>')(xml_bytes)?;
+    let (_, attributes) = parse::read_element_attributes(element)?;
+
+    let extent = match attributes.get(b"Extent") {
+        Some(extent) => Some(parse::parse_extent(extent)?),
+        None => None,
+    };
+
+    let mut sizes = Vec::new();
+    for name in PIECE_SIZES {
+        if let Some(size) = attributes.get(name.as_bytes()) {
+            sizes.push((name.to_string(), parse::parse_number(size, "")?));
+        }
+    }
+
+    let (rest, contents) = match take_until::<_, _, parse::NomError>("</Piece>")(element_end) {
+        Ok((rest, contents)) => (rest, contents),
+        Err(_) => (&element_end[element_end.len()..], element_end),
+    };
+
+    let mut sections = Vec::new();
+    for name in SECTIONS {
+        if let Ok((after_section, section)) = parse::find_section(contents, name.as_bytes()) {
+            let arrays = describe_arrays(section)?;
+            sections.push((contents.len() - after_section.len(), name, arrays));
+        }
+    }
+    sections.sort_by_key(|(position, _, _)| *position);
+
+    let sections = sections
+        .into_iter()
+        .map(|(_, name, arrays)| SectionSummary {
+            name: name.to_string(),
+            arrays,
+        })
+        .collect();
+
+    let summary = PieceSummary {
+        extent,
+        sizes,
+        sections,
+    };
+
+    Ok((rest, summary))
+}
+
+/// describe every `DataArray` of the contents of a section
+fn describe_arrays(mut section: &[u8]) -> Result<Vec<DataArrayDescription>, NomErr<'_>> {
+    let mut arrays = Vec::new();
+
+    while contains(section, b"<DataArray") {
+        let (rest, description) = parse::describe_dataarray(section)?;
+        arrays.push(description);
+        section = rest;
+    }
+
+    Ok(arrays)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
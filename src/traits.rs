@@ -7,9 +7,10 @@
 //!
 
 use crate::parse;
+use crate::parse::IResult;
+use crate::ArrayDeclaration;
 use crate::Error;
 use crate::ParseError;
-use nom::IResult;
 use std::cell::RefMut;
 use std::io::Write;
 use xml::writer::EventWriter;
@@ -49,7 +50,7 @@ use crate::prelude::*;
 ///     fn write_appended_dataarray_headers<W: Write>(
 ///         &self,
 ///         writer: &mut EventWriter<W>,
-///         starting_offset: i64,
+///         offsets: &[i64],
 ///     ) -> Result<(), crate::Error> {
 ///         Ok(())
 ///     }
@@ -64,6 +65,20 @@ use crate::prelude::*;
 /// }
 /// ```
 ///
+/// Arrays can be placed in the `<CellData>` section instead of the `<PointData>` section
+/// with the `#[vtk(cell)]` field attribute:
+///
+/// ```ignore
+/// #[derive(vtk::DataArray)]
+/// struct FlowData {
+///     // shape (nx, ny, nz)
+///     pressure: vtk::Scalar3D<f64>,
+///     // shape (nx - 1, ny - 1, nz - 1)
+///     #[vtk(cell)]
+///     volume: vtk::Scalar3D<f64>,
+/// }
+/// ```
+///
 /// The recommended way of using this trait is deriving. You can encoding into `"binary"`
 /// (default), `"ascii"`, or `"base64"`:
 ///
@@ -88,14 +103,14 @@ use crate::prelude::*;
 ///     <RectilinearGrid WholeExtent="0 63 0 63 0 63">
 ///         <Piece Extent="0 63 0 63 0 63">
 ///             <Coordinates>
-///                 <DataArray type="Float64" NumberOfComponents="1" Name="X" format="appended" offset="-8" />
-///                 <DataArray type="Float64" NumberOfComponents="1" Name="Y" format="appended" offset="504" />
-///                 <DataArray type="Float64" NumberOfComponents="1" Name="Z" format="appended" offset="1016" />
+///                 <DataArray type="Float64" NumberOfComponents="1" Name="X" format="appended" offset="0" />
+///                 <DataArray type="Float64" NumberOfComponents="1" Name="Y" format="appended" offset="520" />
+///                 <DataArray type="Float64" NumberOfComponents="1" Name="Z" format="appended" offset="1040" />
 ///             </Coordinates>
 ///             <PointData>
-///                 <DataArray type="Float64" NumberOfComponents="1" Name="u" format="appended" offset="1528" />
-///                 <DataArray type="Float64" NumberOfComponents="1" Name="v" format="appended" offset="2098680" />
-///                 <DataArray type="Float64" NumberOfComponents="1" Name="w" format="appended" offset="4195832" />
+///                 <DataArray type="Float64" NumberOfComponents="1" Name="u" format="appended" offset="1560" />
+///                 <DataArray type="Float64" NumberOfComponents="1" Name="v" format="appended" offset="2098720" />
+///                 <DataArray type="Float64" NumberOfComponents="1" Name="w" format="appended" offset="4195880" />
 ///             </PointData>
 ///         </Piece>
 ///     </RectilinearGrid>
@@ -103,24 +118,75 @@ use crate::prelude::*;
 ///         _binary data here
 ///     </AppendedData>
 /// </VTKFile>
+/// ```
+///
+/// where every array in the appended section is preceded by a `UInt64` with its number of bytes,
+/// and the offsets point to that header.
 pub trait DataArray<Encoding> {
     /// Write all the arrays in the <PointData> section of the file
     ///
     /// If the encoding is base64 or ascii, this function should write the data in the element.
     /// If the encoding is binary, then this function will only write information about the length
     /// and offset of the arrays and `write_mesh_appended` will handle writing the binary data.
+    ///
+    /// `offsets` contains the offset of every array written by `write_array_appended`, in the
+    /// order they are written. It is empty if nothing is appended. Base64 encoded arrays are
+    /// written with the bytes of each value in `byte_order`.
     fn write_array_header<W: Write>(
         &self,
         writer: &mut EventWriter<W>,
-        starting_offset: i64,
+        offsets: &[i64],
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error>;
 
     /// If the encoding is binary, write all of the binary information to the appended
-    /// section of the binary file (raw bytes)
+    /// section of the binary file (raw bytes in `byte_order`)
     fn write_array_appended<W: Write>(
         &self,
         writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error>;
+
+    /// The number of raw bytes of each array written by `write_array_appended`, in the order
+    /// they are written. The offsets passed to the header functions are computed from these
+    /// sizes (and the compression of the file, if any).
+    ///
+    /// By default nothing is appended, binary encoded implementations must override this.
+    fn appended_sizes(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// Write all the arrays in the <CellData> section of the file, in the same way as
+    /// `write_array_header`. Cell centered arrays have one less value than point arrays in each
+    /// direction.
+    ///
+    /// `offsets` are the same offsets passed to `write_array_header` and cover both the point
+    /// and cell arrays, since `write_array_appended` writes the binary information of both.
+    ///
+    /// By default there are no cell arrays. With the derive, fields marked with `#[vtk(cell)]`
+    /// are written here.
+    fn write_cell_array_header<W: Write>(
+        &self,
+        _writer: &mut EventWriter<W>,
+        _offsets: &[i64],
+        _byte_order: ByteOrder,
+    ) -> Result<(), crate::Error> {
+        Ok(())
+    }
+
+    /// If there are any arrays written by `write_cell_array_header`. The `<CellData>` element
+    /// is only written when this returns `true`
+    fn has_cell_arrays(&self) -> bool {
+        false
+    }
+
+    /// The name, type and number of components of every array, which are declared in the
+    /// `<PPointData>` and `<PCellData>` elements of a parallel file such as a `.pvtr`.
+    ///
+    /// By default no arrays are declared. The derive declares every written field.
+    fn array_declarations(&self) -> Vec<ArrayDeclaration> {
+        Vec::new()
+    }
 }
 
 /// Information on how to write data from a given array (as part of a larger collection
@@ -137,20 +203,23 @@ pub trait Array {
         name: &str,
     ) -> Result<(), crate::Error>;
 
-    /// outputs the information in the data array to base64 encoded data
+    /// outputs the information in the data array to base64 encoded data, with the bytes of
+    /// each value in `byte_order`
     fn write_base64<W: Write>(
         &self,
         writer: &mut EventWriter<W>,
         name: &str,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error>;
 
     /// write the file data to the file to the appended section in binary form
     ///
-    /// if this is the last array written to the file, `is_last` should be set to true
+    /// Only the raw bytes of the elements are written, the header with the length of the
+    /// array is written by [`write_vtk`](crate::write_vtk).
     fn write_binary<W: Write>(
         &self,
         writer: &mut EventWriter<W>,
-        is_last: bool,
+        byte_order: ByteOrder,
     ) -> Result<(), crate::Error>;
 
     // the number of elements in this array
@@ -168,8 +237,15 @@ pub trait Array {
 
 /// Converts a buffer of bytes (as read from a VTK file) to the correct order
 /// for your [`Array`] type
-pub trait FromBuffer<SPAN> {
-    fn from_buffer(buffer: Vec<f64>, spans: &SPAN, components: usize) -> Self;
+///
+/// Every array is decoded to a [`Buffer`](crate::Buffer) of floats, signed or unsigned integers
+/// depending on its `type` attribute, the implementations for containers of [`Numeric`] values
+/// convert the buffer to their own element type.
+///
+/// A buffer that does not hold as many values as the container results in a
+/// [`ParseError::ArrayLength`](crate::ParseError::ArrayLength).
+pub trait FromBuffer<SPAN>: Sized {
+    fn from_buffer(buffer: Buffer, spans: &SPAN, components: usize) -> Result<Self, ParseError>;
 }
 
 /// Description on how to write the mesh and span information to a vtk file.
@@ -177,28 +253,73 @@ pub trait FromBuffer<SPAN> {
 /// This trait is required to be implemented on the type in the `domain` field
 /// of [VtkData](crate::VtkData).
 ///
-/// This type trait is implemented for the [Rectilinear3D](crate::Rectilinear3D)
-/// and [Rectilinear2D](crate::Rectilinear2D) types. You probably want to use one of those
-/// instead of creating your own.
+/// This type trait is implemented for the [Rectilinear3D](crate::Rectilinear3D),
+/// [Rectilinear2D](crate::Rectilinear2D), [Uniform3D](crate::Uniform3D),
+/// [Uniform2D](crate::Uniform2D), [Structured3D](crate::Structured3D),
+/// [Unstructured](crate::Unstructured) and [PolyData](crate::PolyData) types. You probably want
+/// to use one of those instead of creating your own.
 ///
 pub trait Domain<Encoding> {
-    /// Write the mesh information within the `<Coordinates>` section of the file
+    /// Write the geometry of the mesh to the file, including the enclosing element
+    /// (`<Coordinates>` for a rectilinear grid, `<Points>` for a structured grid). Domains
+    /// without any geometry arrays, such as `ImageData`, write nothing here.
     ///
     /// If the encoding is base64 or ascii, this function should write the data in the element.
     /// If the encoding is binary, then this function will only write information about the length
     /// and offset of the arrays and `write_mesh_appended` will handle writing the binary data.
-    fn write_mesh_header<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error>;
+    ///
+    /// `offsets` contains the offset of every array written by `write_mesh_appended`, in the
+    /// order they are written. Base64 encoded arrays are written in `byte_order`.
+    fn write_mesh_header<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        offsets: &[i64],
+        byte_order: ByteOrder,
+    ) -> Result<(), Error>;
 
-    /// If writing binary encoded data, this function writes raw binary information to the writer.
+    /// If writing binary encoded data, this function writes raw binary information (in
+    /// `byte_order`) to the writer.
     ///
     /// If the encoding is base64 / ascii, this function does nothing.
-    fn write_mesh_appended<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error>;
+    fn write_mesh_appended<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error>;
 
     /// The VTK-formatted span / extent string for location spans contained in the mesh
     fn span_string(&self) -> String;
 
-    /// number of raw bytes (not encoded in base64 / ascii) that are contained in this mesh
-    fn mesh_bytes(&self) -> usize;
+    /// The `WholeExtent` of the dataset the mesh is a piece of.
+    ///
+    /// By default the mesh is the whole dataset, so this is the same as `span_string`. A
+    /// [`ParallelPiece`](crate::ParallelPiece) is written with the extent of the whole dataset.
+    fn whole_span_string(&self) -> String {
+        self.span_string()
+    }
+
+    /// The number of raw bytes (not encoded in base64 / ascii) of each array written by
+    /// `write_mesh_appended`, in the order they are written.
+    ///
+    /// By default nothing is appended, binary encoded implementations must override this.
+    fn mesh_appended_sizes(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// The type of VTK dataset this domain is written as
+    fn grid_type(&self) -> GridType;
+
+    /// Additional attributes written to the dataset element alongside `WholeExtent`,
+    /// such as the `Origin` and `Spacing` of `ImageData`
+    fn grid_attributes(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Additional attributes written to the `<Piece>` element, such as the `NumberOfPoints`
+    /// and `NumberOfCells` of an `UnstructuredGrid`
+    fn piece_attributes(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
 }
 
 /// Helper trait to provide type information on a mesh
@@ -235,9 +356,9 @@ pub trait ParseMesh {
 ///     fn read_headers<'a>(
 ///         _spans: &vtk::Spans3D,
 ///         buffer: &'a [u8],
-///     ) -> nom::IResult<&'a [u8], Self> {
-///         let rest = buffer;
-///         let (rest, u) = vtk::parse::parse_dataarray_or_lazy(rest, b"u", 0)?;
+///     ) -> vtk::parse::IResult<'a, Self> {
+///         let (rest, mut point_data) = vtk::parse::read_section_arrays(buffer, b"PointData")?;
+///         let u = point_data.take(b"u", 0)?;
 ///         let u = vtk::parse::PartialDataArrayBuffered::new(u, 0);
 ///         let visitor = SpanDataVisitor { u };
 ///         Ok((rest, visitor))
@@ -251,7 +372,8 @@ pub trait ParseMesh {
 ///     fn finish(self, spans: &vtk::Spans3D) -> Result<Self::Output, vtk::ParseError> {
 ///         let comp = self.u.components();
 ///         let u = self.u.into_buffer();
-///         let u = vtk::FromBuffer::from_buffer(u, &spans, comp);
+///         let u = vtk::FromBuffer::from_buffer(u, &spans, comp)
+///             .map_err(|e| e.with_array_name("u"))?;
 ///         Ok(SpanData { u })
 ///     }
 /// }
@@ -274,16 +396,31 @@ where
     /// The type that will be output from the visitor once parsing is complete
     type Output;
 
-    /// The implementing type is constructed with the `read_headers` function.
-    fn read_headers<'a>(spans: &Spans, buffer: &'a [u8]) -> IResult<&'a [u8], Self>;
+    /// The implementing type is constructed with the `read_headers` function. `buffer` starts at
+    /// the beginning of the piece, so each section (such as `<PointData>`) should be found with
+    /// [`read_section_arrays`](crate::parse::read_section_arrays) (or
+    /// [`skip_to_section`](crate::parse::skip_to_section)) before its arrays are read.
+    fn read_headers<'a>(spans: &Spans, buffer: &'a [u8]) -> IResult<'a, Self>;
+
+    /// Construct the visitor for a piece of a file. `dataset` is the XML of the file before its
+    /// first piece (all of the XML, for a file with a single piece), and `piece` the XML of this
+    /// piece.
+    ///
+    /// By default the headers are read from the piece with `read_headers`. Visitors that read
+    /// attributes of the dataset element (such as the `Origin` of `ImageData`) should override
+    /// this.
+    fn read_piece_headers<'a>(
+        spans: &Spans,
+        _dataset: &'a [u8],
+        piece: &'a [u8],
+    ) -> IResult<'a, Self> {
+        Self::read_headers(spans, piece)
+    }
 
     /// all the internal buffers that are stored in the visitor type
     /// are added to a vector here so that they can be sorted and read (in order by offset) from the
     /// appended binary section of the vtk file.
-    fn add_to_appended_reader<'a, 'b>(
-        &'a self,
-        buffer: &'b mut Vec<RefMut<'a, parse::OffsetBuffer>>,
-    );
+    fn add_to_appended_reader<'a>(&'a self, buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>);
 
     /// After all the binary data has been read, the `finish` function finalizes any last-minute
     /// changes before returning the full information of the type we have been parsing towards.
@@ -324,17 +461,50 @@ pub trait ParseArray {
 /// // a 10 x 30 x 10 sized domain
 /// let extent = "1 10 1 30 1 10";
 /// let parsed_extent = vtk::Spans3D::from_str(extent);
-/// assert_eq!(parsed_extent, vtk::Spans3D::new(10,30,10))
+/// assert_eq!(parsed_extent, Some(vtk::Spans3D::new(10,30,10)));
+///
+/// assert_eq!(vtk::Spans3D::from_str("1 10 1 30"), None);
 /// ```
-pub trait ParseSpan {
+pub trait ParseSpan: Sized {
     /// Takes in the `WholeExtent` or `Extent` attributes from the vtk file
-    /// and returns size information on the domain
-    fn from_str(extent: &str) -> Self;
+    /// and returns size information on the domain, or `None` if the string is malformed
+    fn from_str(extent: &str) -> Option<Self>;
+
+    /// Find the size information of the domain in the file, returning the rest of the file
+    /// after it.
+    ///
+    /// By default the `Extent` attribute of the first `<Piece>` (or the `WholeExtent` attribute
+    /// if the piece has no extent) is located and passed to `from_str`. Spans that are not
+    /// described by an extent (such as those of an `UnstructuredGrid`) should override this.
+    fn read_spans(xml_bytes: &[u8]) -> IResult<'_, Self> {
+        parse::find_piece_extent(xml_bytes)
+    }
+
+    /// The size information of cell centered (`<CellData>`) arrays in the domain. This is
+    /// passed to [`FromBuffer`] for these arrays.
+    ///
+    /// By default the spans are unchanged, which is correct for domains whose arrays do not
+    /// depend on the spans (such as unstructured data stored in a `Vec`).
+    fn cell_spans(&self) -> Self
+    where
+        Self: Clone,
+    {
+        self.clone()
+    }
 }
 
 /// Describes the encoding of a marker type
 pub trait Encode {
     fn is_binary() -> bool;
+
+    /// whether arrays of this encoding can be written to a file with a
+    /// [`Compressor`](crate::Compressor).
+    ///
+    /// Inline `binary` (base64) arrays of a compressed file are also expected to be compressed,
+    /// which is not supported, so this is `false` for [`Base64`](crate::Base64).
+    fn is_compressible() -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "derive")]
@@ -342,6 +512,7 @@ mod testgen {
     //use vtk::prelude::*;
     use crate as vtk;
 
+    #[allow(dead_code)]
     #[derive(vtk::DataArray, vtk::ParseArray)]
     #[vtk_parse(spans = "vtk::Spans3D")]
     #[vtk_write(encoding = "binary")]
@@ -350,49 +521,145 @@ mod testgen {
     }
 }
 
-/// A trait to abstract over [`f64`] and [`f32`] container data types
-pub trait Numeric: std::cmp::PartialEq<Self> + ryu::Float + Sized {
+/// A trait to abstract over the element types of containers: the floating point types
+/// [`f64`] and [`f32`] as well as the signed and unsigned integers from [`i8`] to [`u64`]
+pub trait Numeric: std::cmp::PartialEq<Self> + Copy + Sized {
     const SIZE: usize = std::mem::size_of::<Self>();
     const ZERO: Self;
-    const SMALL: Self;
 
-    fn extend_le_bytes(&self, byte_list: &mut Vec<u8>);
+    fn extend_bytes(&self, byte_list: &mut Vec<u8>, byte_order: ByteOrder);
 
-    fn write_le_bytes<W: Write>(&self, byte_list: &mut W) -> Result<(), std::io::Error>;
+    fn write_bytes<W: Write>(
+        &self,
+        byte_list: &mut W,
+        byte_order: ByteOrder,
+    ) -> Result<(), std::io::Error>;
 
     fn as_precision() -> crate::write_vtk::Precision;
-}
 
-impl Numeric for f32 {
-    const ZERO: Self = 0.0f32;
-    const SMALL: Self = 0.000001f32;
+    /// convert a value decoded from a floating point array of a file to this type, or `None`
+    /// if it is out of the range of this type. Integer types only hold whole numbers, floats
+    /// with a fractional part are `None` instead of being truncated
+    fn from_f64(value: f64) -> Option<Self>;
 
-    fn extend_le_bytes(&self, byte_list: &mut Vec<u8>) {
-        byte_list.extend(self.to_le_bytes())
-    }
+    /// convert a value decoded from a signed integer array of a file to this type, or `None`
+    /// if it is out of the range of this type
+    fn from_i64(value: i64) -> Option<Self>;
 
-    fn write_le_bytes<W: Write>(&self, byte_list: &mut W) -> Result<(), std::io::Error> {
-        byte_list.write_all(&self.to_le_bytes())
-    }
+    /// convert a value decoded from an unsigned integer array of a file to this type, or `None`
+    /// if it is out of the range of this type
+    fn from_u64(value: u64) -> Option<Self>;
 
-    fn as_precision() -> crate::write_vtk::Precision {
-        crate::write_vtk::Precision::Float32
-    }
+    /// append the ascii representation of this value to `buffer`
+    fn push_ascii(&self, buffer: &mut String);
 }
 
-impl Numeric for f64 {
-    const ZERO: Self = 0.0f64;
-    const SMALL: Self = 0.000001f64;
-
-    fn extend_le_bytes(&self, byte_list: &mut Vec<u8>) {
-        byte_list.extend(self.to_le_bytes())
-    }
-
-    fn write_le_bytes<W: Write>(&self, byte_list: &mut W) -> Result<(), std::io::Error> {
-        byte_list.write_all(&self.to_le_bytes())
-    }
+macro_rules! impl_numeric_float {
+    ($type:ty, $precision:ident) => {
+        impl Numeric for $type {
+            const ZERO: Self = 0.0;
+
+            fn extend_bytes(&self, byte_list: &mut Vec<u8>, byte_order: ByteOrder) {
+                match byte_order {
+                    ByteOrder::LittleEndian => byte_list.extend(self.to_le_bytes()),
+                    ByteOrder::BigEndian => byte_list.extend(self.to_be_bytes()),
+                }
+            }
+
+            fn write_bytes<W: Write>(
+                &self,
+                byte_list: &mut W,
+                byte_order: ByteOrder,
+            ) -> Result<(), std::io::Error> {
+                match byte_order {
+                    ByteOrder::LittleEndian => byte_list.write_all(&self.to_le_bytes()),
+                    ByteOrder::BigEndian => byte_list.write_all(&self.to_be_bytes()),
+                }
+            }
+
+            fn as_precision() -> crate::write_vtk::Precision {
+                crate::write_vtk::Precision::$precision
+            }
+
+            fn from_f64(value: f64) -> Option<Self> {
+                num_traits::cast(value)
+            }
+
+            fn from_i64(value: i64) -> Option<Self> {
+                num_traits::cast(value)
+            }
+
+            fn from_u64(value: u64) -> Option<Self> {
+                num_traits::cast(value)
+            }
+
+            fn push_ascii(&self, buffer: &mut String) {
+                let mut ryu_buffer = ryu::Buffer::new();
+                buffer.push_str(ryu_buffer.format(*self));
+            }
+        }
+    };
+}
 
-    fn as_precision() -> crate::write_vtk::Precision {
-        crate::write_vtk::Precision::Float64
-    }
+macro_rules! impl_numeric_integer {
+    ($type:ty, $precision:ident) => {
+        impl Numeric for $type {
+            const ZERO: Self = 0;
+
+            fn extend_bytes(&self, byte_list: &mut Vec<u8>, byte_order: ByteOrder) {
+                match byte_order {
+                    ByteOrder::LittleEndian => byte_list.extend(self.to_le_bytes()),
+                    ByteOrder::BigEndian => byte_list.extend(self.to_be_bytes()),
+                }
+            }
+
+            fn write_bytes<W: Write>(
+                &self,
+                byte_list: &mut W,
+                byte_order: ByteOrder,
+            ) -> Result<(), std::io::Error> {
+                match byte_order {
+                    ByteOrder::LittleEndian => byte_list.write_all(&self.to_le_bytes()),
+                    ByteOrder::BigEndian => byte_list.write_all(&self.to_be_bytes()),
+                }
+            }
+
+            fn as_precision() -> crate::write_vtk::Precision {
+                crate::write_vtk::Precision::$precision
+            }
+
+            fn from_f64(value: f64) -> Option<Self> {
+                // a cast would truncate the fractional part
+                if value.fract() == 0.0 {
+                    num_traits::cast(value)
+                } else {
+                    None
+                }
+            }
+
+            fn from_i64(value: i64) -> Option<Self> {
+                num_traits::cast(value)
+            }
+
+            fn from_u64(value: u64) -> Option<Self> {
+                num_traits::cast(value)
+            }
+
+            fn push_ascii(&self, buffer: &mut String) {
+                buffer.push_str(&self.to_string());
+            }
+        }
+    };
 }
+
+impl_numeric_float!(f32, Float32);
+impl_numeric_float!(f64, Float64);
+
+impl_numeric_integer!(i8, Int8);
+impl_numeric_integer!(i16, Int16);
+impl_numeric_integer!(i32, Int32);
+impl_numeric_integer!(i64, Int64);
+impl_numeric_integer!(u8, UInt8);
+impl_numeric_integer!(u16, UInt16);
+impl_numeric_integer!(u32, UInt32);
+impl_numeric_integer!(u64, UInt64);
@@ -1,8 +1,56 @@
-pub(crate) fn bytes_to_float(bytes: &[u8]) -> f64 {
-    let mut arr = [0; 8];
+use crate::prelude::*;
+
+macro_rules! bytes_to {
+    ($(#[$doc:meta])* $name:ident, $output:ty) => {
+        $(#[$doc])*
+        pub(crate) fn $name(bytes: &[u8], precision: Precision, byte_order: ByteOrder) -> $output {
+            match precision {
+                Precision::Float64 => f64::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::Float32 => f32::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::Int8 => i8::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::Int16 => i16::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::Int32 => i32::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::Int64 => i64::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::UInt8 => bytes[0] as $output,
+                Precision::UInt16 => u16::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::UInt32 => u32::from_le_bytes(to_array(bytes, byte_order)) as $output,
+                Precision::UInt64 => u64::from_le_bytes(to_array(bytes, byte_order)) as $output,
+            }
+        }
+    };
+}
+
+bytes_to!(
+    /// convert the bytes (in `byte_order`) of a single element of the given type to a float
+    bytes_to_float,
+    f64
+);
+
+bytes_to!(
+    /// convert the bytes (in `byte_order`) of a single element of the given type to a signed
+    /// integer, without rounding through a float
+    bytes_to_int,
+    i64
+);
+
+bytes_to!(
+    /// convert the bytes (in `byte_order`) of a single element of the given type to an unsigned
+    /// integer, such as the length header of an array
+    bytes_to_uint,
+    u64
+);
+
+/// copy a slice into a fixed size array of little endian bytes
+fn to_array<const N: usize>(bytes: &[u8], byte_order: ByteOrder) -> [u8; N] {
+    let mut arr = [0; N];
     bytes
-        .into_iter()
+        .iter()
         .enumerate()
         .for_each(|(idx, value)| arr[idx] = *value);
-    f64::from_le_bytes(arr)
+
+    if byte_order == ByteOrder::BigEndian {
+        arr.reverse();
+    }
+
+    arr
 }
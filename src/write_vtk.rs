@@ -7,9 +7,14 @@ use xml::name::Name;
 use xml::namespace::Namespace;
 use xml::writer::{EventWriter, XmlEvent};
 
-const STARTING_OFFSET: i64 = 0;
+use crate::compression::{self, Compressor};
+use crate::FieldData;
 
-/// Write a given vtk file to a `Writer`
+/// The number of bytes of the (`UInt64`) header before every uncompressed appended array
+pub(crate) const ARRAY_HEADER_BYTES: usize = std::mem::size_of::<u64>();
+
+/// Write a given vtk file to a `Writer`, with the default [`WriteOptions`]: little endian and
+/// uncompressed
 pub fn write_vtk<W, D, DOMAIN, EncMesh, EncArray>(
     writer: W,
     data: VtkData<DOMAIN, D>,
@@ -21,8 +26,335 @@ where
     EncArray: Encode,
     EncMesh: Encode,
 {
+    write_vtk_with_options(writer, data, WriteOptions::default())
+}
+
+/// Write a given vtk file to a `Writer`, with the byte order and compressor of `options`.
+///
+/// ```no_run
+/// # fn write<D: vtk::DataArray<vtk::Binary>>(
+/// #     data: vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, D>,
+/// # ) -> Result<(), vtk::Error> {
+/// let file = std::fs::File::create("./output.vtr")?;
+/// let options = vtk::WriteOptions::new().with_byte_order(vtk::ByteOrder::BigEndian);
+/// vtk::write_vtk_with_options(file, data, options)
+/// # }
+/// ```
+pub fn write_vtk_with_options<W, D, DOMAIN, EncMesh, EncArray>(
+    writer: W,
+    data: VtkData<DOMAIN, D>,
+    options: WriteOptions,
+) -> Result<(), Error>
+where
+    W: Write,
+    D: DataArray<EncArray>,
+    DOMAIN: Domain<EncMesh>,
+    EncArray: Encode,
+    EncMesh: Encode,
+{
+    write_vtk_inner(writer, std::slice::from_ref(&data), options)
+}
+
+/// Write several pieces to a single file, such as the blocks of a domain that is decomposed
+/// within a single process, with the byte order and compressor of `options`.
+///
+/// The `WholeExtent` of the file is the smallest extent that contains the extent of every
+/// piece. Field data describes the whole file, so every piece must have the same field data.
+/// The pieces are read back with [`read_vtk_pieces`](crate::read_vtk_pieces).
+pub fn write_vtk_pieces<W, D, DOMAIN, EncMesh, EncArray>(
+    writer: W,
+    pieces: Vec<VtkData<DOMAIN, D>>,
+    options: WriteOptions,
+) -> Result<(), Error>
+where
+    W: Write,
+    D: DataArray<EncArray>,
+    DOMAIN: Domain<EncMesh>,
+    EncArray: Encode,
+    EncMesh: Encode,
+{
+    write_vtk_inner(writer, &pieces, options)
+}
+
+/// How the binary (base64 or appended) values of a file are written by
+/// [`write_vtk_with_options`] and [`write_vtk_pieces`]
+///
+/// ```
+/// let options = vtk::WriteOptions::new().with_byte_order(vtk::ByteOrder::BigEndian);
+/// assert_eq!(options.compressor, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WriteOptions {
+    /// the order of the bytes of every binary value. Little endian files should be preferred
+    /// unless the files are read by programs that only support big endian data
+    pub byte_order: ByteOrder,
+    /// the compressor of every appended array. Compressors are enabled with cargo features, such
+    /// as `zlib`. Inline base64 arrays cannot be compressed, and ascii arrays are written as usual. A file without appended
+    /// arrays does not name a compressor
+    pub compressor: Option<Compressor>,
+}
+
+impl WriteOptions {
+    /// little endian and uncompressed, as [`write_vtk`] writes files
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    pub fn with_compressor(mut self, compressor: Compressor) -> Self {
+        self.compressor = Some(compressor);
+        self
+    }
+}
+
+fn write_vtk_inner<W, D, DOMAIN, EncMesh, EncArray>(
+    writer: W,
+    pieces: &[VtkData<DOMAIN, D>],
+    options: WriteOptions,
+) -> Result<(), Error>
+where
+    W: Write,
+    D: DataArray<EncArray>,
+    DOMAIN: Domain<EncMesh>,
+    EncArray: Encode,
+    EncMesh: Encode,
+{
+    let WriteOptions {
+        byte_order,
+        compressor,
+    } = options;
+
+    // the inline arrays of a compressed file are expected to be compressed as well
+    if compressor.is_some() && !(EncArray::is_compressible() && EncMesh::is_compressible()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "base64 arrays cannot be written to a compressed file",
+        )
+        .into());
+    }
+
+    let first = pieces.first().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "a file must contain at least one piece",
+        )
+    })?;
+
     let mut writer = EventWriter::new(writer);
+    let has_appended = EncMesh::is_binary() || EncArray::is_binary();
+
+    // the sizes of the mesh and data arrays of every piece, which are appended one piece
+    // after the other
+    let sizes: Vec<(Vec<usize>, Vec<usize>)> = pieces
+        .iter()
+        .map(|piece| {
+            (
+                piece.domain.mesh_appended_sizes(),
+                piece.data.appended_sizes(),
+            )
+        })
+        .collect();
+
+    // compressed arrays have to be compressed before any headers are written, since
+    // their offsets depend on the compressed sizes
+    let compressed = match compressor {
+        Some(compressor) if has_appended => {
+            let mut arrays = Vec::new();
+            for (piece, (mesh_sizes, array_sizes)) in pieces.iter().zip(&sizes) {
+                arrays.extend(compress_appended(
+                    piece,
+                    mesh_sizes,
+                    array_sizes,
+                    compressor,
+                    byte_order,
+                )?);
+            }
+            Some(arrays)
+        }
+        _ => None,
+    };
+
+    // every array starts with a header of its length, compressed arrays already include it
+    let offsets = match &compressed {
+        Some(arrays) => appended_offsets(arrays.iter().map(Vec::len)),
+        None => appended_offsets(
+            sizes
+                .iter()
+                .flat_map(|(mesh_sizes, array_sizes)| mesh_sizes.iter().chain(array_sizes))
+                .map(|size| size + ARRAY_HEADER_BYTES),
+        ),
+    };
+
+    // the field data describes the whole file, so it is only written if no piece has field data
+    // of its own
+    if pieces
+        .iter()
+        .any(|piece| piece.field_data != first.field_data)
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the pieces of a file must all have the same field data",
+        )
+        .into());
+    }
+
+    let whole_extent = match pieces {
+        [piece] => piece.domain.whole_span_string(),
+        _ => union_extent(
+            pieces
+                .iter()
+                .flat_map(|piece| [piece.domain.span_string(), piece.domain.whole_span_string()]),
+        )
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "the extents of the pieces of a file must have the same number of values",
+            )
+        })?,
+    };
+
+    write_file_start(
+        &mut writer,
+        &first.domain,
+        &whole_extent,
+        &first.field_data,
+        // a reader would expect the inline arrays of a file with a compressor to be compressed
+        // as well, so it is only named if there are appended arrays to compress
+        compressed.as_ref().and(compressor),
+        byte_order,
+    )?;
+
+    let mut remaining_offsets = offsets.as_slice();
+
+    for (piece, (mesh_sizes, array_sizes)) in pieces.iter().zip(&sizes) {
+        let (piece_offsets, rest) =
+            remaining_offsets.split_at(mesh_sizes.len() + array_sizes.len());
+        let (mesh_offsets, array_offsets) = piece_offsets.split_at(mesh_sizes.len());
+        remaining_offsets = rest;
+
+        write_piece_start(&mut writer, &piece.domain, mesh_offsets, byte_order)?;
+
+        writer.write(XmlEvent::StartElement {
+            name: Name::from("PointData"),
+            attributes: vec![].into(),
+            namespace: Cow::Owned(Namespace::empty()),
+        })?;
+
+        piece
+            .data
+            .write_array_header(&mut writer, array_offsets, byte_order)?;
+
+        writer.write(XmlEvent::EndElement {
+            name: Some(Name::from("PointData")),
+        })?;
+
+        if piece.data.has_cell_arrays() {
+            open_element(&mut writer, "CellData")?;
+            piece
+                .data
+                .write_cell_array_header(&mut writer, array_offsets, byte_order)?;
+            close_element(&mut writer, "CellData")?;
+        }
+
+        close_element(&mut writer, "Piece")?;
+    }
+
+    close_element(&mut writer, first.domain.grid_type().to_str())?;
+
+    if let Some(arrays) = compressed {
+        appended_binary_header_start(&mut writer)?;
+
+        for array in arrays {
+            writer.inner_mut().write_all(&array)?;
+        }
+
+        appended_binary_header_end(&mut writer)?;
+    }
+    // if we are doing _any_ sort of appending of data
+    else if has_appended {
+        appended_binary_header_start(&mut writer)?;
+
+        let sizes = sizes
+            .into_iter()
+            .flat_map(|(mesh_sizes, array_sizes)| mesh_sizes.into_iter().chain(array_sizes))
+            .collect();
+        let mut appended =
+            EventWriter::new(ArrayHeaders::new(writer.inner_mut(), sizes, byte_order));
+
+        for piece in pieces {
+            // implementations will do nothing if they are not responsible for writing any
+            // binary information
+            piece
+                .domain
+                .write_mesh_appended(&mut appended, byte_order)?;
+            // same here
+            piece.data.write_array_appended(&mut appended, byte_order)?;
+        }
+
+        appended.into_inner().finish()?;
+
+        appended_binary_header_end(&mut writer)?;
+    }
+
+    writer.write(XmlEvent::EndElement {
+        name: Some(Name::from("VTKFile")),
+    })?;
+
+    Ok(())
+}
+
+/// the smallest extent that contains every one of `extents`, which alternate between the
+/// start and the end index of each direction (such as `"1 10 1 20 1 1"`)
+fn union_extent(extents: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut union: Option<Vec<i64>> = None;
+
+    for extent in extents {
+        let values = extent
+            .split_ascii_whitespace()
+            .map(|value| value.parse().ok())
+            .collect::<Option<Vec<i64>>>()?;
+
+        union = match union {
+            None => Some(values),
+            Some(union) if union.len() == values.len() => Some(
+                union
+                    .iter()
+                    .zip(&values)
+                    .enumerate()
+                    .map(|(idx, (a, b))| if idx % 2 == 0 { *a.min(b) } else { *a.max(b) })
+                    .collect(),
+            ),
+            Some(_) => return None,
+        };
+    }
+
+    union.map(|union| {
+        union
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
 
+/// write the start of a file up to its first piece: the `<VTKFile>` and dataset elements (with
+/// the `WholeExtent` of the file, for datasets with an extent), and the field data of the dataset
+pub(crate) fn write_file_start<W, DOMAIN, EncMesh>(
+    writer: &mut EventWriter<W>,
+    domain: &DOMAIN,
+    whole_extent: &str,
+    field_data: &FieldData,
+    compressor: Option<Compressor>,
+    byte_order: ByteOrder,
+) -> Result<(), Error>
+where
+    W: Write,
+    DOMAIN: Domain<EncMesh>,
+{
     let version = xml::common::XmlVersion::Version10;
     writer.write(XmlEvent::StartDocument {
         version,
@@ -30,98 +362,233 @@ where
         standalone: None,
     })?;
 
+    let grid_type = domain.grid_type();
+
     writer.write(XmlEvent::StartElement {
         name: Name::from("VTKFile"),
         attributes: vec![
-            make_att("type", "RectilinearGrid"),
+            make_att("type", grid_type.to_str()),
             make_att("version", "1.0"),
-            make_att("byte_order", "LittleEndian"),
+            make_att("byte_order", byte_order.to_str()),
             make_att("header_type", "UInt64"),
         ]
+        .into_iter()
+        .chain(compressor.map(|compressor| make_att("compressor", compressor.to_str())))
+        .collect::<Vec<_>>()
         .into(),
         namespace: Cow::Owned(Namespace::empty()),
     })?;
 
-    // output the spans
-    let span_str = data.domain.span_string();
+    let grid_attributes = domain.grid_attributes();
 
-    writer.write(XmlEvent::StartElement {
-        name: Name::from("RectilinearGrid"),
-        attributes: vec![make_att("WholeExtent", &span_str)].into(),
-        namespace: Cow::Owned(Namespace::empty()),
-    })?;
+    let mut attributes = Vec::new();
+
+    // unstructured datasets do not have any extents, their sizes are
+    // described in the piece attributes instead
+    if grid_type.has_extent() {
+        attributes.push(make_att("WholeExtent", whole_extent));
+    }
+
+    attributes.extend(
+        grid_attributes
+            .iter()
+            .map(|(name, value)| make_att(name, value)),
+    );
 
     writer.write(XmlEvent::StartElement {
-        name: Name::from("Piece"),
-        attributes: vec![make_att("Extent", &span_str)].into(),
+        name: Name::from(grid_type.to_str()),
+        attributes: attributes.into(),
         namespace: Cow::Owned(Namespace::empty()),
     })?;
 
+    // dataset-wide metadata must come before any pieces
+    field_data.write(writer)?;
+
+    Ok(())
+}
+
+/// open the `<Piece>` element of `domain` and write the geometry of its mesh, which is followed
+/// by the data arrays of the piece
+pub(crate) fn write_piece_start<W, DOMAIN, EncMesh>(
+    writer: &mut EventWriter<W>,
+    domain: &DOMAIN,
+    mesh_offsets: &[i64],
+    byte_order: ByteOrder,
+) -> Result<(), Error>
+where
+    W: Write,
+    DOMAIN: Domain<EncMesh>,
+{
+    let span_str = domain.span_string();
+    let piece_attributes = domain.piece_attributes();
+
+    let mut piece = Vec::new();
+
+    if domain.grid_type().has_extent() {
+        piece.push(make_att("Extent", &span_str));
+    }
+
+    piece.extend(
+        piece_attributes
+            .iter()
+            .map(|(name, value)| make_att(name, value)),
+    );
+
     writer.write(XmlEvent::StartElement {
-        name: Name::from("Coordinates"),
-        attributes: vec![].into(),
+        name: Name::from("Piece"),
+        attributes: piece.into(),
         namespace: Cow::Owned(Namespace::empty()),
     })?;
 
     // write the mesh information out
-    data.domain.write_mesh_header(&mut writer)?;
-
-    // either write the loation of all the verticies inline
-    // here or write only the headers w/ offsets and write the data as binary later
-    let starting_offset = if EncMesh::is_binary() {
-        data.domain.mesh_bytes() as i64
-    } else {
-        STARTING_OFFSET
-    };
+    domain.write_mesh_header(writer, mesh_offsets, byte_order)?;
 
-    writer.write(XmlEvent::EndElement {
-        name: Some(Name::from("Coordinates")),
-    })?;
+    Ok(())
+}
 
-    writer.write(XmlEvent::StartElement {
-        name: Name::from("PointData"),
-        attributes: vec![].into(),
-        namespace: Cow::Owned(Namespace::empty()),
-    })?;
+/// the offset of every array in the appended section from the number of bytes each
+/// array occupies in the section
+pub(crate) fn appended_offsets(sizes: impl Iterator<Item = usize>) -> Vec<i64> {
+    sizes
+        .scan(0, |offset, size| {
+            let current = *offset;
+            *offset += size as i64;
+            Some(current)
+        })
+        .collect()
+}
 
-    data.data.write_array_header(&mut writer, starting_offset)?;
+/// Writes the header with the number of bytes of each appended array directly before the
+/// first byte of the array, so that the arrays can be written back to back without knowing
+/// where one ends and the next begins.
+pub(crate) struct ArrayHeaders<W> {
+    inner: W,
+    /// the number of bytes of the arrays that have not been started yet
+    sizes: std::vec::IntoIter<usize>,
+    /// the number of bytes left in the current array
+    remaining: usize,
+    byte_order: ByteOrder,
+}
 
-    writer.write(XmlEvent::EndElement {
-        name: Some(Name::from("PointData")),
-    })?;
+impl<W: Write> ArrayHeaders<W> {
+    pub(crate) fn new(inner: W, sizes: Vec<usize>, byte_order: ByteOrder) -> Self {
+        Self {
+            inner,
+            sizes: sizes.into_iter(),
+            remaining: 0,
+            byte_order,
+        }
+    }
 
-    writer.write(XmlEvent::EndElement {
-        name: Some(Name::from("Piece")),
-    })?;
-    writer.write(XmlEvent::EndElement {
-        name: Some(Name::from("RectilinearGrid")),
-    })?;
+    /// write the header of the next array (and of any empty arrays before it)
+    fn start_array(&mut self) -> std::io::Result<()> {
+        while self.remaining == 0 {
+            let size = self.sizes.next().ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "more bytes were appended than described by the sizes of the arrays",
+                )
+            })?;
+
+            self.write_header(size)?;
+            self.remaining = size;
+        }
 
-    // if we are doing _any_ sort of appending of data
-    if EncMesh::is_binary() || EncArray::is_binary() {
-        appended_binary_header_start(&mut writer)?;
+        Ok(())
+    }
+
+    fn write_header(&mut self, size: usize) -> std::io::Result<()> {
+        (size as u64).write_bytes(&mut self.inner, self.byte_order)
+    }
 
-        // for some reason paraview expects the first byte that is not '_' to
-        // be garbage and it is skipped over. Previously we just used an initial offset=-8
-        // to fix this issue, but it turns out that has unpredictable behavior when
-        // writing appended binary coordinate arrays
+    /// write the headers of any empty arrays at the end of the section and check that every
+    /// array was written completely, returning the inner writer
+    pub(crate) fn finish(mut self) -> std::io::Result<W> {
+        let sizes = std::mem::take(&mut self.sizes);
 
-        [100f64].as_ref().write_binary(&mut writer, false)?;
+        for size in sizes {
+            if size != 0 {
+                self.remaining += size;
+                break;
+            }
 
-        // implementations will do nothing if they are not responsible for writing any binary
-        // information
-        data.domain.write_mesh_appended(&mut writer)?;
-        // same here
-        data.data.write_array_appended(&mut writer)?;
+            self.write_header(size)?;
+        }
 
-        appended_binary_header_end(&mut writer)?;
+        if self.remaining != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "fewer bytes were appended than described by the sizes of the arrays",
+            ));
+        }
+
+        Ok(self.inner)
     }
+}
 
-    writer.write(XmlEvent::EndElement {
-        name: Some(Name::from("VTKFile")),
-    })?;
+impl<W: Write> Write for ArrayHeaders<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
 
-    Ok(())
+        self.start_array()?;
+
+        let len = buf.len().min(self.remaining);
+        self.inner.write_all(&buf[..len])?;
+        self.remaining -= len;
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// write the appended arrays of the mesh and the data to memory and compress each of them
+/// separately, in the order they are written to the file
+fn compress_appended<D, DOMAIN, EncMesh, EncArray>(
+    data: &VtkData<DOMAIN, D>,
+    mesh_sizes: &[usize],
+    array_sizes: &[usize],
+    compressor: Compressor,
+    byte_order: ByteOrder,
+) -> Result<Vec<Vec<u8>>, Error>
+where
+    D: DataArray<EncArray>,
+    DOMAIN: Domain<EncMesh>,
+{
+    let mut raw = EventWriter::new(Vec::new());
+    data.domain.write_mesh_appended(&mut raw, byte_order)?;
+    data.data.write_array_appended(&mut raw, byte_order)?;
+    let raw = raw.into_inner();
+
+    let sizes = mesh_sizes.iter().chain(array_sizes.iter());
+
+    // a `Domain` or `DataArray` whose sizes do not match the bytes it writes would otherwise
+    // split the arrays at the wrong bytes
+    if raw.len() != sizes.clone().sum::<usize>() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the sizes of the appended arrays do not match the number of bytes written",
+        )
+        .into());
+    }
+
+    let mut start = 0;
+    let mut arrays = Vec::with_capacity(mesh_sizes.len() + array_sizes.len());
+
+    for size in sizes {
+        arrays.push(compression::compress(
+            &raw[start..start + size],
+            compressor,
+            byte_order,
+        )?);
+        start += size;
+    }
+
+    Ok(arrays)
 }
 
 pub(crate) fn appended_binary_header_start<W: Write>(
@@ -154,16 +621,140 @@ impl Encoding {
     }
 }
 
+/// The type of VTK dataset contained in a file. This is both the `type` attribute of
+/// the `<VTKFile>` element and the name of the element directly below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridType {
+    /// A grid with axis-aligned but arbitrarily spaced points (`.vtr`)
+    RectilinearGrid,
+    /// A grid with uniformly spaced points (`.vti`)
+    ImageData,
+    /// A curvilinear grid where the location of every point is given explicitly (`.vts`)
+    StructuredGrid,
+    /// A grid of arbitrary cells with explicit connectivity (`.vtu`)
+    UnstructuredGrid,
+    /// Points, lines and surfaces with explicit connectivity (`.vtp`)
+    PolyData,
+}
+
+impl GridType {
+    pub(crate) fn to_str(self) -> &'static str {
+        match self {
+            Self::RectilinearGrid => "RectilinearGrid",
+            Self::ImageData => "ImageData",
+            Self::StructuredGrid => "StructuredGrid",
+            Self::UnstructuredGrid => "UnstructuredGrid",
+            Self::PolyData => "PolyData",
+        }
+    }
+
+    /// parse the `type` attribute of a `<VTKFile>`
+    pub(crate) fn from_bytes(name: &[u8]) -> Option<Self> {
+        match name {
+            b"RectilinearGrid" => Some(Self::RectilinearGrid),
+            b"ImageData" => Some(Self::ImageData),
+            b"StructuredGrid" => Some(Self::StructuredGrid),
+            b"UnstructuredGrid" => Some(Self::UnstructuredGrid),
+            b"PolyData" => Some(Self::PolyData),
+            _ => None,
+        }
+    }
+
+    /// if the dataset is described by `WholeExtent` / `Extent` attributes
+    pub(crate) fn has_extent(self) -> bool {
+        !matches!(self, Self::UnstructuredGrid | Self::PolyData)
+    }
+}
+
+/// The order of the bytes of every binary (base64 or appended) value in a file, written to the
+/// `byte_order` attribute of the `<VTKFile>` element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    #[default]
+    LittleEndian,
+    BigEndian,
+}
+
+impl ByteOrder {
+    pub(crate) fn to_str(self) -> &'static str {
+        match self {
+            Self::LittleEndian => "LittleEndian",
+            Self::BigEndian => "BigEndian",
+        }
+    }
+
+    /// parse the `byte_order` attribute of a `<VTKFile>`
+    pub(crate) fn from_bytes(name: &[u8]) -> Option<Self> {
+        match name {
+            b"LittleEndian" => Some(Self::LittleEndian),
+            b"BigEndian" => Some(Self::BigEndian),
+            _ => None,
+        }
+    }
+}
+
+/// The type of each element of a data array, written to the `type` attribute of a `DataArray`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
 pub enum Precision {
     Float64,
     Float32,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
 }
 
 impl Precision {
-    fn to_str(&self) -> &'static str {
-        match &self {
+    pub(crate) fn to_str(self) -> &'static str {
+        match self {
             Self::Float64 => "Float64",
             Self::Float32 => "Float32",
+            Self::Int8 => "Int8",
+            Self::Int16 => "Int16",
+            Self::Int32 => "Int32",
+            Self::Int64 => "Int64",
+            Self::UInt8 => "UInt8",
+            Self::UInt16 => "UInt16",
+            Self::UInt32 => "UInt32",
+            Self::UInt64 => "UInt64",
+        }
+    }
+
+    /// parse the `type` attribute of a `DataArray`
+    pub(crate) fn from_bytes(type_name: &[u8]) -> Option<Self> {
+        let precision = match type_name {
+            b"Float64" => Self::Float64,
+            b"Float32" => Self::Float32,
+            b"Int8" => Self::Int8,
+            b"Int16" => Self::Int16,
+            b"Int32" => Self::Int32,
+            b"Int64" => Self::Int64,
+            b"UInt8" => Self::UInt8,
+            b"UInt16" => Self::UInt16,
+            b"UInt32" => Self::UInt32,
+            b"UInt64" => Self::UInt64,
+            _ => return None,
+        };
+
+        Some(precision)
+    }
+
+    /// if the elements are floating point numbers rather than integers
+    pub(crate) fn is_float(self) -> bool {
+        matches!(self, Self::Float64 | Self::Float32)
+    }
+
+    /// the number of bytes that a single element of this type occupies in binary
+    pub(crate) fn size_of(self) -> usize {
+        match self {
+            Self::Float64 | Self::Int64 | Self::UInt64 => 8,
+            Self::Float32 | Self::Int32 | Self::UInt32 => 4,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int8 | Self::UInt8 => 1,
         }
     }
 }
@@ -190,6 +781,19 @@ pub fn write_inline_array_header<W: Write>(
     Ok(())
 }
 
+/// base64 encode the bytes of an inline array.
+///
+/// Like vtk, the (`UInt64`) length header and the bytes of the array are encoded separately, so
+/// the encoded header is always padded
+pub(crate) fn encode_base64_array(bytes: &[u8], byte_order: ByteOrder) -> String {
+    let mut header = Vec::with_capacity(ARRAY_HEADER_BYTES);
+    (bytes.len() as u64).extend_bytes(&mut header, byte_order);
+
+    let mut data = base64::encode(header);
+    data.push_str(&base64::encode(bytes));
+    data
+}
+
 pub fn close_inline_array_header<W: Write>(writer: &mut EventWriter<W>) -> Result<(), Error> {
     writer.write(XmlEvent::EndElement {
         name: Some(Name::from("DataArray")),
@@ -205,13 +809,14 @@ pub fn write_inline_dataarray<W: Write, A: Array>(
     data: &A,
     name: &str,
     encoding: Encoding,
+    byte_order: ByteOrder,
 ) -> Result<(), Error> {
     match encoding {
         Encoding::Ascii => {
             data.write_ascii(writer, name)?;
         }
         Encoding::Base64 => {
-            data.write_base64(writer, name)?;
+            data.write_base64(writer, name, byte_order)?;
         }
     };
 
@@ -251,7 +856,49 @@ pub fn write_appended_dataarray_header<W: Write>(
     Ok(())
 }
 
-fn make_att<'a>(name: &'static str, value: &'a str) -> Attribute<'a> {
+/// write the opening tag of an element that has no attributes
+pub(crate) fn open_element<W: Write>(writer: &mut EventWriter<W>, name: &str) -> Result<(), Error> {
+    writer.write(XmlEvent::StartElement {
+        name: Name::from(name),
+        attributes: vec![].into(),
+        namespace: Cow::Owned(Namespace::empty()),
+    })?;
+
+    Ok(())
+}
+
+/// write the opening tag of an element with `attributes`
+pub(crate) fn start_element<W: Write>(
+    writer: &mut EventWriter<W>,
+    name: &str,
+    attributes: &[(&'static str, &str)],
+) -> Result<(), Error> {
+    writer.write(XmlEvent::StartElement {
+        name: Name::from(name),
+        attributes: attributes
+            .iter()
+            .map(|(name, value)| make_att(name, value))
+            .collect::<Vec<_>>()
+            .into(),
+        namespace: Cow::Owned(Namespace::empty()),
+    })?;
+
+    Ok(())
+}
+
+/// write the closing tag of an element
+pub(crate) fn close_element<W: Write>(
+    writer: &mut EventWriter<W>,
+    name: &str,
+) -> Result<(), Error> {
+    writer.write(XmlEvent::EndElement {
+        name: Some(Name::from(name)),
+    })?;
+
+    Ok(())
+}
+
+pub(crate) fn make_att<'a>(name: &'static str, value: &'a str) -> Attribute<'a> {
     let name = Name::from(name);
     Attribute::new(name, value)
 }
@@ -0,0 +1,176 @@
+"""Generate the `static/*.vtr` files read by `tests/external_files.rs`.
+
+The files are not written by VTK, ParaView, PyVista or meshio. They follow the
+layout of the files that `vtkXMLRectilinearGridWriter` writes, as described in
+the VTK file format documentation, and should be replaced with files written by
+VTK (or ParaView / PyVista) where one is available.
+
+Run from the root of the repository: `python3 static/generate_vtk_files.py`
+"""
+import struct, base64, zlib, math
+
+NX, NY, NZ = 4, 3, 2
+x = [0.0, 0.5, 1.0, 1.5]
+y = [-1.0, 0.0, 1.0]
+z = [0.0, 2.0]
+n = NX*NY*NZ
+rho = [i*0.25 for i in range(n)]
+vel = []
+for i in range(n):
+    vel += [float(i), -float(i), 0.5*i]
+ncell = (NX-1)*(NY-1)*(NZ-1)
+rank = list(range(ncell))
+
+def fmt(v):
+    # vtk prints with %g like precision
+    s = repr(float(v))
+    if s.endswith('.0'): s = s[:-2]
+    if s == '-0': s = '0'
+    return s
+
+vmag = [math.sqrt(vel[3*i]**2+vel[3*i+1]**2+vel[3*i+2]**2) for i in range(n)]
+vmag32 = [struct.unpack('<f', struct.pack('<f', v))[0] for v in vmag]
+
+arrays_point = [
+    dict(type="Float64", name="rho", comps=1, values=rho, code='d', rmin=min(rho), rmax=max(rho)),
+    dict(type="Float32", name="velocity", comps=3, values=vel, code='f', rmin=min(vmag), rmax=max(vmag)),
+]
+arrays_cell = [dict(type="Int32", name="rank", comps=1, values=rank, code='i', rmin=0, rmax=ncell-1)]
+coords = [
+    dict(type="Float64", name="Array 0x55d5c8a0e3c0", comps=1, values=x, code='d', rmin=min(x), rmax=max(x)),
+    dict(type="Float64", name="Array 0x55d5c8a0e5f0", comps=1, values=y, code='d', rmin=min(y), rmax=max(y)),
+    dict(type="Float64", name="Array 0x55d5c8a0e820", comps=1, values=z, code='d', rmin=min(z), rmax=max(z)),
+]
+
+E = '<'
+BO = 'LittleEndian'
+
+def raw(a):
+    return struct.pack(E + '%d%s' % (len(a['values']), a['code']), *a['values'])
+
+def attrs(a, fmt_name, extra=''):
+    s = 'type="%s" Name="%s"' % (a['type'], a['name'])
+    if a['comps'] > 1:
+        s += ' NumberOfComponents="%d"' % a['comps']
+    s += ' format="%s" RangeMin="%s" RangeMax="%s"' % (fmt_name, fmt(a['rmin']), fmt(a['rmax']))
+    return s + extra
+
+def header(version, header_type, compressor):
+    s = '<?xml version="1.0"?>\n<VTKFile type="RectilinearGrid" version="%s" byte_order="%s"' % (version, BO)
+    if header_type: s += ' header_type="%s"' % header_type
+    if compressor: s += ' compressor="%s"' % compressor
+    return s + '>\n'
+
+def layout(version, header_type, compressor, array_text, field_data=''):
+    out = header(version, header_type, compressor)
+    out += '  <RectilinearGrid WholeExtent="0 3 0 2 0 1">\n'
+    out += field_data
+    out += '    <Piece Extent="0 3 0 2 0 1">\n'
+    out += '      <PointData Scalars="rho" Vectors="velocity">\n'
+    for a in arrays_point: out += array_text(a)
+    out += '      </PointData>\n'
+    out += '      <CellData Scalars="rank">\n'
+    for a in arrays_cell: out += array_text(a)
+    out += '      </CellData>\n'
+    out += '      <Coordinates>\n'
+    for a in coords: out += array_text(a)
+    out += '      </Coordinates>\n'
+    out += '    </Piece>\n'
+    out += '  </RectilinearGrid>\n'
+    return out
+
+order = arrays_point + arrays_cell + coords
+
+def appended(path, version, header_type, compressor, encode, encoding='raw'):
+    blobs = [encode(raw(a)) for a in order]
+    offsets = {}
+    off = 0
+    for a, b in zip(order, blobs):
+        offsets[a['name']] = off
+        off += len(b)
+    text = lambda a: '        <DataArray %s/>\n' % attrs(a, 'appended', ' offset="%d" ' % offsets[a['name']]).rstrip() if False else \
+        '        <DataArray %s offset="%d" />\n' % (attrs(a, 'appended'), offsets[a['name']])
+    out = layout(version, header_type, compressor, text).encode()
+    out += b'  <AppendedData encoding="' + encoding.encode() + b'">\n   _' + b''.join(blobs) + b'\n  </AppendedData>\n</VTKFile>\n'
+    open(path, 'wb').write(out)
+
+def raw_u32(b): return struct.pack('<I', len(b)) + b
+
+def zlib_header(b, code='I', block=32768):
+    blocks = [zlib.compress(b[i:i+block]) for i in range(0, len(b), block)]
+    h = [len(blocks), block, len(b) % block] + [len(c) for c in blocks]
+    return struct.pack('<%d%s' % (len(h), code), *h), b''.join(blocks)
+
+def zlib_u32(b):
+    return b''.join(zlib_header(b))
+
+# base64 arrays encode their header and their (compressed) values separately
+def b64_u64(b):
+    return base64.b64encode(struct.pack('<Q', len(b))) + base64.b64encode(b)
+
+def b64_zlib_u32(b):
+    h, blocks = zlib_header(b)
+    return base64.b64encode(h) + base64.b64encode(blocks)
+
+appended('static/vtk_appended_raw.vtr', '0.1', None, None, raw_u32)
+appended('static/vtk_appended_raw_uint64.vtr', '1.0', 'UInt64', None, lambda b: struct.pack('<Q', len(b)) + b)
+appended('static/vtk_appended_zlib.vtr', '1.0', 'UInt32', 'vtkZLibDataCompressor', zlib_u32)
+
+# vtk's default: an appended section with `encoding="base64"`, whose offsets index into the text
+appended('static/vtk_appended_base64.vtr', '1.0', 'UInt64', None, b64_u64, 'base64')
+appended('static/vtk_appended_base64_zlib.vtr', '1.0', 'UInt32', 'vtkZLibDataCompressor', b64_zlib_u32, 'base64')
+
+# inline base64 with UInt64 headers, header and data encoded separately
+def b64_text(a):
+    data = raw(a)
+    enc = base64.b64encode(struct.pack(E + 'Q', len(data))).decode() + base64.b64encode(data).decode()
+    return '        <DataArray %s>\n          %s\n        </DataArray>\n' % (attrs(a, 'binary'), enc)
+open('static/vtk_binary.vtr', 'w').write(layout('1.0', 'UInt64', None, b64_text) + '</VTKFile>\n')
+
+# pyvista's default: compressed inline base64 arrays
+def b64_zlib_text(a):
+    enc = b64_zlib_u32(raw(a)).decode()
+    return '        <DataArray %s>\n          %s\n        </DataArray>\n' % (attrs(a, 'binary'), enc)
+open('static/vtk_binary_zlib.vtr', 'w').write(
+    layout('1.0', 'UInt32', 'vtkZLibDataCompressor', b64_zlib_text) + '</VTKFile>\n')
+
+# ascii, six values per line
+def ascii_text(a):
+    vals = [fmt(v) if a['code'] != 'i' else str(v) for v in a['values']]
+    lines = [' '.join(vals[i:i+6]) for i in range(0, len(vals), 6)]
+    body = ''.join('          %s\n' % l for l in lines)
+    return '        <DataArray %s>\n%s        </DataArray>\n' % (attrs(a, 'ascii'), body)
+field = ('    <FieldData>\n'
+         '      <DataArray type="Float64" Name="TimeValue" NumberOfTuples="1" format="ascii" RangeMin="0.5" RangeMax="0.5">\n'
+         '        0.5\n'
+         '      </DataArray>\n'
+         '    </FieldData>\n')
+open('static/vtk_ascii.vtr', 'w').write(layout('1.0', 'UInt64', None, ascii_text, field) + '</VTKFile>\n')
+
+# the other header types of the raw, base64 and zlib encodings
+appended('static/vtk_appended_zlib_uint64.vtr', '1.0', 'UInt64', 'vtkZLibDataCompressor',
+         lambda b: b''.join(zlib_header(b, 'Q')))
+
+def b64_u32_text(a):
+    data = raw(a)
+    enc = base64.b64encode(struct.pack(E + 'I', len(data))).decode() + base64.b64encode(data).decode()
+    return '        <DataArray %s>\n          %s\n        </DataArray>\n' % (attrs(a, 'binary'), enc)
+open('static/vtk_binary_uint32.vtr', 'w').write(layout('1.0', 'UInt32', None, b64_u32_text) + '</VTKFile>\n')
+
+# meshio encodes the header and the values of an uncompressed array as a single base64 stream
+def single_stream_text(code):
+    def text(a):
+        data = raw(a)
+        enc = base64.b64encode(struct.pack(E + code, len(data)) + data).decode()
+        return '        <DataArray %s>\n          %s\n        </DataArray>\n' % (attrs(a, 'binary'), enc)
+    return text
+open('static/single_stream_binary.vtr', 'w').write(
+    layout('1.0', 'UInt32', None, single_stream_text('I')) + '</VTKFile>\n')
+open('static/single_stream_binary_uint64.vtr', 'w').write(
+    layout('1.0', 'UInt64', None, single_stream_text('Q')) + '</VTKFile>\n')
+
+# big endian files, as written on big endian machines
+E = '>'
+BO = 'BigEndian'
+appended('static/vtk_big_endian_appended.vtr', '1.0', 'UInt64', None, lambda b: struct.pack('>Q', len(b)) + b)
+open('static/vtk_big_endian_binary.vtr', 'w').write(layout('1.0', 'UInt64', None, b64_text) + '</VTKFile>\n')
@@ -0,0 +1,106 @@
+#[cfg(feature = "derive")]
+mod appended_binary {
+    use vtk::prelude::*;
+
+    use vtk::Mesh3D;
+    use vtk::Rectilinear3D;
+    use vtk::Spans3D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: Vec<f64>,
+        pressure: Vec<f32>,
+        empty: Vec<f64>,
+        flags: Vec<u8>,
+    }
+
+    const NX: usize = 3;
+    const NY: usize = 2;
+    const NZ: usize = 2;
+    const LEN: usize = NX * NY * NZ;
+
+    // every array ends with a zero
+    fn setup_vtk() -> VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> {
+        let mesh = Mesh3D::new(vec![-1., 0.5, 0.], vec![1., 0.], vec![2., 0.]);
+        let domain = Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ));
+
+        let data = Binary {
+            rho: (0..LEN).rev().map(|i| i as f64).collect(),
+            pressure: (0..LEN)
+                .map(|i| -(i as f32))
+                .chain([-0.0])
+                .skip(1)
+                .collect(),
+            empty: Vec::new(),
+            flags: (0..LEN as u8).rev().collect(),
+        };
+
+        VtkData::new(domain, data)
+    }
+
+    /// find the integer value of the `offset` attribute of every `DataArray`
+    fn offsets(file: &[u8]) -> Vec<usize> {
+        let file = String::from_utf8_lossy(file);
+        file.split(r#"offset=""#)
+            .skip(1)
+            .map(|rest| rest.split('"').next().unwrap().parse().unwrap())
+            .collect()
+    }
+
+    fn appended_section(file: &[u8]) -> &[u8] {
+        let start = b"<AppendedData encoding=\"raw\">_";
+        let idx = file
+            .windows(start.len())
+            .position(|window| window == start)
+            .unwrap();
+        &file[idx + start.len()..]
+    }
+
+    #[test]
+    fn arrays_have_length_headers() {
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, setup_vtk()).unwrap();
+
+        let offsets = offsets(&file);
+        let appended = appended_section(&file);
+
+        // x, y, z, then the data arrays in the order of the fields
+        let expected_lengths = [3 * 8, 2 * 8, 2 * 8, LEN * 8, LEN * 4, 0, LEN];
+
+        assert_eq!(offsets.len(), expected_lengths.len());
+        assert_eq!(offsets[0], 0);
+
+        for (idx, (offset, length)) in offsets.iter().zip(expected_lengths).enumerate() {
+            let header = u64::from_le_bytes(appended[*offset..offset + 8].try_into().unwrap());
+            assert_eq!(header as usize, length);
+
+            if let Some(next) = offsets.get(idx + 1) {
+                assert_eq!(*next, offset + 8 + length);
+            }
+        }
+
+        let end = offsets.last().unwrap() + 8 + LEN;
+        assert!(appended[end..].starts_with(b"</AppendedData>"));
+    }
+
+    #[test]
+    fn trailing_zeros_are_preserved() {
+        let vtk = setup_vtk();
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+        assert_eq!(
+            out.data.pressure.last().unwrap().to_bits(),
+            (-0.0f32).to_bits()
+        );
+        assert_eq!(*out.domain.mesh.z_locations.last().unwrap(), 0.);
+    }
+}
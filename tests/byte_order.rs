@@ -0,0 +1,150 @@
+#[cfg(feature = "derive")]
+mod byte_order {
+    use vtk::prelude::*;
+
+    use vtk::ByteOrder;
+    use vtk::Mesh3D;
+    use vtk::Rectilinear3D;
+    use vtk::Spans3D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f32>,
+        #[vtk(cell)]
+        rank: Vec<i32>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rho: vtk::Scalar3D<f64>,
+        #[vtk(cell)]
+        rank: Vec<i32>,
+    }
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+    const LEN: usize = NX * NY * NZ;
+
+    fn setup_domain<T>() -> Rectilinear3D<f64, T> {
+        let mesh = Mesh3D::new(vec![0., 0.5, 1., 1.5], vec![-1., 0., 1.], vec![0., 2.]);
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    fn rho() -> vtk::Scalar3D<f64> {
+        let arr = ndarray::Array1::from_iter((0..LEN).map(|i| i as f64 * 0.25))
+            .into_shape((NX, NY, NZ))
+            .unwrap();
+        vtk::Scalar3D::new(arr)
+    }
+
+    fn rank() -> Vec<i32> {
+        (0..(NX - 1) * (NY - 1) * (NZ - 1))
+            .map(|i| i as i32 - 2)
+            .collect()
+    }
+
+    fn big_endian() -> vtk::WriteOptions {
+        vtk::WriteOptions::new().with_byte_order(ByteOrder::BigEndian)
+    }
+
+    fn setup_binary() -> VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> {
+        let velocity = ndarray::Array1::from_iter((0..3 * LEN).map(|i| i as f32 * -0.5))
+            .into_shape((3, NX, NY, NZ))
+            .unwrap();
+
+        let data = Binary {
+            rho: rho(),
+            velocity: vtk::Vector3D::new(velocity),
+            rank: rank(),
+        };
+
+        VtkData::new(setup_domain(), data)
+    }
+
+    #[test]
+    fn writes_byte_order_attribute() {
+        let mut file = Vec::new();
+        vtk::write_vtk_with_options(&mut file, setup_binary(), big_endian()).unwrap();
+        let file = String::from_utf8_lossy(&file);
+        assert!(file.contains(r#"byte_order="BigEndian""#));
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, setup_binary()).unwrap();
+        let file = String::from_utf8_lossy(&file);
+        assert!(file.contains(r#"byte_order="LittleEndian""#));
+    }
+
+    #[test]
+    fn big_endian_appended_headers() {
+        let mut file = Vec::new();
+        vtk::write_vtk_with_options(&mut file, setup_binary(), big_endian()).unwrap();
+
+        let start = b"<AppendedData encoding=\"raw\">_";
+        let idx = file
+            .windows(start.len())
+            .position(|window| window == start)
+            .unwrap();
+        let appended = &file[idx + start.len()..];
+
+        // the first array is the x coordinates
+        assert_eq!(appended[..8], (4u64 * 8).to_be_bytes());
+        assert_eq!(appended[8..16], 0f64.to_be_bytes());
+        assert_eq!(appended[16..24], 0.5f64.to_be_bytes());
+    }
+
+    #[test]
+    fn read_big_endian_appended() {
+        let vtk = setup_binary();
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk_with_options(&mut file, vtk, big_endian()).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn read_big_endian_base64() {
+        let vtk = VtkData::new(
+            setup_domain::<vtk::Binary>(),
+            Base64 {
+                rho: rho(),
+                rank: rank(),
+            },
+        );
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk_with_options(&mut file, vtk, big_endian()).unwrap();
+
+        // the header of the first cell array, encoded separately from its values
+        let header = base64::encode((rank().len() as u64 * 4).to_be_bytes());
+        assert!(String::from_utf8_lossy(&file).contains(&header));
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Base64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn unknown_byte_order_is_an_error() {
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, setup_binary()).unwrap();
+        let file = String::from_utf8_lossy(&file).replace("LittleEndian", "MiddleEndian");
+
+        let out: Result<VtkData<Rectilinear3D<f64, vtk::Binary>, Binary>, _> =
+            vtk::parse::parse_xml_document(file.as_bytes());
+
+        assert!(out.is_err());
+    }
+}
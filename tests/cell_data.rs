@@ -0,0 +1,175 @@
+#[cfg(feature = "derive")]
+mod cell_data_3d {
+    use vtk::prelude::*;
+
+    use vtk::Mesh3D;
+    use vtk::Rectilinear3D;
+    use vtk::Spans3D;
+
+    // cell fields are placed between point fields to check that the offsets
+    // of appended arrays are tracked correctly
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+        #[vtk(cell)]
+        volume: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+        #[vtk(cell)]
+        flux: vtk::Vector3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rho: vtk::Scalar3D<f64>,
+        #[vtk(cell)]
+        volume: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+        #[vtk(cell)]
+        flux: vtk::Vector3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        rho: vtk::Scalar3D<f64>,
+        #[vtk(cell)]
+        volume: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+        #[vtk(cell)]
+        flux: vtk::Vector3D<f64>,
+    }
+
+    const NX: usize = 5;
+    const NY: usize = 4;
+    const NZ: usize = 3;
+
+    fn scalar(nx: usize, ny: usize, nz: usize, start: f64) -> vtk::Scalar3D<f64> {
+        let arr = ndarray::Array1::range(start, start + (nx * ny * nz) as f64, 1.)
+            .into_shape((nx, ny, nz))
+            .unwrap();
+        vtk::Scalar3D::new(arr)
+    }
+
+    fn vector(nx: usize, ny: usize, nz: usize, start: f64) -> vtk::Vector3D<f64> {
+        let arr = ndarray::Array1::range(start, start + (3 * nx * ny * nz) as f64, 1.)
+            .into_shape((3, nx, ny, nz))
+            .unwrap();
+        vtk::Vector3D::new(arr)
+    }
+
+    fn setup_domain<T>() -> Rectilinear3D<f64, T> {
+        let mesh = Mesh3D::new(
+            (0..NX).map(|x| x as f64 + 1.).collect(),
+            (0..NY).map(|y| y as f64 + 1.).collect(),
+            (0..NZ).map(|z| z as f64 + 1.).collect(),
+        );
+
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    macro_rules! setup_data {
+        ($name:ident) => {
+            $name {
+                rho: scalar(NX, NY, NZ, 1.),
+                volume: scalar(NX - 1, NY - 1, NZ - 1, 100.),
+                velocity: vector(NX, NY, NZ, 1000.),
+                flux: vector(NX - 1, NY - 1, NZ - 1, 10000.),
+            }
+        };
+    }
+
+    #[test]
+    fn write_cell_data() {
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data!(Binary));
+
+        let file = std::fs::File::create("./test_vtks/cell_data.vtr").unwrap();
+        vtk::write_vtk(file, vtk).unwrap();
+    }
+
+    #[test]
+    fn writes_cell_data_element() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), setup_data!(Ascii));
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        let point_data = file.find("<PointData>").unwrap();
+        let cell_data = file.find("<CellData>").unwrap();
+        let volume = file.find(r#"Name="volume""#).unwrap();
+        let velocity = file.find(r#"Name="velocity""#).unwrap();
+
+        assert!(point_data < velocity);
+        assert!(velocity < cell_data);
+        assert!(cell_data < volume);
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data!(Binary));
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+        assert_eq!(out.data.volume.dim(), (NX - 1, NY - 1, NZ - 1));
+        assert_eq!(out.data.flux.dim(), (3, NX - 1, NY - 1, NZ - 1));
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), setup_data!(Base64));
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Base64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, expected.data);
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), setup_data!(Ascii));
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, expected.data);
+    }
+
+    #[test]
+    fn no_cell_data_element_without_cell_arrays() {
+        #[derive(vtk::DataArray, Debug, Clone, PartialEq)]
+        #[vtk_write(encoding = "ascii")]
+        pub struct PointsOnly {
+            rho: vtk::Scalar3D<f64>,
+        }
+
+        let data = PointsOnly {
+            rho: scalar(NX, NY, NZ, 1.),
+        };
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), data);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(!file.contains("CellData"));
+    }
+}
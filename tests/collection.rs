@@ -0,0 +1,146 @@
+#[cfg(feature = "derive")]
+mod collection {
+    //! `.pvd` collections of the files of a time series
+    use vtk::prelude::*;
+
+    use vtk::{Collection, CollectionEntry, Mesh3D, Rectilinear3D, Spans3D};
+
+    use std::path::Path;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Fields {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    fn snapshot(time: f64) -> VtkData<Rectilinear3D<f64, vtk::Binary>, Fields> {
+        let mesh = Mesh3D::new(vec![0., 1., 2.], vec![0., 1.], vec![0., 1.]);
+        let domain = Rectilinear3D::new(mesh, Spans3D::new(3, 2, 2));
+        let rho = ndarray::Array3::from_elem((3, 2, 2), time);
+
+        VtkData::new(
+            domain,
+            Fields {
+                rho: vtk::Scalar3D::new(rho),
+            },
+        )
+    }
+
+    #[test]
+    fn write_collection() {
+        let mut collection = Collection::new("flow.pvd".as_ref());
+        collection.add(0., 0, "flow_0.vtr");
+        collection.add(0.5, 0, "flow_1.vtr");
+        collection.add(0.5, 1, "flow_1_part_1.vtr");
+
+        let mut file = Vec::new();
+        collection.write(&mut file).unwrap();
+        let file = String::from_utf8(file).unwrap();
+
+        let expected = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<VTKFile type="Collection" version="0.1" byte_order="LittleEndian">"#,
+            r#"<Collection>"#,
+            r#"<DataSet timestep="0" group="" part="0" file="flow_0.vtr" />"#,
+            r#"<DataSet timestep="0.5" group="" part="0" file="flow_1.vtr" />"#,
+            r#"<DataSet timestep="0.5" group="" part="1" file="flow_1_part_1.vtr" />"#,
+            r#"</Collection>"#,
+            r#"</VTKFile>"#,
+        );
+        assert_eq!(file, expected);
+
+        assert_eq!(
+            vtk::parse_pvd(file.as_bytes()).unwrap(),
+            collection.entries()
+        );
+    }
+
+    #[test]
+    fn parse_paraview_collection() {
+        // as written by ParaView, without a part for every dataset
+        let file = r#"<?xml version="1.0"?>
+<VTKFile type="Collection" version="0.1" byte_order="LittleEndian">
+  <Collection>
+    <DataSet timestep="0.25" group="" part="" file="data/flow_0.vtr"/>
+    <DataSet timestep="1e-3" file="data/flow_1.vtr"/>
+  </Collection>
+</VTKFile>
+"#;
+
+        assert_eq!(
+            vtk::parse_pvd(file.as_bytes()).unwrap(),
+            vec![
+                CollectionEntry::new(0.25, 0, "data/flow_0.vtr"),
+                CollectionEntry::new(0.001, 0, "data/flow_1.vtr"),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_timestep() {
+        let file = r#"<VTKFile type="Collection"><Collection><DataSet file="flow.vtr"/></Collection></VTKFile>"#;
+        let error = vtk::parse_pvd(file.as_bytes()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            vtk::Error::Nom(vtk::ParseError::MissingAttribute { .. })
+        ));
+    }
+
+    #[test]
+    fn restart() {
+        let path = Path::new("./test_vtks/restart.pvd");
+        std::fs::remove_file(path).ok();
+
+        // the first run writes three files
+        let mut collection = Collection::open(path).unwrap();
+        assert!(collection.entries().is_empty());
+
+        for step in 0..3 {
+            let time = step as f64 * 0.5;
+            let file = format!("restart_{}.vtr", step);
+            collection
+                .write_vtk(time, 0, &file, snapshot(time))
+                .unwrap();
+        }
+
+        // every written file is part of the collection on disk
+        let written = Collection::open(path).unwrap();
+        assert_eq!(written, collection);
+
+        let vtk: VtkData<Rectilinear3D<f64, vtk::Binary>, Fields> =
+            vtk::read_vtk(Path::new("./test_vtks/restart_2.vtr")).unwrap();
+        assert_eq!(vtk.data, snapshot(1.).data);
+
+        // the second run restarts from the second file
+        let mut collection = Collection::open(path).unwrap();
+        collection.remove_after(0.5);
+        collection.add(0.75, 0, "restart_3.vtr");
+        collection.flush().unwrap();
+
+        let restarted = Collection::open(path).unwrap();
+        assert_eq!(
+            restarted.entries(),
+            [
+                CollectionEntry::new(0., 0, "restart_0.vtr"),
+                CollectionEntry::new(0.5, 0, "restart_1.vtr"),
+                CollectionEntry::new(0.75, 0, "restart_3.vtr"),
+            ]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn failed_write_is_not_added() {
+        // the whole file fits in the buffer of the writer, so the error is only found when the
+        // buffer is flushed
+        let path = Path::new("./test_vtks/failed_write.pvd");
+        let mut collection = Collection::new(path);
+
+        assert!(collection
+            .write_vtk(0., 0, "/dev/full", snapshot(0.))
+            .is_err());
+        assert!(collection.entries().is_empty());
+    }
+}
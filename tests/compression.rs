@@ -0,0 +1,349 @@
+#[cfg(all(
+    feature = "derive",
+    any(feature = "zlib", feature = "lz4", feature = "lzma")
+))]
+mod compression {
+    use vtk::prelude::*;
+
+    use vtk::CellType;
+    use vtk::Compressor;
+    use vtk::Mesh3D;
+    use vtk::Rectilinear3D;
+    use vtk::Spans3D;
+    use vtk::Unstructured;
+    use vtk::UnstructuredMesh;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f32>,
+        #[vtk(cell)]
+        rank: Vec<i32>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::UnstructuredSpans")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Temperature {
+        temperature: Vec<f64>,
+    }
+
+    // large enough that the arrays are split into several blocks
+    const NX: usize = 24;
+    const NY: usize = 20;
+    const NZ: usize = 16;
+    const LEN: usize = NX * NY * NZ;
+
+    fn setup_domain<T>() -> Rectilinear3D<f64, T> {
+        let mesh = Mesh3D::new(
+            (0..NX).map(|x| x as f64 + 1.).collect(),
+            (0..NY).map(|y| y as f64 * 0.5 + 1.).collect(),
+            (0..NZ).map(|z| z as f64 * 0.25 + 1.).collect(),
+        );
+
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    fn rho() -> vtk::Scalar3D<f64> {
+        let arr = ndarray::Array1::from_iter((0..LEN).map(|i| (i % 7) as f64 + 1.))
+            .into_shape((NX, NY, NZ))
+            .unwrap();
+        vtk::Scalar3D::new(arr)
+    }
+
+    fn setup_data() -> Binary {
+        let velocity = ndarray::Array1::from_iter((0..3 * LEN).map(|i| i as f32 * 0.5 + 1.))
+            .into_shape((3, NX, NY, NZ))
+            .unwrap();
+
+        Binary {
+            rho: rho(),
+            velocity: vtk::Vector3D::new(velocity),
+            rank: vec![3; (NX - 1) * (NY - 1) * (NZ - 1)],
+        }
+    }
+
+    // the same tests are run for every compressor
+    macro_rules! compressor_tests {
+        ($feature:literal, $module:ident, $compressor:expr, $name:literal, $file:literal) => {
+            #[cfg(feature = $feature)]
+            mod $module {
+                use super::*;
+
+                fn options() -> vtk::WriteOptions {
+                    vtk::WriteOptions::new().with_compressor($compressor)
+                }
+
+                #[test]
+                fn write_compressed() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data());
+
+                    let file = std::fs::File::create($file).unwrap();
+                    vtk::write_vtk_with_options(file, vtk, options()).unwrap();
+                }
+
+                #[test]
+                fn writes_compressor_attribute() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data());
+
+                    let mut file = Vec::new();
+                    vtk::write_vtk_with_options(&mut file, vtk, options()).unwrap();
+                    let file = String::from_utf8_lossy(&file);
+
+                    assert!(
+                        file.contains(&format!(r#"header_type="UInt64" compressor="{}">"#, $name))
+                    );
+                    assert!(file.contains(r#"<AppendedData encoding="raw">_"#));
+                }
+
+                #[test]
+                fn compressed_file_is_smaller() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data());
+
+                    let mut raw = Vec::new();
+                    vtk::write_vtk(&mut raw, vtk.clone()).unwrap();
+
+                    let mut compressed = Vec::new();
+                    vtk::write_vtk_with_options(&mut compressed, vtk, options()).unwrap();
+
+                    assert!(compressed.len() < raw.len());
+                }
+
+                #[test]
+                fn read_rectilinear_after_write() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data());
+                    let expected = vtk.clone();
+
+                    let mut file = Vec::new();
+                    vtk::write_vtk_with_options(&mut file, vtk, options()).unwrap();
+
+                    let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+                        vtk::parse::parse_xml_document(&file).unwrap();
+
+                    assert_eq!(out, expected);
+                }
+
+                #[test]
+                fn read_big_endian_after_write() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data());
+                    let expected = vtk.clone();
+
+                    let options = options().with_byte_order(vtk::ByteOrder::BigEndian);
+                    let mut file = Vec::new();
+                    vtk::write_vtk_with_options(&mut file, vtk, options).unwrap();
+                    assert!(String::from_utf8_lossy(&file).contains(r#"byte_order="BigEndian""#));
+
+                    let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+                        vtk::parse::parse_xml_document(&file).unwrap();
+
+                    assert_eq!(out, expected);
+                }
+
+                #[test]
+                fn base64_arrays_are_not_compressed() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Binary>(), Base64 { rho: rho() });
+
+                    let error =
+                        vtk::write_vtk_with_options(Vec::new(), vtk, options()).unwrap_err();
+                    assert!(matches!(error, vtk::Error::Io(_)));
+                }
+
+                #[test]
+                fn read_ascii_arrays_with_compressed_mesh() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Binary>(), Ascii { rho: rho() });
+                    let expected = vtk.clone();
+
+                    let mut file = Vec::new();
+                    vtk::write_vtk_with_options(&mut file, vtk, options()).unwrap();
+
+                    let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii> =
+                        vtk::parse::parse_xml_document(&file).unwrap();
+
+                    assert_eq!(out, expected);
+                }
+
+                #[test]
+                fn ascii_file_has_no_compressor() {
+                    let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), Ascii { rho: rho() });
+
+                    let mut file = Vec::new();
+                    vtk::write_vtk_with_options(&mut file, vtk, options()).unwrap();
+                    let file = String::from_utf8_lossy(&file);
+
+                    assert!(file.contains(r#"header_type="UInt64">"#));
+                    assert!(!file.contains("compressor="));
+                }
+
+                #[test]
+                fn read_unstructured_after_write() {
+                    let points = vec![
+                        [0., 0., 0.],
+                        [1., 0., 0.],
+                        [1., 1., 0.],
+                        [0., 1., 0.],
+                        [0.5, 0.5, 1.],
+                    ];
+
+                    let mut mesh = UnstructuredMesh::new(points, vec![], vec![], vec![]);
+                    mesh.push_cell(CellType::Pyramid, &[0, 1, 2, 3, 4]);
+                    mesh.push_cell(CellType::Triangle, &[0, 1, 4]);
+
+                    let vtk = VtkData::new(
+                        Unstructured::new(mesh),
+                        Temperature {
+                            temperature: vec![1., 2., 3., 4., 5.],
+                        },
+                    );
+                    let expected = vtk.clone();
+
+                    let mut file = Vec::new();
+                    vtk::write_vtk_with_options(&mut file, vtk, options()).unwrap();
+
+                    let out: VtkData<Unstructured<f64, vtk::Binary>, Temperature> =
+                        vtk::parse::parse_xml_document(&file).unwrap();
+
+                    assert_eq!(out, expected);
+                }
+            }
+        };
+    }
+
+    compressor_tests!(
+        "zlib",
+        zlib,
+        Compressor::ZLib,
+        "vtkZLibDataCompressor",
+        "./test_vtks/compressed_zlib.vtr"
+    );
+    compressor_tests!(
+        "lz4",
+        lz4,
+        Compressor::Lz4,
+        "vtkLZ4DataCompressor",
+        "./test_vtks/compressed_lz4.vtr"
+    );
+    compressor_tests!(
+        "lzma",
+        lzma,
+        Compressor::Lzma,
+        "vtkLZMADataCompressor",
+        "./test_vtks/compressed_lzma.vtr"
+    );
+
+    #[cfg(feature = "zlib")]
+    mod vtk_written {
+        use super::*;
+
+        use std::io::Write;
+
+        /// compress `bytes` in blocks of `block_size` with a `UInt32` header, as VTK does by default
+        fn compress_uint32(bytes: &[u8], block_size: usize) -> Vec<u8> {
+            let blocks: Vec<Vec<u8>> = bytes
+                .chunks(block_size)
+                .map(|block| {
+                    let mut encoder =
+                        flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+                    encoder.write_all(block).unwrap();
+                    encoder.finish().unwrap()
+                })
+                .collect();
+
+            let mut header = vec![
+                blocks.len() as u32,
+                block_size as u32,
+                (bytes.len() % block_size) as u32,
+            ];
+            header.extend(blocks.iter().map(|block| block.len() as u32));
+
+            header
+                .into_iter()
+                .flat_map(u32::to_le_bytes)
+                .chain(blocks.into_iter().flatten())
+                .collect()
+        }
+
+        fn f64_bytes(values: &[f64]) -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect()
+        }
+
+        #[test]
+        fn read_vtk_written_file() {
+            // compressed with 32 bit headers and a small block size, with the
+            // arrays stored in a different order than they are declared
+            let x = f64_bytes(&[0., 1., 2., 3.]);
+            let y = f64_bytes(&[0., 1., 2.]);
+            let z = f64_bytes(&[0., 1.]);
+            let rho_values: Vec<f64> = (0..24).map(|i| i as f64 * 0.25).collect();
+            let rho = f64_bytes(&rho_values);
+
+            let arrays = [rho, x, y, z]
+                .iter()
+                .map(|array| compress_uint32(array, 40))
+                .collect::<Vec<_>>();
+
+            let mut offsets = vec![0];
+            for array in &arrays {
+                offsets.push(offsets.last().unwrap() + array.len());
+            }
+
+            let mut file = format!(
+                r#"<?xml version="1.0"?>
+    <VTKFile type="RectilinearGrid" version="1.0" byte_order="LittleEndian" compressor="vtkZLibDataCompressor">
+      <RectilinearGrid WholeExtent="0 3 0 2 0 1">
+        <Piece Extent="0 3 0 2 0 1">
+          <Coordinates>
+            <DataArray type="Float64" NumberOfComponents="1" Name="X" format="appended" offset="{}" RangeMin="0" RangeMax="3"/>
+            <DataArray type="Float64" NumberOfComponents="1" Name="Y" format="appended" offset="{}" RangeMin="0" RangeMax="2"/>
+            <DataArray type="Float64" NumberOfComponents="1" Name="Z" format="appended" offset="{}" RangeMin="0" RangeMax="1"/>
+          </Coordinates>
+          <PointData>
+            <DataArray type="Float64" NumberOfComponents="1" Name="rho" format="appended" offset="0" RangeMin="0" RangeMax="5.75"/>
+          </PointData>
+        </Piece>
+      </RectilinearGrid>
+      <AppendedData encoding="raw">
+       _"#,
+                offsets[1], offsets[2], offsets[3]
+            )
+            .into_bytes();
+
+            for array in arrays {
+                file.extend(array);
+            }
+            file.extend_from_slice(b"\n  </AppendedData>\n</VTKFile>\n");
+
+            // parsing does not depend on the encoding the type was written with
+            let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii> =
+                vtk::parse::parse_xml_document(&file).unwrap();
+
+            let mesh = out.domain.mesh;
+            assert_eq!(mesh.x_locations, vec![0., 1., 2., 3.]);
+            assert_eq!(mesh.y_locations, vec![0., 1., 2.]);
+            assert_eq!(mesh.z_locations, vec![0., 1.]);
+
+            // the x index varies fastest in the file
+            for (idx, value) in rho_values.into_iter().enumerate() {
+                assert_eq!(out.data.rho[[idx % 4, (idx / 4) % 3, idx / 12]], value);
+            }
+        }
+    }
+}
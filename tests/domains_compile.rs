@@ -1,8 +1,7 @@
-use nom::IResult;
 use std::cell::RefMut;
 use vtk::parse;
+use vtk::parse::IResult;
 use vtk::prelude::*;
-use vtk::Binary;
 use vtk::Mesh2D;
 use vtk::Mesh3D;
 use vtk::ParseError;
@@ -10,6 +9,10 @@ use vtk::Rectilinear2D;
 use vtk::Rectilinear3D;
 use vtk::Spans2D;
 use vtk::Spans3D;
+use vtk::{PolyData, PolyMesh};
+use vtk::{Structured3D, StructuredMesh3D};
+use vtk::{Uniform2D, Uniform3D, UniformMesh2D, UniformMesh3D};
+use vtk::{Unstructured, UnstructuredMesh};
 
 #[cfg(test)]
 struct ArrayContainer;
@@ -26,7 +29,8 @@ impl DataArray<vtk::Ascii> for ArrayContainer {
     fn write_array_header<W: std::io::Write>(
         &self,
         _writer: &mut EventWriter<W>,
-        _starting_offset: i64,
+        _offsets: &[i64],
+        _byte_order: vtk::ByteOrder,
     ) -> Result<(), vtk::Error> {
         Ok(())
     }
@@ -34,6 +38,7 @@ impl DataArray<vtk::Ascii> for ArrayContainer {
     fn write_array_appended<W: std::io::Write>(
         &self,
         _writer: &mut EventWriter<W>,
+        _byte_order: vtk::ByteOrder,
     ) -> Result<(), vtk::Error> {
         Ok(())
     }
@@ -43,15 +48,11 @@ impl DataArray<vtk::Ascii> for ArrayContainer {
 impl<T> Visitor<T> for ArrayContainerVisitor {
     type Output = ArrayContainer;
 
-    fn read_headers<'a>(_spans: &T, _buffer: &'a [u8]) -> IResult<&'a [u8], Self> {
+    fn read_headers<'a>(_spans: &T, _buffer: &'a [u8]) -> IResult<'a, Self> {
         Ok((_buffer, ArrayContainerVisitor))
     }
 
-    fn add_to_appended_reader<'a, 'b>(
-        &'a self,
-        _buffer: &'b mut Vec<RefMut<'a, parse::OffsetBuffer>>,
-    ) {
-    }
+    fn add_to_appended_reader<'a>(&'a self, _buffer: &mut Vec<RefMut<'a, parse::OffsetBuffer>>) {}
 
     fn finish(self, _: &T) -> Result<Self::Output, ParseError> {
         Ok(ArrayContainer)
@@ -105,3 +106,119 @@ fn compile_dim2_read() {
     let _: Result<VtkData<Rectilinear2D<f64, vtk::Binary>, ArrayContainer>, _> =
         vtk::read_vtk(&path);
 }
+
+#[test]
+/// verify we have implemented all the traits for Uniform3D to write files
+fn compile_uniform3d_write() {
+    let arrays = ArrayContainer;
+
+    let mesh = UniformMesh3D::new([0., 0., 0.], [1., 1., 1.]);
+    let spans = Spans3D::new(1, 1, 1);
+    let domain = Uniform3D::<f64>::new(mesh, spans);
+    let vtk = VtkData::new(domain, arrays);
+
+    let writer = Vec::new();
+
+    vtk::write_vtk(writer, vtk).ok();
+}
+
+#[test]
+/// verify we have implemented all the traits for Uniform3D to read files
+fn compile_uniform3d_read() {
+    let path = std::path::PathBuf::from("/");
+
+    let _: Result<VtkData<Uniform3D<f64>, ArrayContainer>, _> = vtk::read_vtk(&path);
+}
+
+#[test]
+/// verify we have implemented all the traits for Uniform2D to write files
+fn compile_uniform2d_write() {
+    let arrays = ArrayContainer;
+
+    let mesh = UniformMesh2D::new([0., 0.], [1., 1.]);
+    let spans = Spans2D::new(1, 1);
+    let domain = Uniform2D::<f64>::new(mesh, spans);
+    let vtk = VtkData::new(domain, arrays);
+
+    let writer = Vec::new();
+
+    vtk::write_vtk(writer, vtk).ok();
+}
+
+#[test]
+/// verify we have implemented all the traits for Uniform2D to read files
+fn compile_uniform2d_read() {
+    let path = std::path::PathBuf::from("/");
+
+    let _: Result<VtkData<Uniform2D<f64>, ArrayContainer>, _> = vtk::read_vtk(&path);
+}
+
+#[test]
+/// verify we have implemented all the traits for Structured3D to write files
+fn compile_structured3d_write() {
+    let arrays = ArrayContainer;
+
+    let points = vtk::Vector3D::new(ndarray::Array4::zeros((3, 1, 1, 1)));
+    let mesh = StructuredMesh3D::<f64, vtk::Binary>::new(points);
+    let spans = Spans3D::new(1, 1, 1);
+    let domain = Structured3D::new(mesh, spans);
+    let vtk = VtkData::new(domain, arrays);
+
+    let writer = Vec::new();
+
+    vtk::write_vtk(writer, vtk).ok();
+}
+
+#[test]
+/// verify we have implemented all the traits for Structured3D to read files
+fn compile_structured3d_read() {
+    let path = std::path::PathBuf::from("/");
+
+    let _: Result<VtkData<Structured3D<f64, vtk::Binary>, ArrayContainer>, _> =
+        vtk::read_vtk(&path);
+}
+
+#[test]
+/// verify we have implemented all the traits for Unstructured to write files
+fn compile_unstructured_write() {
+    let arrays = ArrayContainer;
+
+    let mesh = UnstructuredMesh::<f64, vtk::Binary>::new(vec![], vec![], vec![], vec![]);
+    let domain = Unstructured::new(mesh);
+    let vtk = VtkData::new(domain, arrays);
+
+    let writer = Vec::new();
+
+    vtk::write_vtk(writer, vtk).ok();
+}
+
+#[test]
+/// verify we have implemented all the traits for Unstructured to read files
+fn compile_unstructured_read() {
+    let path = std::path::PathBuf::from("/");
+
+    let _: Result<VtkData<Unstructured<f64, vtk::Binary>, ArrayContainer>, _> =
+        vtk::read_vtk(&path);
+}
+
+#[test]
+/// verify we have implemented all the traits for PolyData to write files
+fn compile_poly_data_write() {
+    let arrays = ArrayContainer;
+
+    let mesh = PolyMesh::<f64, vtk::Binary>::new(vec![]);
+    let domain = PolyData::new(mesh);
+    let vtk = VtkData::new(domain, arrays);
+
+    let writer = Vec::new();
+
+    vtk::write_vtk(writer, vtk).ok();
+}
+
+#[test]
+/// verify we have implemented all the traits for PolyData to read files
+fn compile_poly_data_read() {
+    let path = std::path::PathBuf::from("/");
+
+    let _: Result<VtkData<PolyData<f64, vtk::Binary>, ArrayContainer>, _> = vtk::read_vtk(&path);
+}
@@ -0,0 +1,122 @@
+mod dynamic {
+    //! reading every array of a file without a `ParseArray` type
+    use std::path::Path;
+
+    use vtk::{Buffer, DynDomain, DynVtk, Precision};
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+
+    fn read(path: &str) -> DynVtk {
+        vtk::read_vtk_dynamic(Path::new(path)).unwrap()
+    }
+
+    // the contents of the `static/vtk_*.vtr` files
+    fn check_rectilinear(vtk: &DynVtk) {
+        let mesh = match &vtk.domain {
+            DynDomain::Rectilinear(domain) => &domain.mesh,
+            other => panic!("unexpected domain {:?}", other),
+        };
+        assert_eq!(mesh.x_locations, vec![0., 0.5, 1., 1.5]);
+        assert_eq!(vtk.domain.grid_type(), vtk::GridType::RectilinearGrid);
+
+        let names: Vec<_> = vtk.point_data.names().collect();
+        assert_eq!(names, ["rho", "velocity"]);
+
+        let rho = vtk.point_data.get("rho").unwrap();
+        assert_eq!(rho.precision, Precision::Float64);
+        assert_eq!((rho.components, rho.tuples()), (1, NX * NY * NZ));
+        assert_eq!(rho.to_vec::<f64>().unwrap()[5], 1.25);
+
+        let velocity = vtk.point_data.get("velocity").unwrap();
+        assert_eq!(velocity.precision, Precision::Float32);
+        assert_eq!((velocity.components, velocity.tuples()), (3, NX * NY * NZ));
+        assert_eq!(&velocity.to_vec::<f64>().unwrap()[3..6], &[1., -1., 0.5]);
+
+        assert_eq!(vtk.cell_data.len(), 1);
+        let rank = vtk.cell_data.get("rank").unwrap();
+        assert_eq!(rank.precision, Precision::Int32);
+        assert_eq!(rank.values, Buffer::Int(vec![0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn inline_ascii() {
+        let vtk = read("./static/vtk_ascii.vtr");
+        check_rectilinear(&vtk);
+
+        let time = vtk.field_data.get("TimeValue").unwrap();
+        assert_eq!(time.as_float(), Some(0.5));
+    }
+
+    #[test]
+    fn inline_base64() {
+        check_rectilinear(&read("./static/vtk_binary.vtr"));
+    }
+
+    #[test]
+    fn appended_raw() {
+        check_rectilinear(&read("./static/vtk_appended_raw.vtr"));
+    }
+
+    #[test]
+    fn big_endian_appended() {
+        check_rectilinear(&read("./static/vtk_big_endian_appended.vtr"));
+    }
+
+    #[test]
+    fn unclosed_section() {
+        let file = std::fs::read("./static/vtk_ascii.vtr").unwrap();
+        let file = String::from_utf8(file).unwrap().replace("</PointData>", "");
+
+        assert!(vtk::parse_dynamic(file.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn unsupported_type() {
+        let file = br#"<VTKFile type="HyperTreeGrid" version="1.0"></VTKFile>"#;
+        assert!(vtk::parse_dynamic(file).is_err());
+    }
+}
+
+#[cfg(feature = "derive")]
+mod dynamic_derive {
+    use vtk::prelude::*;
+
+    use vtk::{Buffer, DynDomain, Precision};
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Cells {
+        #[vtk(cell)]
+        flags: Vec<u8>,
+    }
+
+    #[test]
+    fn image_data() {
+        let mesh = vtk::UniformMesh3D::new([0.5, -1.0, 2.0], [0.25, 0.5, 1.0]);
+        let domain = vtk::Uniform3D::new(mesh.clone(), vtk::Spans3D::new(NX, NY, NZ));
+        let data = vec![7u8; (NX - 1) * (NY - 1) * (NZ - 1)];
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, VtkData::new(domain, Cells { flags: data })).unwrap();
+        let vtk = vtk::parse_dynamic(&file).unwrap();
+
+        match &vtk.domain {
+            DynDomain::Uniform(domain) => assert_eq!(domain.mesh, mesh),
+            other => panic!("unexpected domain {:?}", other),
+        }
+
+        assert!(vtk.point_data.is_empty());
+        assert!(vtk.field_data.is_empty());
+
+        let flags = vtk.cell_data.get("flags").unwrap();
+        assert_eq!(flags.precision, Precision::UInt8);
+        assert_eq!(flags.values, Buffer::UInt(vec![7; 6]));
+    }
+}
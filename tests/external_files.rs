@@ -0,0 +1,177 @@
+#[cfg(feature = "derive")]
+mod external_files {
+    //! The files in `static/*.vtr` are not written by VTK. They are generated by
+    //! `static/generate_vtk_files.py` to follow the layout of `vtkXMLRectilinearGridWriter`
+    //! output: `<PointData>` and `<CellData>` before the `<Coordinates>`, arbitrary names
+    //! for the coordinate arrays, `NumberOfComponents` only for arrays with more than one
+    //! component, `RangeMin` / `RangeMax` attributes and real length headers for every array.
+    //! The `single_stream_*.vtr` files encode the header and the values of each inline array
+    //! as a single base64 stream, as meshio does.
+    //!
+    //! Every file contains the same data.
+    use vtk::prelude::*;
+
+    use std::path::Path;
+
+    use vtk::Rectilinear3D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Data {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f32>,
+        #[vtk(cell)]
+        rank: Vec<i32>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        #[vtk(cell)]
+        rank: Vec<i32>,
+    }
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+
+    fn read(path: &str) -> VtkData<Rectilinear3D<f64, vtk::Binary>, Data> {
+        vtk::parse::read_and_parse(Path::new(path)).unwrap()
+    }
+
+    fn check_contents(vtk: &VtkData<Rectilinear3D<f64, vtk::Binary>, Data>) {
+        let mesh = &vtk.domain.mesh;
+        assert_eq!(mesh.x_locations, vec![0., 0.5, 1., 1.5]);
+        assert_eq!(mesh.y_locations, vec![-1., 0., 1.]);
+        assert_eq!(mesh.z_locations, vec![0., 2.]);
+
+        // the x index varies fastest in the file
+        for idx in 0..NX * NY * NZ {
+            let (i, j, k) = (idx % NX, (idx / NX) % NY, idx / (NX * NY));
+            let value = idx as f64;
+
+            assert_eq!(vtk.data.rho[[i, j, k]], value * 0.25);
+
+            assert_eq!(vtk.data.velocity[[0, i, j, k]], value as f32);
+            assert_eq!(vtk.data.velocity[[1, i, j, k]], -value as f32);
+            assert_eq!(vtk.data.velocity[[2, i, j, k]], value as f32 * 0.5);
+        }
+
+        assert_eq!(vtk.data.rank, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn appended_raw_uint32_headers() {
+        // an older file without a `header_type`, which defaults to `UInt32`
+        let out = read("./static/vtk_appended_raw.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn appended_raw_uint64_headers() {
+        let out = read("./static/vtk_appended_raw_uint64.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    #[cfg(feature = "zlib")]
+    fn appended_zlib() {
+        let out = read("./static/vtk_appended_zlib.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    #[cfg(feature = "zlib")]
+    fn appended_zlib_uint64_headers() {
+        let out = read("./static/vtk_appended_zlib_uint64.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn inline_base64() {
+        let out = read("./static/vtk_binary.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn inline_base64_uint32_headers() {
+        let out = read("./static/vtk_binary_uint32.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn inline_base64_single_stream() {
+        let out = read("./static/single_stream_binary.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn inline_base64_single_stream_uint64_headers() {
+        let out = read("./static/single_stream_binary_uint64.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn appended_base64() {
+        // vtk's default encoding of the appended section
+        let out = read("./static/vtk_appended_base64.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    #[cfg(feature = "zlib")]
+    fn appended_base64_zlib() {
+        let out = read("./static/vtk_appended_base64_zlib.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    #[cfg(feature = "zlib")]
+    fn inline_base64_zlib() {
+        // compressed inline arrays, as pyvista writes them by default
+        let out = read("./static/vtk_binary_zlib.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn inline_ascii() {
+        let out = read("./static/vtk_ascii.vtr");
+        check_contents(&out);
+
+        let time = out.field_data.get("TimeValue").unwrap();
+        assert_eq!(time.as_float(), Some(0.5));
+    }
+
+    #[test]
+    fn big_endian_appended() {
+        let out = read("./static/vtk_big_endian_appended.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn big_endian_inline_base64() {
+        let out = read("./static/vtk_big_endian_binary.vtr");
+        check_contents(&out);
+    }
+
+    #[test]
+    fn base64_header_is_encoded_separately() {
+        let mesh = vtk::Mesh3D::new(vec![0., 1.], vec![0., 1.], vec![0., 1.]);
+        let domain = Rectilinear3D::<f64, vtk::Ascii>::new(mesh, vtk::Spans3D::new(2, 2, 2));
+        let vtk = VtkData::new(domain, Base64 { rank: vec![7] });
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8(file).unwrap();
+
+        // as vtk writes it: the padded 8 byte header followed by the 4 bytes of the array
+        let expected = format!(
+            "{}{}",
+            base64::encode(4u64.to_le_bytes()),
+            base64::encode(7i32.to_le_bytes())
+        );
+        assert!(file.contains(&expected));
+    }
+}
@@ -0,0 +1,210 @@
+#[cfg(feature = "derive")]
+mod field_data {
+    use vtk::prelude::*;
+
+    use vtk::FieldData;
+    use vtk::FieldValue;
+    use vtk::Mesh3D;
+    use vtk::Rectilinear3D;
+    use vtk::Spans3D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+
+    fn rho() -> vtk::Scalar3D<f64> {
+        let arr = ndarray::Array1::range(0., (NX * NY * NZ) as f64, 1.)
+            .into_shape((NX, NY, NZ))
+            .unwrap();
+        vtk::Scalar3D::new(arr)
+    }
+
+    fn setup_domain<T>() -> Rectilinear3D<f64, T> {
+        let mesh = Mesh3D::new(
+            (0..NX).map(|x| x as f64).collect(),
+            (0..NY).map(|y| y as f64).collect(),
+            (0..NZ).map(|z| z as f64).collect(),
+        );
+
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    fn simulation_metadata() -> FieldData {
+        let mut field_data = FieldData::new();
+        field_data.insert("TimeValue", 12.5);
+        field_data.insert("Iteration", 4000i64);
+        field_data.insert("ReynoldsNumber", 1600.);
+        field_data.insert("Solver", "streams");
+        field_data.insert("Probes", vec![0.25, -1.5]);
+        field_data.insert(
+            "Boundaries",
+            vec!["wall".to_string(), String::new(), "inflow".to_string()],
+        );
+        field_data
+    }
+
+    #[test]
+    fn write_field_data() {
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), Binary { rho: rho() })
+            .with_field_data(simulation_metadata());
+
+        let file = std::fs::File::create("./test_vtks/field_data.vtr").unwrap();
+        vtk::write_vtk(file, vtk).unwrap();
+    }
+
+    #[test]
+    fn field_data_written_before_piece() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), Ascii { rho: rho() })
+            .with_field_data(simulation_metadata());
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        let grid = file.find("<RectilinearGrid").unwrap();
+        let field_data = file.find("<FieldData>").unwrap();
+        let piece = file.find("<Piece").unwrap();
+
+        assert!(grid < field_data);
+        assert!(field_data < piece);
+        assert!(file.contains(
+            r#"<DataArray type="Float64" Name="TimeValue" NumberOfTuples="1" format="ascii">12.5</DataArray>"#
+        ));
+        assert!(file.contains(r#"Name="Solver" NumberOfTuples="1""#));
+    }
+
+    #[test]
+    fn no_field_data_element_when_empty() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), Ascii { rho: rho() });
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(!file.contains("FieldData"));
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(file.as_bytes()).unwrap();
+        assert!(out.field_data.is_empty());
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), Binary { rho: rho() })
+            .with_field_data(simulation_metadata());
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), Base64 { rho: rho() })
+            .with_field_data(simulation_metadata());
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Base64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, expected.data);
+        assert_eq!(out.field_data, expected.field_data);
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), Ascii { rho: rho() })
+            .with_field_data(simulation_metadata());
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, expected.data);
+        assert_eq!(out.field_data, expected.field_data);
+
+        let field_data = out.field_data;
+        assert_eq!(field_data.get("TimeValue").unwrap().as_float(), Some(12.5));
+        assert_eq!(field_data.get("Iteration").unwrap().as_int(), Some(4000));
+        assert_eq!(field_data.get("Solver").unwrap().as_str(), Some("streams"));
+    }
+
+    #[test]
+    fn read_binary_field_data() {
+        // binary (base64) field data as written by paraview, with attributes
+        // in a different order than this crate writes them. The header and the
+        // values are encoded separately
+        let time = base64::encode(8u64.to_le_bytes()) + &base64::encode(0.5f64.to_le_bytes());
+        let step = base64::encode(8u64.to_le_bytes()) + &base64::encode(7i64.to_le_bytes());
+
+        let file = format!(
+            r#"<?xml version="1.0"?>
+<VTKFile type="RectilinearGrid" version="1.0" byte_order="LittleEndian" header_type="UInt64">
+<RectilinearGrid WholeExtent="0 3 0 2 0 1">
+<FieldData>
+<DataArray type="Float64" Name="TimeValue" NumberOfTuples="1" format="binary">{}</DataArray>
+<DataArray format="binary" NumberOfTuples="1" Name="CYCLE" type="Int64">{}</DataArray>
+</FieldData>
+<Piece Extent="0 3 0 2 0 1">
+<Coordinates>
+<DataArray type="Float64" NumberOfComponents="1" Name="X" format="ascii">0 1 2 3</DataArray>
+<DataArray type="Float64" NumberOfComponents="1" Name="Y" format="ascii">0 1 2</DataArray>
+<DataArray type="Float64" NumberOfComponents="1" Name="Z" format="ascii">0 1</DataArray>
+</Coordinates>
+<PointData>
+<DataArray type="Float64" NumberOfComponents="1" Name="rho" format="ascii">{}</DataArray>
+</PointData>
+</Piece>
+</RectilinearGrid>
+</VTKFile>"#,
+            time,
+            step,
+            (0..NX * NY * NZ)
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(file.as_bytes()).unwrap();
+
+        assert_eq!(
+            out.field_data.get("TimeValue"),
+            Some(&FieldValue::Float(vec![0.5]))
+        );
+        assert_eq!(out.field_data.get("CYCLE"), Some(&FieldValue::Int(vec![7])));
+        assert_eq!(out.data.rho.dim(), (NX, NY, NZ));
+    }
+}
@@ -43,7 +43,7 @@ mod field3d {
 
         dbg!(&data);
 
-        vtk::VtkData { data, domain }
+        vtk::VtkData::new(domain, data)
     }
 
     #[test]
@@ -113,7 +113,7 @@ mod field2d {
 
         dbg!(&data);
 
-        vtk::VtkData { data, domain }
+        vtk::VtkData::new(domain, data)
     }
 
     #[test]
@@ -191,7 +191,7 @@ mod scalar_3d {
 
         dbg!(&data);
 
-        vtk::VtkData { data, domain }
+        vtk::VtkData::new(domain, data)
     }
 
     #[test]
@@ -267,7 +267,7 @@ mod scalar_2d {
 
         dbg!(&data);
 
-        vtk::VtkData { data, domain }
+        vtk::VtkData::new(domain, data)
     }
 
     #[test]
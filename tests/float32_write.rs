@@ -1,7 +1,6 @@
 #[cfg(all(test, feature = "derive"))]
 mod inner {
     use vtk::Mesh3D;
-    use vtk::Rectilinear3D;
     use vtk::Scalar3D;
     use vtk::Spans3D;
     use vtk::Vector3D;
@@ -10,21 +9,24 @@ mod inner {
     use ndarray::Array3;
     use ndarray::Array4;
 
-    #[derive(vtk::DataArray, Clone)]
+    #[derive(vtk::DataArray, vtk::ParseArray, Clone, Debug, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
     #[vtk_write(encoding = "binary")]
     pub struct Binary {
         rho: Scalar3D<f32>,
         velocity: Vector3D<f32>,
     }
 
-    #[derive(vtk::DataArray, Clone)]
+    #[derive(vtk::DataArray, vtk::ParseArray, Clone, Debug, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
     #[vtk_write(encoding = "base64")]
     pub struct Base64 {
         rho: Scalar3D<f32>,
         velocity: Vector3D<f32>,
     }
 
-    #[derive(vtk::DataArray, Clone)]
+    #[derive(vtk::DataArray, vtk::ParseArray, Clone, Debug, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
     #[vtk_write(encoding = "ascii")]
     pub struct Ascii {
         rho: Scalar3D<f32>,
@@ -39,9 +41,9 @@ mod inner {
         let mut rho = Array3::<f32>::zeros((NX, NY, NZ));
         let mut velocity = Array4::<f32>::zeros((3, NX, NY, NZ));
 
-        let x: Array1<f32> = ndarray::ArrayBase::linspace(0., 2. * 3.14, NX);
-        let y: Array1<f32> = ndarray::ArrayBase::linspace(0., 2. * 3.14, NY);
-        let z: Array1<f32> = ndarray::ArrayBase::linspace(0., 2. * 3.14, NZ);
+        let x: Array1<f32> = ndarray::ArrayBase::linspace(0., 2. * std::f32::consts::PI, NX);
+        let y: Array1<f32> = ndarray::ArrayBase::linspace(0., 2. * std::f32::consts::PI, NY);
+        let z: Array1<f32> = ndarray::ArrayBase::linspace(0., 2. * std::f32::consts::PI, NZ);
 
         for i in 0..NX {
             for j in 0..NY {
@@ -115,4 +117,77 @@ mod inner {
 
         vtk::write_vtk(writer, _vtk).unwrap();
     }
+
+    #[derive(vtk::ParseArray, Clone, Debug, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    pub struct DoublePrecision {
+        rho: Scalar3D<f64>,
+        velocity: Vector3D<f64>,
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        let (rho, velocity, mesh, spans) = generate_data::<vtk::Binary>();
+        let data = Binary { rho, velocity };
+        let domain: vtk::Rectilinear3D<f32, vtk::Binary> = vtk::Rectilinear3D::new(mesh, spans);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk::VtkData::new(domain, data.clone())).unwrap();
+
+        let out: vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, data);
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        let (rho, velocity, mesh, spans) = generate_data::<vtk::Ascii>();
+        let data = Base64 { rho, velocity };
+        let domain: vtk::Rectilinear3D<f32, vtk::Ascii> = vtk::Rectilinear3D::new(mesh, spans);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk::VtkData::new(domain, data.clone())).unwrap();
+
+        let out: vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, Base64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, data);
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        let (rho, velocity, mesh, spans) = generate_data::<vtk::Ascii>();
+        let data = Ascii { rho, velocity };
+        let domain: vtk::Rectilinear3D<f32, vtk::Ascii> = vtk::Rectilinear3D::new(mesh, spans);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk::VtkData::new(domain, data.clone())).unwrap();
+
+        let out: vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, data);
+    }
+
+    #[test]
+    fn read_float32_as_double_precision() {
+        // a `Float32` file can also be read into `f64` containers
+        let (rho, velocity, mesh, spans) = generate_data::<vtk::Binary>();
+        let data = Binary { rho, velocity };
+        let x_locations = mesh.x_locations.clone();
+        let domain: vtk::Rectilinear3D<f32, vtk::Binary> = vtk::Rectilinear3D::new(mesh, spans);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk::VtkData::new(domain, data.clone())).unwrap();
+
+        let out: vtk::VtkData<vtk::Rectilinear3D<f64, vtk::Binary>, DoublePrecision> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data.rho.mapv(|x| x as f32), *data.rho);
+        assert_eq!(out.data.velocity.mapv(|x| x as f32), *data.velocity);
+
+        let x_locations: Vec<f64> = x_locations.into_iter().map(f64::from).collect();
+        assert_eq!(out.domain.mesh.x_locations, x_locations);
+    }
 }
@@ -0,0 +1,199 @@
+#[cfg(feature = "derive")]
+mod uniform_3d {
+    use vtk::prelude::*;
+
+    use vtk::Spans3D;
+    use vtk::Uniform3D;
+    use vtk::UniformMesh3D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    fn setup_vtk() -> VtkData<Uniform3D<f64>, Binary> {
+        let nx = 4;
+        let ny = 3;
+        let nz = 5;
+
+        let rho: ndarray::Array3<f64> = ndarray::Array1::range(0., (nx * ny * nz) as f64, 1.)
+            .into_shape((nx, ny, nz))
+            .unwrap();
+        let velocity: ndarray::Array4<f64> =
+            ndarray::Array1::range(0., (3 * nx * ny * nz) as f64, 1.)
+                .into_shape((3, nx, ny, nz))
+                .unwrap();
+
+        let data = Binary {
+            rho: vtk::Scalar3D::new(rho),
+            velocity: vtk::Vector3D::new(velocity),
+        };
+
+        let mesh = UniformMesh3D::new([0.5, -1.0, 2.0], [0.25, 0.5, 1.0]);
+        let domain = Uniform3D::new(mesh, Spans3D::new(nx, ny, nz));
+
+        VtkData::new(domain, data)
+    }
+
+    #[test]
+    fn write_image_data() {
+        let vtk = setup_vtk();
+
+        let file = std::fs::File::create("./test_vtks/uniform_3d.vti").unwrap();
+        vtk::write_vtk(file, vtk).unwrap();
+    }
+
+    #[test]
+    fn writes_origin_and_spacing() {
+        let vtk = setup_vtk();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(file.contains(r#"<VTKFile type="ImageData""#));
+        // the first point (extent index 1) should be located at the origin of the mesh
+        assert!(file.contains(r#"Origin="0.25 -1.5 1.0""#));
+        assert!(file.contains(r#"Spacing="0.25 0.5 1.0""#));
+        assert!(!file.contains("<Coordinates"));
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        let vtk = setup_vtk();
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Uniform3D<f64>, Binary> = vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    /// reverse the order of the attributes of the `<ImageData>` element, so that the `Origin` and
+    /// `Spacing` come before the `WholeExtent`
+    pub fn reverse_image_data_attributes(file: &[u8]) -> Vec<u8> {
+        let tag = b"<ImageData ";
+        let start = file.windows(tag.len()).position(|w| w == tag).unwrap() + tag.len();
+        let end = start + file[start..].iter().position(|&c| c == b'>').unwrap();
+
+        let element = std::str::from_utf8(&file[start..end]).unwrap();
+        let parts: Vec<&str> = element.split('"').collect();
+        let attributes: Vec<String> = parts
+            .chunks_exact(2)
+            .rev()
+            .map(|pair| format!(r#"{}"{}""#, pair[0].trim(), pair[1]))
+            .collect();
+        let reversed = attributes.join(" ");
+        assert!(reversed.find("Origin=").unwrap() < reversed.find("WholeExtent=").unwrap());
+
+        [&file[..start], reversed.as_bytes(), &file[end..]].concat()
+    }
+
+    #[test]
+    fn read_reordered_attributes() {
+        let vtk = setup_vtk();
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = reverse_image_data_attributes(&file);
+
+        let out: VtkData<Uniform3D<f64>, Binary> = vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        let vtk = setup_vtk();
+        let data = Ascii {
+            rho: vtk.data.rho.clone(),
+        };
+        let vtk = vtk.new_data(data);
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Uniform3D<f64>, Ascii> = vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+}
+
+#[cfg(feature = "derive")]
+mod uniform_2d {
+    use vtk::prelude::*;
+
+    use vtk::Spans2D;
+    use vtk::Uniform2D;
+    use vtk::UniformMesh2D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans2D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rho: vtk::Scalar2D<f64>,
+        velocity: vtk::Vector2D<f64>,
+    }
+
+    fn setup_vtk() -> VtkData<Uniform2D<f64>, Base64> {
+        let nx = 6;
+        let ny = 4;
+
+        let rho: ndarray::Array2<f64> = ndarray::Array1::range(0., (nx * ny) as f64, 1.)
+            .into_shape((nx, ny))
+            .unwrap();
+        let velocity: ndarray::Array3<f64> = ndarray::Array1::range(0., (2 * nx * ny) as f64, 1.)
+            .into_shape((2, nx, ny))
+            .unwrap();
+
+        let data = Base64 {
+            rho: vtk::Scalar2D::new(rho),
+            velocity: vtk::Vector2D::new(velocity),
+        };
+
+        let mesh = UniformMesh2D::new([1.0, 2.0], [0.5, 0.25]);
+        let domain = Uniform2D::new(mesh, Spans2D::new(nx, ny));
+        VtkData::new(domain, data)
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        let vtk = setup_vtk();
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Uniform2D<f64>, Base64> = vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn read_reordered_attributes() {
+        let vtk = setup_vtk();
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = super::uniform_3d::reverse_image_data_attributes(&file);
+
+        let out: VtkData<Uniform2D<f64>, Base64> = vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+}
@@ -0,0 +1,306 @@
+#[cfg(feature = "derive")]
+mod integer_arrays {
+    use vtk::prelude::*;
+
+    use vtk::Mesh3D;
+    use vtk::Rectilinear3D;
+    use vtk::Scalar3D;
+    use vtk::Spans3D;
+    use vtk::Vector3D;
+
+    // integer arrays of different widths are mixed with floats to check that the
+    // offsets of appended arrays account for the size of each element
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rank: Vec<i32>,
+        mask: Scalar3D<u8>,
+        rho: Scalar3D<f64>,
+        flags: Scalar3D<i8>,
+        boundary: Vector3D<i16>,
+        counts: Scalar3D<u16>,
+        material: Scalar3D<u32>,
+        ids: Vec<u64>,
+        cells: Vec<i64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rank: Vec<i32>,
+        mask: Scalar3D<u8>,
+        rho: Scalar3D<f64>,
+        flags: Scalar3D<i8>,
+        boundary: Vector3D<i16>,
+        counts: Scalar3D<u16>,
+        material: Scalar3D<u32>,
+        ids: Vec<u64>,
+        cells: Vec<i64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        rank: Vec<i32>,
+        mask: Scalar3D<u8>,
+        rho: Scalar3D<f64>,
+        flags: Scalar3D<i8>,
+        boundary: Vector3D<i16>,
+        counts: Scalar3D<u16>,
+        material: Scalar3D<u32>,
+        ids: Vec<u64>,
+        cells: Vec<i64>,
+    }
+
+    // 64 bit values that a `f64` cannot represent, which are only read back exactly if they are
+    // never decoded as floats
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct LargeBinary {
+        ids: Vec<u64>,
+        cells: Vec<i64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct LargeBase64 {
+        ids: Vec<u64>,
+        cells: Vec<i64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct LargeAscii {
+        ids: Vec<u64>,
+        cells: Vec<i64>,
+    }
+
+    // the same names as the arrays of `Binary`, with element types that can not hold every value
+    #[derive(vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    pub struct NarrowRank {
+        rank: Vec<u8>,
+    }
+
+    #[derive(vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    pub struct IntegerRho {
+        rho: Vec<i32>,
+    }
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+    const LEN: usize = NX * NY * NZ;
+
+    fn scalar<T: vtk::Numeric>(values: impl Iterator<Item = T>) -> Scalar3D<T> {
+        let arr = ndarray::Array1::from_iter(values)
+            .into_shape((NX, NY, NZ))
+            .unwrap();
+        Scalar3D::new(arr)
+    }
+
+    fn setup_domain<T>() -> Rectilinear3D<f64, T> {
+        let mesh = Mesh3D::new(
+            (0..NX).map(|x| x as f64).collect(),
+            (0..NY).map(|y| y as f64).collect(),
+            (0..NZ).map(|z| z as f64).collect(),
+        );
+
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    macro_rules! setup_data {
+        ($name:ident) => {
+            $name {
+                rank: (0..LEN as i32).map(|i| i - 10).collect(),
+                mask: scalar((0..LEN).map(|i| (i * 11) as u8)),
+                rho: scalar((0..LEN).map(|i| i as f64 * 0.5 + 1.)),
+                flags: scalar((0..LEN).map(|i| -(i as i8))),
+                boundary: Vector3D::new(
+                    ndarray::Array1::from_iter((0..3 * LEN).map(|i| i as i16 - 30))
+                        .into_shape((3, NX, NY, NZ))
+                        .unwrap(),
+                ),
+                counts: scalar((0..LEN).map(|i| (i * 1000) as u16)),
+                material: scalar((0..LEN).map(|i| (i as u32) << 20)),
+                ids: (0..LEN as u64).map(|i| i << 40).collect(),
+                cells: (0..LEN as i64).map(|i| -(i << 33)).collect(),
+            }
+        };
+    }
+
+    macro_rules! setup_large_data {
+        ($name:ident) => {
+            $name {
+                ids: (0..LEN as u64).map(|i| u64::MAX - 1 - i).collect(),
+                cells: (0..LEN as i64)
+                    .map(|i| {
+                        if i % 2 == 0 {
+                            (1 << 53) + 1 + i
+                        } else {
+                            i64::MIN + i
+                        }
+                    })
+                    .collect(),
+            }
+        };
+    }
+
+    #[test]
+    fn write_integer_arrays() {
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data!(Binary));
+
+        let file = std::fs::File::create("./test_vtks/integer_arrays.vtr").unwrap();
+        vtk::write_vtk(file, vtk).unwrap();
+    }
+
+    #[test]
+    fn writes_integer_types() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), setup_data!(Ascii));
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(file.contains(r#"type="Int32" NumberOfComponents="1" Name="rank""#));
+        assert!(file.contains(r#"type="UInt8" NumberOfComponents="1" Name="mask""#));
+        assert!(file.contains(r#"type="Int8" NumberOfComponents="1" Name="flags""#));
+        assert!(file.contains(r#"type="Int16" NumberOfComponents="3" Name="boundary""#));
+        assert!(file.contains(r#"type="UInt16" NumberOfComponents="1" Name="counts""#));
+        assert!(file.contains(r#"type="UInt32" NumberOfComponents="1" Name="material""#));
+        assert!(file.contains(r#"type="UInt64" NumberOfComponents="1" Name="ids""#));
+        assert!(file.contains(r#"type="Int64" NumberOfComponents="1" Name="cells""#));
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), setup_data!(Binary));
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), setup_data!(Base64));
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Base64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, expected.data);
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), setup_data!(Ascii));
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.data, expected.data);
+    }
+
+    #[test]
+    fn read_large_integers() {
+        let binary = setup_large_data!(LargeBinary);
+        assert_ne!(binary.ids[0] as f64 as u64, binary.ids[0]);
+        assert_ne!(binary.cells[0] as f64 as i64, binary.cells[0]);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(
+            &mut file,
+            VtkData::new(setup_domain::<vtk::Binary>(), binary.clone()),
+        )
+        .unwrap();
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, LargeBinary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+        assert_eq!(out.data, binary);
+
+        let base64 = setup_large_data!(LargeBase64);
+        let mut file = Vec::new();
+        vtk::write_vtk(
+            &mut file,
+            VtkData::new(setup_domain::<vtk::Ascii>(), base64.clone()),
+        )
+        .unwrap();
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, LargeBase64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+        assert_eq!(out.data, base64);
+
+        let ascii = setup_large_data!(LargeAscii);
+        let mut file = Vec::new();
+        vtk::write_vtk(
+            &mut file,
+            VtkData::new(setup_domain::<vtk::Ascii>(), ascii.clone()),
+        )
+        .unwrap();
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, LargeAscii> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+        assert_eq!(out.data, ascii);
+    }
+
+    /// the array name, value and type of the error of reading the `Binary` arrays into `D`
+    fn unrepresentable<D>(data: Binary) -> (String, String, vtk::Precision)
+    where
+        D: vtk::ParseArray + std::fmt::Debug,
+        D::Visitor: vtk::Visitor<Spans3D, Output = D>,
+    {
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, VtkData::new(setup_domain::<vtk::Binary>(), data)).unwrap();
+
+        let out: Result<VtkData<Rectilinear3D<f64, vtk::Binary>, D>, _> =
+            vtk::parse::parse_xml_document(&file);
+
+        match out {
+            Err(vtk::Error::Nom(vtk::ParseError::UnrepresentableValue {
+                array,
+                value,
+                precision,
+            })) => (array, value, precision),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn integer_out_of_range() {
+        let mut data = setup_data!(Binary);
+        data.rank = (0..LEN as i32).map(|i| i * 100).collect();
+
+        let error = unrepresentable::<NarrowRank>(data);
+        assert_eq!(
+            error,
+            ("rank".to_string(), "300".to_string(), vtk::Precision::UInt8)
+        );
+    }
+
+    #[test]
+    fn fractional_float_into_integer() {
+        // the second value of `rho` is 1.5
+        let error = unrepresentable::<IntegerRho>(setup_data!(Binary));
+        assert_eq!(
+            error,
+            ("rho".to_string(), "1.5".to_string(), vtk::Precision::Int32)
+        );
+    }
+}
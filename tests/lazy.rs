@@ -0,0 +1,130 @@
+#[cfg(feature = "derive")]
+mod lazy {
+    //! reading only some arrays of a file, and the rest on demand
+    use vtk::Precision;
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+
+    #[derive(vtk::ParseArray)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    pub struct LazyFields {
+        rho: vtk::Scalar3D<f64>,
+        #[vtk(lazy)]
+        velocity: vtk::LazyArray,
+        #[vtk(cell, lazy)]
+        rank: vtk::LazyArray,
+        #[vtk(skip)]
+        missing: Vec<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    pub struct SkippedFields {
+        rho: vtk::Scalar3D<f64>,
+        #[vtk(skip)]
+        scratch: Vec<f64>,
+    }
+
+    type Domain = vtk::Rectilinear3D<f64, vtk::Binary>;
+
+    fn check_lazy(bytes: &[u8], vtk: vtk::VtkData<Domain, LazyFields>) {
+        assert_eq!(vtk.data.rho.get((1, 1, 0)), Some(&1.25));
+        assert!(vtk.data.missing.is_empty());
+
+        let velocity = &vtk.data.velocity;
+        assert_eq!(velocity.name(), "velocity");
+        assert_eq!(velocity.description().header.components(), 3);
+
+        let values = velocity.load(bytes).unwrap();
+        assert_eq!(values.precision, Precision::Float32);
+        assert_eq!(values.tuples(), NX * NY * NZ);
+        assert_eq!(&values.to_vec::<f64>().unwrap()[3..6], &[1., -1., 0.5]);
+
+        let velocity: vtk::Vector3D<f64> = velocity.load_as(bytes, &vtk.domain.spans).unwrap();
+        assert_eq!(velocity.dim(), (3, NX, NY, NZ));
+
+        let rank = vtk.data.rank.load(bytes).unwrap();
+        assert_eq!(rank.to_vec::<i32>().unwrap(), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn lazy_arrays() {
+        for path in [
+            "./static/vtk_appended_raw.vtr",
+            "./static/vtk_ascii.vtr",
+            "./static/vtk_binary.vtr",
+        ] {
+            let bytes = std::fs::read(path).unwrap();
+            let vtk =
+                vtk::parse::parse_xml_document::<_, _, _, vtk::Mesh3D<f64, vtk::Binary>, _, _>(
+                    &bytes,
+                )
+                .unwrap();
+            check_lazy(&bytes, vtk);
+        }
+    }
+
+    #[test]
+    fn lazy_array_missing_from_other_file() {
+        let bytes = std::fs::read("./static/vtk_appended_raw.vtr").unwrap();
+        let vtk: vtk::VtkData<Domain, LazyFields> =
+            vtk::parse::parse_xml_document::<_, _, _, vtk::Mesh3D<f64, vtk::Binary>, _, _>(&bytes)
+                .unwrap();
+
+        let other = br#"<VTKFile type="RectilinearGrid" version="1.0" byte_order="LittleEndian">
+            <RectilinearGrid WholeExtent="0 1 0 1 0 1"><Piece Extent="0 1 0 1 0 1">
+            <PointData></PointData>
+            </Piece></RectilinearGrid></VTKFile>"#;
+        assert!(vtk.data.velocity.load(other).is_err());
+    }
+
+    #[test]
+    fn skipped_fields_are_not_written() {
+        let mesh = vtk::Mesh3D::<f64, vtk::Binary>::new(
+            ndarray::Array1::linspace(0., 1., NX).to_vec(),
+            ndarray::Array1::linspace(0., 1., NY).to_vec(),
+            ndarray::Array1::linspace(0., 1., NZ).to_vec(),
+        );
+        let domain = vtk::Rectilinear3D::new(mesh, vtk::Spans3D::new(NX, NY, NZ));
+        let data = SkippedFields {
+            rho: vtk::Scalar3D::new(ndarray::Array3::from_elem((NX, NY, NZ), 2.)),
+            scratch: vec![1., 2., 3.],
+        };
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk::VtkData::new(domain, data.clone())).unwrap();
+
+        let summary = vtk::parse_summary(&file).unwrap();
+        let point_data = summary.pieces[0].section("PointData").unwrap();
+        let names: Vec<_> = point_data.arrays.iter().map(|array| &array.name).collect();
+        assert_eq!(names, ["rho"]);
+
+        let vtk: vtk::VtkData<Domain, SkippedFields> =
+            vtk::parse::parse_xml_document::<_, _, _, vtk::Mesh3D<f64, vtk::Binary>, _, _>(&file)
+                .unwrap();
+        assert_eq!(vtk.data.rho, data.rho);
+        assert!(vtk.data.scratch.is_empty());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn mapped_file() {
+        let path = std::path::Path::new("./static/vtk_appended_raw.vtr");
+        let file = vtk::MappedFile::open(path).unwrap();
+        let vtk: vtk::VtkData<Domain, LazyFields> = file.parse().unwrap();
+        check_lazy(&file, vtk);
+
+        let vtk: vtk::VtkData<Domain, LazyFields> = vtk::read_vtk_mmap(path).unwrap();
+        assert_eq!(vtk.data.rank.name(), "rank");
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn mapped_file_missing() {
+        assert!(
+            vtk::MappedFile::open(std::path::Path::new("./static/does_not_exist.vtr")).is_err()
+        );
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(feature = "derive")]
+mod multiblock {
+    //! `.vtm` datasets of blocks that are written to separate files
+    use vtk::prelude::*;
+
+    use vtk::{Block, Mesh3D, MultiBlock, Rectilinear3D, Spans3D};
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Fields {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    /// a block of `n` points in every direction, with a different spacing for every resolution
+    fn zone(n: usize, value: f64) -> VtkData<Rectilinear3D<f64, vtk::Binary>, Fields> {
+        let locations: Vec<f64> = (0..n).map(|i| i as f64 / (n - 1) as f64).collect();
+        let mesh = Mesh3D::new(locations.clone(), locations.clone(), locations);
+        let domain = Rectilinear3D::new(mesh, Spans3D::new(n, n, n));
+        let rho = ndarray::Array3::from_elem((n, n, n), value);
+
+        VtkData::new(
+            domain,
+            Fields {
+                rho: vtk::Scalar3D::new(rho),
+            },
+        )
+    }
+
+    fn description() -> MultiBlock {
+        let fluid = MultiBlock::new()
+            .with_dataset("coarse", "multiblock/coarse.vtr")
+            .with_dataset("fine", "multiblock/fine.vtr");
+
+        MultiBlock::new()
+            .with_block("fluid", fluid)
+            .with_dataset("solid", "multiblock/solid.vtr")
+    }
+
+    #[test]
+    fn write_vtm() {
+        let mut file = Vec::new();
+        description().write(&mut file).unwrap();
+        let file = String::from_utf8(file).unwrap();
+
+        let expected = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<VTKFile type="vtkMultiBlockDataSet" version="1.0" byte_order="LittleEndian" header_type="UInt64">"#,
+            r#"<vtkMultiBlockDataSet>"#,
+            r#"<Block index="0" name="fluid">"#,
+            r#"<DataSet index="0" name="coarse" file="multiblock/coarse.vtr" />"#,
+            r#"<DataSet index="1" name="fine" file="multiblock/fine.vtr" />"#,
+            r#"</Block>"#,
+            r#"<DataSet index="1" name="solid" file="multiblock/solid.vtr" />"#,
+            r#"</vtkMultiBlockDataSet>"#,
+            r#"</VTKFile>"#,
+        );
+        assert_eq!(file, expected);
+
+        assert_eq!(vtk::parse_vtm(file.as_bytes()).unwrap(), description());
+    }
+
+    #[test]
+    fn parse_paraview_vtm() {
+        // as written by ParaView, with an empty block and a dataset without a file
+        let file = r#"<?xml version="1.0"?>
+<VTKFile type="vtkMultiBlockDataSet" version="1.0" byte_order="LittleEndian" header_type="UInt32" compressor="vtkZLibDataCompressor">
+  <vtkMultiBlockDataSet>
+    <Block index="0" name="fluid">
+      <DataSet index="0" name="zone" file="case/case_0_0.vtr">
+      </DataSet>
+      <DataSet index="1" name="empty"/>
+      <Block index="2"/>
+    </Block>
+    <DataSet index="1" file="case/case_1.vtr"/>
+  </vtkMultiBlockDataSet>
+</VTKFile>
+"#;
+        let description = vtk::parse_vtm(file.as_bytes()).unwrap();
+
+        assert_eq!(
+            description.blocks(),
+            [
+                Block::MultiBlock {
+                    name: "fluid".to_string(),
+                    blocks: vec![
+                        Block::DataSet {
+                            name: "zone".to_string(),
+                            data: "case/case_0_0.vtr".to_string(),
+                        },
+                        Block::MultiBlock {
+                            name: String::new(),
+                            blocks: Vec::new(),
+                        },
+                    ],
+                },
+                Block::DataSet {
+                    name: String::new(),
+                    data: "case/case_1.vtr".to_string(),
+                },
+            ]
+        );
+    }
+
+    /// the name of the block of an [`UnclosedBlock`](vtk::ParseError::UnclosedBlock) error
+    fn unclosed_block_name(file: &str) -> String {
+        match vtk::parse_vtm(file.as_bytes()) {
+            Err(vtk::Error::Nom(vtk::ParseError::UnclosedBlock { block, offset })) => {
+                assert!(offset.is_some());
+                block
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unclosed_block() {
+        let file = r#"<VTKFile type="vtkMultiBlockDataSet"><vtkMultiBlockDataSet><Block index="0"></vtkMultiBlockDataSet></VTKFile>"#;
+
+        // unnamed blocks are described by their index
+        assert_eq!(unclosed_block_name(file), "0");
+    }
+
+    #[test]
+    fn malformed_nesting() {
+        // `fluid` is closed by the `</Block>` of `fine`, so `outer` is never closed
+        let file = r#"<VTKFile type="vtkMultiBlockDataSet">
+            <vtkMultiBlockDataSet>
+                <Block index="0" name="outer">
+                    <Block index="0" name="fluid">
+                        <Block index="0" name="fine">
+                            <DataSet index="0" name="a" file="a.vtr"/>
+                    </Block>
+                </Block>
+            </vtkMultiBlockDataSet>
+        </VTKFile>"#;
+
+        let block = unclosed_block_name(file);
+        assert_eq!(block, "outer");
+
+        let message = vtk::parse_vtm(file.as_bytes()).unwrap_err().to_string();
+        assert!(message.contains("`outer`"));
+    }
+
+    #[test]
+    fn unexpected_closing_block() {
+        let file = r#"<VTKFile type="vtkMultiBlockDataSet"><vtkMultiBlockDataSet><DataSet index="0" file="a.vtr"/></Block></vtkMultiBlockDataSet></VTKFile>"#;
+
+        match vtk::parse_vtm(file.as_bytes()) {
+            Err(vtk::Error::Nom(vtk::ParseError::Nom { offset, .. })) => {
+                assert_eq!(offset, file.find("/Block"))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_vtm() {
+        std::fs::create_dir_all("./test_vtks/multiblock").unwrap();
+
+        let zones = [("coarse", 3, 1.), ("fine", 5, 2.), ("solid", 4, 3.)];
+        for (name, n, value) in zones {
+            let file =
+                std::fs::File::create(format!("./test_vtks/multiblock/{}.vtr", name)).unwrap();
+            vtk::write_vtk(file, zone(n, value)).unwrap();
+        }
+
+        let path = std::path::Path::new("./test_vtks/multiblock.vtm");
+        description()
+            .write(std::fs::File::create(path).unwrap())
+            .unwrap();
+
+        let blocks: Vec<Block<VtkData<Rectilinear3D<f64, vtk::Binary>, Fields>>> =
+            vtk::read_vtm(path).unwrap();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].name(), "fluid");
+        assert!(blocks[0].data().is_none());
+
+        let fluid = blocks[0].blocks();
+        assert_eq!(fluid[0].name(), "coarse");
+        assert_eq!(fluid[0].data(), Some(&zone(3, 1.)));
+        assert_eq!(fluid[1].name(), "fine");
+        assert_eq!(fluid[1].data(), Some(&zone(5, 2.)));
+
+        assert_eq!(blocks[1].name(), "solid");
+        assert!(blocks[1].blocks().is_empty());
+        assert_eq!(blocks[1].data(), Some(&zone(4, 3.)));
+    }
+}
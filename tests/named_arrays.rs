@@ -0,0 +1,136 @@
+#[cfg(feature = "derive")]
+mod named_arrays {
+    //! arrays are found by their `Name`, so files may contain arrays that the reader does not
+    //! know about, in any order
+    use vtk::prelude::*;
+
+    use vtk::Mesh3D;
+    use vtk::Rectilinear3D;
+    use vtk::Spans3D;
+
+    // the solver output, with a diagnostic field that readers do not know about
+    macro_rules! solver_output {
+        ($name:ident, $encoding:literal) => {
+            #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+            #[vtk_parse(spans = "vtk::Spans3D")]
+            #[vtk_write(encoding = $encoding)]
+            pub struct $name {
+                diagnostic: Vec<f64>,
+                velocity: vtk::Vector3D<f64>,
+                #[vtk(cell)]
+                residual: Vec<f32>,
+                #[vtk(cell)]
+                rank: Vec<i32>,
+                rho: vtk::Scalar3D<f64>,
+            }
+        };
+    }
+
+    solver_output!(Binary, "binary");
+    solver_output!(Base64, "base64");
+    solver_output!(Ascii, "ascii");
+
+    // the fields in a different order than they are written, without the diagnostic
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Reader {
+        #[vtk(cell)]
+        rank: Vec<i32>,
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Missing {
+        rho: vtk::Scalar3D<f64>,
+        pressure: Vec<f64>,
+    }
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+    const LEN: usize = NX * NY * NZ;
+    const CELLS: usize = (NX - 1) * (NY - 1) * (NZ - 1);
+
+    fn setup_domain() -> Rectilinear3D<f64, vtk::Binary> {
+        let mesh = Mesh3D::new(vec![0., 1., 2., 3.], vec![0., 1., 2.], vec![0., 1.]);
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    fn rho() -> vtk::Scalar3D<f64> {
+        let arr = ndarray::Array1::from_iter((0..LEN).map(|i| i as f64 * 0.5))
+            .into_shape((NX, NY, NZ))
+            .unwrap();
+        vtk::Scalar3D::new(arr)
+    }
+
+    fn velocity() -> vtk::Vector3D<f64> {
+        let arr = ndarray::Array1::from_iter((0..3 * LEN).map(|i| -(i as f64)))
+            .into_shape((3, NX, NY, NZ))
+            .unwrap();
+        vtk::Vector3D::new(arr)
+    }
+
+    fn rank() -> Vec<i32> {
+        (0..CELLS as i32).collect()
+    }
+
+    fn expected() -> Reader {
+        Reader {
+            rank: rank(),
+            rho: rho(),
+            velocity: velocity(),
+        }
+    }
+
+    macro_rules! write_file {
+        ($name:ident) => {{
+            let data = $name {
+                diagnostic: vec![7.; LEN],
+                velocity: velocity(),
+                residual: vec![1e-3; CELLS],
+                rank: rank(),
+                rho: rho(),
+            };
+
+            let mut file = Vec::new();
+            vtk::write_vtk(&mut file, VtkData::new(setup_domain(), data)).unwrap();
+            file
+        }};
+    }
+
+    #[test]
+    fn read_subset_binary() {
+        let file = write_file!(Binary);
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Reader> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+        assert_eq!(out.data, expected());
+    }
+
+    #[test]
+    fn read_subset_base64() {
+        let file = write_file!(Base64);
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Reader> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+        assert_eq!(out.data, expected());
+    }
+
+    #[test]
+    fn read_subset_ascii() {
+        let file = write_file!(Ascii);
+        let out: VtkData<Rectilinear3D<f64, vtk::Binary>, Reader> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+        assert_eq!(out.data, expected());
+    }
+
+    #[test]
+    fn missing_array_is_an_error() {
+        let file = write_file!(Binary);
+        let out: Result<VtkData<Rectilinear3D<f64, vtk::Binary>, Missing>, _> =
+            vtk::parse::parse_xml_document(&file);
+        assert!(out.is_err());
+    }
+}
@@ -0,0 +1,274 @@
+#[cfg(feature = "derive")]
+mod parallel {
+    //! writing each rank of a decomposed domain to its own piece
+    use vtk::prelude::*;
+
+    use vtk::{Mesh3D, ParallelPiece, ParallelRectilinear, Precision, Rectilinear3D, Spans3D};
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Fields {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f32>,
+        #[vtk(cell)]
+        rank: Vec<i32>,
+    }
+
+    const NX: usize = 8;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+
+    fn whole_spans() -> Spans3D {
+        Spans3D::new(NX, NY, NZ)
+    }
+
+    /// the spans of each of the two ranks, which share the points at x = 4
+    fn rank_spans(rank: usize) -> Spans3D {
+        let (x_start, x_end) = if rank == 0 { (1, 4) } else { (4, NX) };
+
+        Spans3D {
+            x_start,
+            x_end,
+            ..whole_spans()
+        }
+    }
+
+    fn value(x: usize, y: usize, z: usize) -> f64 {
+        (x + 10 * y + 100 * z) as f64
+    }
+
+    fn rank_piece(rank: usize) -> VtkData<ParallelPiece<Rectilinear3D<f64, vtk::Binary>>, Fields> {
+        let spans = rank_spans(rank);
+        let (nx, ny, nz) = (spans.x_len(), spans.y_len(), spans.z_len());
+        let x0 = spans.x_start - 1;
+
+        let mesh = Mesh3D::new(
+            (spans.x_start..=spans.x_end)
+                .map(|x| x as f64 * 0.5)
+                .collect(),
+            (0..ny).map(|y| y as f64).collect(),
+            (0..nz).map(|z| z as f64).collect(),
+        );
+        let domain = ParallelPiece::new(Rectilinear3D::new(mesh, spans), whole_spans());
+
+        // every value is a function of its global index, so the stitched arrays are known
+        let rho = ndarray::Array3::from_shape_fn((nx, ny, nz), |(x, y, z)| value(x0 + x, y, z));
+        let velocity = ndarray::Array4::from_shape_fn((3, nx, ny, nz), |(c, x, y, z)| {
+            ((c as f64 - 1.) * value(x0 + x, y, z)) as f32
+        });
+        let rank = (0..nz - 1)
+            .flat_map(|z| (0..ny - 1).flat_map(move |y| (0..nx - 1).map(move |x| (x, y, z))))
+            .map(|(x, y, z)| value(x0 + x, y, z) as i32)
+            .collect();
+
+        let data = Fields {
+            rho: vtk::Scalar3D::new(rho),
+            velocity: vtk::Vector3D::new(velocity),
+            rank,
+        };
+
+        VtkData::new(domain, data)
+    }
+
+    #[test]
+    fn piece_extents() {
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, rank_piece(1)).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(file.contains(r#"<RectilinearGrid WholeExtent="1 8 1 3 1 2">"#));
+        assert!(file.contains(r#"<Piece Extent="4 8 1 3 1 2">"#));
+    }
+
+    #[test]
+    fn read_piece() {
+        let mut file = Vec::new();
+        let piece = rank_piece(1);
+        vtk::write_vtk(&mut file, piece.clone()).unwrap();
+
+        let vtk: VtkData<Rectilinear3D<f64, vtk::Binary>, Fields> =
+            vtk::parse::parse_xml_document::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file)
+                .unwrap();
+
+        // pieces are read with their own extent
+        assert_eq!(vtk.domain, piece.domain.domain);
+        assert_eq!(vtk.data, piece.data);
+
+        let summary = vtk::parse_summary(&file).unwrap();
+        assert_eq!(summary.whole_extent, Some(whole_spans()));
+        assert_eq!(summary.pieces[0].extent, Some(rank_spans(1)));
+    }
+
+    #[test]
+    fn declarations() {
+        let piece = rank_piece(0);
+        let declarations = piece.data.array_declarations();
+
+        assert_eq!(
+            declarations,
+            vec![
+                vtk::ArrayDeclaration::new("rho", Precision::Float64, 1, false),
+                vtk::ArrayDeclaration::new("velocity", Precision::Float32, 3, false),
+                vtk::ArrayDeclaration::new("rank", Precision::Int32, 1, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn pvtr() {
+        let piece = rank_piece(0);
+
+        let mut file = Vec::new();
+        ParallelRectilinear::new(&piece.domain, &piece.data)
+            .with_piece(rank_spans(0), "output_0.vtr")
+            .with_piece(rank_spans(1), "output_1.vtr")
+            .write(&mut file)
+            .unwrap();
+        let file = String::from_utf8(file).unwrap();
+
+        let expected = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<VTKFile type="PRectilinearGrid" version="1.0" byte_order="LittleEndian" header_type="UInt64">"#,
+            r#"<PRectilinearGrid WholeExtent="1 8 1 3 1 2" GhostLevel="0">"#,
+            r#"<PPointData>"#,
+            r#"<PDataArray type="Float64" Name="rho" NumberOfComponents="1" />"#,
+            r#"<PDataArray type="Float32" Name="velocity" NumberOfComponents="3" />"#,
+            r#"</PPointData>"#,
+            r#"<PCellData>"#,
+            r#"<PDataArray type="Int32" Name="rank" NumberOfComponents="1" />"#,
+            r#"</PCellData>"#,
+            r#"<PCoordinates>"#,
+            r#"<PDataArray type="Float64" Name="X" NumberOfComponents="1" />"#,
+            r#"<PDataArray type="Float64" Name="Y" NumberOfComponents="1" />"#,
+            r#"<PDataArray type="Float64" Name="Z" NumberOfComponents="1" />"#,
+            r#"</PCoordinates>"#,
+            r#"<Piece Extent="1 4 1 3 1 2" Source="output_0.vtr" />"#,
+            r#"<Piece Extent="4 8 1 3 1 2" Source="output_1.vtr" />"#,
+            r#"</PRectilinearGrid>"#,
+            r#"</VTKFile>"#,
+        );
+        assert_eq!(file, expected);
+    }
+
+    /// write both ranks and their `.pvtr` file to `./test_vtks/`
+    fn write_dataset(name: &str, pieces: &[usize]) -> std::path::PathBuf {
+        let piece = rank_piece(0);
+        let mut pvtr = ParallelRectilinear::new(&piece.domain, &piece.data);
+
+        for rank in pieces {
+            let source = format!("{}_{}.vtr", name, rank);
+            let file = std::fs::File::create(format!("./test_vtks/{}", source)).unwrap();
+            vtk::write_vtk(file, rank_piece(*rank)).unwrap();
+
+            pvtr = pvtr.with_piece(rank_spans(*rank), &source);
+        }
+
+        let path = std::path::PathBuf::from(format!("./test_vtks/{}.pvtr", name));
+        pvtr.write(std::fs::File::create(&path).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn parse_pvtr() {
+        let piece = rank_piece(0);
+        let expected = ParallelRectilinear::new(&piece.domain, &piece.data)
+            .with_piece(rank_spans(0), "output_0.vtr")
+            .with_piece(rank_spans(1), "output_1.vtr");
+
+        let mut file = Vec::new();
+        expected.write(&mut file).unwrap();
+
+        assert_eq!(vtk::parse_pvtr(&file).unwrap(), expected);
+    }
+
+    #[test]
+    fn stitch() {
+        let path = write_dataset("stitch", &[0, 1]);
+        let dataset: vtk::ParallelDataset<Fields> = vtk::read_pvtr(&path).unwrap();
+        assert_eq!(dataset.pieces.len(), 2);
+
+        let domain = dataset.domain();
+        assert_eq!(domain.spans, whole_spans());
+        let x: Vec<f64> = (1..=NX).map(|x| x as f64 * 0.5).collect();
+        assert_eq!(domain.mesh.x_locations, x);
+        assert_eq!(domain.mesh.z_locations, vec![0., 1.]);
+
+        let rho = dataset.stitch(|data| &data.rho).unwrap();
+        assert_eq!(rho.dim(), (NX, NY, NZ));
+        assert!(rho
+            .indexed_iter()
+            .all(|((x, y, z), v)| *v == value(x, y, z)));
+
+        let velocity = dataset.stitch(|data| &data.velocity).unwrap();
+        assert_eq!(velocity.dim(), (3, NX, NY, NZ));
+        assert_eq!(velocity[(0, 5, 2, 1)], -rho[(5, 2, 1)] as f32);
+        assert_eq!(velocity[(2, 3, 1, 0)], rho[(3, 1, 0)] as f32);
+
+        let rank = dataset.stitch_cells(|data| &data.rank).unwrap();
+        let expected: Vec<i32> = (0..NZ - 1)
+            .flat_map(|z| (0..NY - 1).flat_map(move |y| (0..NX - 1).map(move |x| (x, y, z))))
+            .map(|(x, y, z)| value(x, y, z) as i32)
+            .collect();
+        assert_eq!(rank, expected);
+    }
+
+    #[test]
+    fn missing_piece() {
+        let path = write_dataset("missing_piece", &[1]);
+        let error = vtk::read_pvtr::<Fields, _>(&path).unwrap_err();
+
+        assert!(matches!(
+            error,
+            vtk::Error::Nom(vtk::ParseError::IncompletePieces { .. })
+        ));
+    }
+
+    #[test]
+    fn overlapping_pieces() {
+        // the same piece listed twice covers its cells twice, even though every cell of the
+        // whole extent is covered
+        let path = write_dataset("overlapping_pieces", &[0, 1, 0]);
+        let error = vtk::read_pvtr::<Fields, _>(&path).unwrap_err();
+
+        assert!(matches!(
+            error,
+            vtk::Error::Nom(vtk::ParseError::OverlappingPieces { .. })
+        ));
+    }
+
+    #[test]
+    fn large_whole_extent() {
+        // the coverage is checked without allocating anything for the whole extent
+        let path = std::path::Path::new("./test_vtks/large_whole_extent.pvtr");
+        let file = r#"<VTKFile type="PRectilinearGrid" version="1.0">
+<PRectilinearGrid WholeExtent="0 4000000000 0 4000000000 0 4000000000">
+<Piece Extent="0 1 0 1 0 1" Source="large_whole_extent_0.vtr"/>
+</PRectilinearGrid>
+</VTKFile>"#;
+        std::fs::write(path, file).unwrap();
+
+        let error = vtk::read_pvtr::<Fields, _>(path).unwrap_err();
+        assert!(matches!(
+            error,
+            vtk::Error::Nom(vtk::ParseError::IncompletePieces { .. })
+        ));
+    }
+
+    #[test]
+    fn no_pieces() {
+        let path = write_dataset("no_pieces", &[]);
+        let error = vtk::read_pvtr::<Fields, _>(&path).unwrap_err();
+
+        assert!(matches!(
+            error,
+            vtk::Error::Nom(vtk::ParseError::IncompletePieces { .. })
+        ));
+
+        let dataset: vtk::ParallelDataset<Fields> = vtk::ParallelDataset {
+            whole_spans: whole_spans(),
+            pieces: Vec::new(),
+        };
+        assert!(dataset.stitch(|data| &data.rho).is_err());
+    }
+}
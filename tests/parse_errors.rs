@@ -0,0 +1,254 @@
+#[cfg(feature = "derive")]
+mod parse_errors {
+    //! malformed files are reported with the name of the array and the position of the error,
+    //! instead of panicking
+    use vtk::prelude::*;
+
+    use vtk::Mesh3D;
+    use vtk::ParseError;
+    use vtk::Rectilinear3D;
+    use vtk::Spans3D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    macro_rules! write_file {
+        ($name:ident) => {{
+            let mesh = Mesh3D::new(vec![0., 1.], vec![0., 1.], vec![0., 1.]);
+            let domain = Rectilinear3D::<f64, vtk::Ascii>::new(mesh, Spans3D::new(2, 2, 2));
+            let rho = vtk::Scalar3D::new(ndarray::Array3::from_elem((2, 2, 2), 1.5));
+
+            let mut file = Vec::new();
+            vtk::write_vtk(&mut file, VtkData::new(domain, $name { rho })).unwrap();
+            String::from_utf8(file).unwrap()
+        }};
+    }
+
+    /// a file with `rho` as the only appended array, whose header starts at the returned index
+    fn write_appended(compressor: Option<vtk::Compressor>) -> (Vec<u8>, usize) {
+        let mesh = Mesh3D::new(vec![0., 1.], vec![0., 1.], vec![0., 1.]);
+        let domain = Rectilinear3D::<f64, vtk::Ascii>::new(mesh, Spans3D::new(2, 2, 2));
+        let rho = vtk::Scalar3D::new(ndarray::Array3::from_elem((2, 2, 2), 1.5));
+        let vtk = VtkData::new(domain, Binary { rho });
+
+        let mut file = Vec::new();
+        match compressor {
+            Some(compressor) => vtk::write_vtk_with_options(
+                &mut file,
+                vtk,
+                vtk::WriteOptions::new().with_compressor(compressor),
+            )
+            .unwrap(),
+            None => vtk::write_vtk(&mut file, vtk).unwrap(),
+        }
+
+        let marker = br#"<AppendedData encoding="raw">_"#;
+        let start = file
+            .windows(marker.len())
+            .position(|window| window == marker)
+            .unwrap();
+
+        (file, start + marker.len())
+    }
+
+    /// replace the `UInt64` values at `start` of a file
+    fn overwrite_header(file: &mut [u8], start: usize, header: &[u64]) {
+        for (idx, value) in header.iter().enumerate() {
+            let at = start + idx * 8;
+            file[at..at + 8].copy_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    /// parse a file that is expected to be malformed
+    fn parse_error(file: impl AsRef<[u8]>) -> ParseError {
+        let out: Result<VtkData<Rectilinear3D<f64, vtk::Binary>, Ascii>, _> =
+            vtk::parse::parse_xml_document(file.as_ref());
+
+        match out {
+            Err(vtk::Error::Nom(e)) => e,
+            Err(e) => panic!("expected a parse error, got {}", e),
+            Ok(_) => panic!("malformed file was parsed"),
+        }
+    }
+
+    #[test]
+    fn invalid_number() {
+        let file = write_file!(Ascii).replacen("1.5 1.5 1.5", "1.5 1.5 oops", 1);
+
+        match parse_error(&file) {
+            ParseError::InvalidNumber {
+                value,
+                array,
+                offset,
+            } => {
+                assert_eq!((value.as_str(), array.as_str()), ("oops", "rho"));
+                assert_eq!(offset, file.find("oops"));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_components() {
+        let file = write_file!(Ascii).replace(
+            r#"NumberOfComponents="1" Name="rho""#,
+            r#"NumberOfComponents="one" Name="rho""#,
+        );
+
+        match parse_error(&file) {
+            ParseError::InvalidNumber {
+                value,
+                array,
+                offset,
+            } => {
+                assert_eq!((value.as_str(), array.as_str()), ("one", "rho"));
+                assert!(offset.is_some());
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn missing_attribute() {
+        let file = write_file!(Ascii).replace(r#"Name="rho" format="ascii""#, r#"Name="rho""#);
+
+        match parse_error(&file) {
+            ParseError::MissingAttribute {
+                attribute,
+                array,
+                offset,
+            } => {
+                assert_eq!((attribute.as_str(), array.as_str()), ("format", "rho"));
+                assert!(offset.is_some());
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn wrong_array_length() {
+        let file = write_file!(Ascii).replacen("1.5 ", "", 1);
+
+        match parse_error(&file) {
+            ParseError::ArrayLength {
+                array,
+                expected,
+                found,
+            } => {
+                assert_eq!(array, "rho");
+                assert_eq!((expected, found), (8, 7));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_extent() {
+        let file =
+            write_file!(Ascii).replace(r#"WholeExtent="1 2 1 2 1 2""#, r#"WholeExtent="1 2 1 2""#);
+
+        match parse_error(&file) {
+            ParseError::InvalidExtent { extent, offset } => {
+                assert_eq!(extent, "1 2 1 2");
+                assert!(offset.is_some());
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn missing_array() {
+        let file = write_file!(Ascii).replace(r#"Name="rho""#, r#"Name="pressure""#);
+
+        match parse_error(&file) {
+            ParseError::MissingArray { array, .. } => assert_eq!(array, "rho"),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_base64() {
+        let file = write_file!(Base64);
+        let start = file.find(r#"format="binary">"#).unwrap();
+        let invalid = start + file[start..].find("+D8").unwrap();
+        let file = format!("{}*{}", &file[..invalid], &file[invalid + 1..]);
+
+        match parse_error(&file) {
+            ParseError::InvalidBase64 { array, offset } => {
+                assert_eq!(array, "rho");
+                assert_eq!(offset, Some(invalid));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn errors_name_the_array() {
+        let file = write_file!(Ascii).replacen("1.5 1.5 1.5", "1.5 1.5 oops", 1);
+        let message = vtk::Error::Nom(parse_error(&file)).to_string();
+
+        assert!(message.contains("`oops`"));
+        assert!(message.contains("`rho`"));
+        assert!(message.contains(&format!("at byte {}", file.find("oops").unwrap())));
+    }
+
+    #[test]
+    fn oversized_length_header() {
+        // the length of an array is read from the file, and must not overflow when the end of
+        // the array is found from it
+        for length in [u64::MAX, u64::MAX - 7, 1 << 40] {
+            let (mut file, start) = write_appended(None);
+            overwrite_header(&mut file, start, &[length]);
+
+            match parse_error(&file) {
+                ParseError::Nom { extra_info, .. } => {
+                    assert_eq!(extra_info, "an array is longer than the appended section")
+                }
+                other => panic!("unexpected error {:?}", other),
+            }
+        }
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn oversized_compression_header() {
+        let (file, start) = write_appended(Some(vtk::Compressor::ZLib));
+        let compressed_size = u64::from_le_bytes(file[start + 24..start + 32].try_into().unwrap());
+
+        // [number of blocks, block size, last block size, compressed sizes ...]
+        let headers = [
+            vec![u64::MAX, 32768, 0],
+            vec![1 << 40, 1 << 30, 0],
+            vec![1, 1 << 40, 0, compressed_size],
+            vec![1, 32768, 0, u64::MAX],
+        ];
+
+        for header in headers {
+            let mut file = file.clone();
+            overwrite_header(&mut file, start, &header);
+
+            match parse_error(&file) {
+                ParseError::InvalidCompression { array, .. } => assert_eq!(array, "rho"),
+                other => panic!("unexpected error {:?} for the header {:?}", other, header),
+            }
+        }
+    }
+}
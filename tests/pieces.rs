@@ -0,0 +1,409 @@
+#[cfg(feature = "derive")]
+mod pieces {
+    //! writing several pieces of a domain to a single file
+    use vtk::prelude::*;
+
+    use vtk::{Mesh3D, ParallelPiece, Rectilinear3D, Spans3D};
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Fields {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f32>,
+        #[vtk(cell)]
+        block: Vec<i32>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct AsciiFields {
+        rho: vtk::Scalar3D<f64>,
+    }
+
+    #[derive(vtk::ParseArray)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    pub struct LazyFields {
+        #[vtk(lazy)]
+        rho: vtk::LazyArray,
+        #[vtk(cell, lazy)]
+        block: vtk::LazyArray,
+    }
+
+    #[derive(vtk::ParseArray)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    pub struct LazyRho {
+        #[vtk(lazy)]
+        rho: vtk::LazyArray,
+    }
+
+    const NX: usize = 8;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+
+    fn whole_spans() -> Spans3D {
+        Spans3D::new(NX, NY, NZ)
+    }
+
+    /// the spans of each of the two blocks, which share the points at x = 3
+    fn block_spans(block: usize) -> Spans3D {
+        let (x_start, x_end) = if block == 0 { (1, 3) } else { (3, NX) };
+
+        Spans3D {
+            x_start,
+            x_end,
+            ..whole_spans()
+        }
+    }
+
+    fn value(x: usize, y: usize, z: usize) -> f64 {
+        (x + 10 * y + 100 * z) as f64
+    }
+
+    fn block_domain<E>(block: usize) -> ParallelPiece<Rectilinear3D<f64, E>> {
+        let spans = block_spans(block);
+
+        let mesh = Mesh3D::new(
+            (spans.x_start..=spans.x_end).map(|x| x as f64).collect(),
+            (0..spans.y_len()).map(|y| y as f64).collect(),
+            (0..spans.z_len()).map(|z| z as f64).collect(),
+        );
+
+        ParallelPiece::new(Rectilinear3D::new(mesh, spans), whole_spans())
+    }
+
+    fn block_rho(block: usize) -> vtk::Scalar3D<f64> {
+        let spans = block_spans(block);
+        let x0 = spans.x_start - 1;
+
+        vtk::Scalar3D::new(ndarray::Array3::from_shape_fn(
+            (spans.x_len(), spans.y_len(), spans.z_len()),
+            |(x, y, z)| value(x0 + x, y, z),
+        ))
+    }
+
+    fn block(block: usize) -> VtkData<ParallelPiece<Rectilinear3D<f64, vtk::Binary>>, Fields> {
+        let spans = block_spans(block);
+        let (nx, ny, nz) = (spans.x_len(), spans.y_len(), spans.z_len());
+
+        let velocity = ndarray::Array4::from_shape_fn((3, nx, ny, nz), |(c, x, y, z)| {
+            (c * 1000 + block * 100 + x + 10 * y + 100 * z) as f32
+        });
+        let cells = (nx - 1) * (ny - 1) * (nz - 1);
+
+        let data = Fields {
+            rho: block_rho(block),
+            velocity: vtk::Vector3D::new(velocity),
+            block: vec![block as i32; cells],
+        };
+
+        VtkData::new(block_domain(block), data)
+    }
+
+    #[test]
+    fn write_pieces() {
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(
+            &mut file,
+            vec![block(0), block(1)],
+            vtk::WriteOptions::new(),
+        )
+        .unwrap();
+
+        let summary = vtk::parse_summary(&file).unwrap();
+        assert_eq!(summary.whole_extent, Some(whole_spans()));
+        assert_eq!(summary.pieces.len(), 2);
+        assert_eq!(summary.pieces[0].extent, Some(block_spans(0)));
+        assert_eq!(summary.pieces[1].extent, Some(block_spans(1)));
+
+        // the arrays of the second piece follow every array of the first piece
+        let headers: Vec<_> = summary
+            .pieces
+            .iter()
+            .map(|piece| piece.section("PointData").unwrap().arrays[0].header)
+            .collect();
+        assert_ne!(headers[0], headers[1]);
+    }
+
+    #[test]
+    fn read_pieces() {
+        let blocks = vec![block(0), block(1)];
+
+        let path = std::path::Path::new("./test_vtks/pieces.vtr");
+        let file = std::fs::File::create(path).unwrap();
+        vtk::write_vtk_pieces(file, blocks.clone(), vtk::WriteOptions::new()).unwrap();
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, Fields>> =
+            vtk::read_vtk_pieces(path).unwrap();
+
+        assert_eq!(pieces.len(), 2);
+
+        // every piece is read with its own extent
+        for (piece, expected) in pieces.iter().zip(&blocks) {
+            assert_eq!(piece.domain, expected.domain.domain);
+            assert_eq!(piece.data, expected.data);
+        }
+    }
+
+    #[test]
+    fn read_big_endian_pieces() {
+        let blocks = vec![block(0), block(1)];
+
+        let options = vtk::WriteOptions::new().with_byte_order(vtk::ByteOrder::BigEndian);
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(&mut file, blocks.clone(), options).unwrap();
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, Fields>> =
+            vtk::parse::parse_xml_pieces::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file).unwrap();
+
+        for (piece, expected) in pieces.iter().zip(&blocks) {
+            assert_eq!(piece.data, expected.data);
+        }
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn read_compressed_pieces() {
+        let blocks = vec![block(0), block(1)];
+
+        let options = vtk::WriteOptions::new()
+            .with_compressor(vtk::Compressor::ZLib)
+            .with_byte_order(vtk::ByteOrder::BigEndian);
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(&mut file, blocks.clone(), options).unwrap();
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, Fields>> =
+            vtk::parse::parse_xml_pieces::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file).unwrap();
+
+        for (piece, expected) in pieces.iter().zip(&blocks) {
+            assert_eq!(piece.domain, expected.domain.domain);
+            assert_eq!(piece.data, expected.data);
+        }
+    }
+
+    #[test]
+    fn read_ascii_pieces() {
+        let blocks: Vec<VtkData<ParallelPiece<Rectilinear3D<f64, vtk::Ascii>>, _>> = (0..2)
+            .map(|block| {
+                VtkData::new(
+                    block_domain(block),
+                    AsciiFields {
+                        rho: block_rho(block),
+                    },
+                )
+            })
+            .collect();
+
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(&mut file, blocks.clone(), vtk::WriteOptions::new()).unwrap();
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, AsciiFields>> =
+            vtk::parse::parse_xml_pieces::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file).unwrap();
+
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].data, blocks[0].data);
+        assert_eq!(pieces[1].data, blocks[1].data);
+        assert_eq!(pieces[1].domain.spans, block_spans(1));
+    }
+
+    #[test]
+    fn lazy_pieces() {
+        // each lazy array is loaded from its own piece
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(
+            &mut file,
+            vec![block(0), block(1)],
+            vtk::WriteOptions::new(),
+        )
+        .unwrap();
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, LazyFields>> =
+            vtk::parse::parse_xml_pieces::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file).unwrap();
+
+        for (idx, piece) in pieces.iter().enumerate() {
+            let rho: vtk::Scalar3D<f64> =
+                piece.data.rho.load_as(&file, &piece.domain.spans).unwrap();
+            assert_eq!(rho, block_rho(idx));
+
+            let block = piece.data.block.load(&file).unwrap();
+            assert!(block
+                .to_vec::<i32>()
+                .unwrap()
+                .iter()
+                .all(|&value| value == idx as i32));
+        }
+
+        let blocks: Vec<_> = (0..2)
+            .map(|block| {
+                VtkData::new(
+                    block_domain::<vtk::Ascii>(block),
+                    AsciiFields {
+                        rho: block_rho(block),
+                    },
+                )
+            })
+            .collect();
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(&mut file, blocks, vtk::WriteOptions::new()).unwrap();
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, LazyRho>> =
+            vtk::parse::parse_xml_pieces::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file).unwrap();
+
+        let rho: vtk::Scalar3D<f64> = pieces[1]
+            .data
+            .rho
+            .load_as(&file, &pieces[1].domain.spans)
+            .unwrap();
+        assert_eq!(rho, block_rho(1));
+    }
+
+    #[test]
+    fn single_piece() {
+        let expected = block(1);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, expected.clone()).unwrap();
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, Fields>> =
+            vtk::parse::parse_xml_pieces::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file).unwrap();
+
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].domain, expected.domain.domain);
+        assert_eq!(pieces[0].data, expected.data);
+    }
+
+    #[test]
+    fn no_pieces() {
+        let blocks: Vec<VtkData<ParallelPiece<Rectilinear3D<f64, vtk::Binary>>, Fields>> =
+            Vec::new();
+
+        let error =
+            vtk::write_vtk_pieces(Vec::new(), blocks, vtk::WriteOptions::new()).unwrap_err();
+        assert!(matches!(error, vtk::Error::Io(_)));
+    }
+
+    fn ascii_block(
+        block: usize,
+    ) -> VtkData<ParallelPiece<Rectilinear3D<f64, vtk::Ascii>>, AsciiFields> {
+        VtkData::new(
+            block_domain(block),
+            AsciiFields {
+                rho: block_rho(block),
+            },
+        )
+    }
+
+    #[test]
+    fn whole_extent_contains_every_piece() {
+        // pieces that are not `ParallelPiece`s only know their own extent
+        let blocks: Vec<_> = (0..2)
+            .map(|block| {
+                let piece = ascii_block(block);
+                VtkData::new(piece.domain.domain, piece.data)
+            })
+            .collect();
+
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(&mut file, blocks, vtk::WriteOptions::new()).unwrap();
+
+        let summary = vtk::parse_summary(&file).unwrap();
+        assert_eq!(summary.whole_extent, Some(whole_spans()));
+    }
+
+    #[test]
+    fn field_data_of_pieces() {
+        let mut blocks = vec![ascii_block(0), ascii_block(1)];
+        for block in blocks.iter_mut() {
+            block.field_data.insert("TimeValue", 0.5);
+        }
+
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(&mut file, blocks.clone(), vtk::WriteOptions::new()).unwrap();
+        let text = String::from_utf8_lossy(&file);
+        assert_eq!(text.matches("<FieldData>").count(), 1);
+
+        let pieces: Vec<VtkData<Rectilinear3D<f64, vtk::Binary>, AsciiFields>> =
+            vtk::parse::parse_xml_pieces::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file).unwrap();
+        assert!(pieces
+            .iter()
+            .all(|piece| piece.field_data == blocks[0].field_data));
+
+        // field data that differs between pieces does not describe the whole file
+        blocks[1].field_data.insert("TimeValue", 1.5);
+        let error =
+            vtk::write_vtk_pieces(Vec::new(), blocks, vtk::WriteOptions::new()).unwrap_err();
+        assert!(matches!(error, vtk::Error::Io(_)));
+    }
+
+    #[test]
+    fn error_offsets_of_later_pieces() {
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(
+            &mut file,
+            vec![ascii_block(0), ascii_block(1)],
+            vtk::WriteOptions::new(),
+        )
+        .unwrap();
+        let mut file = String::from_utf8(file).unwrap();
+
+        // replace the first value of the second piece
+        let second_piece = file.rfind("<Piece").unwrap();
+        let values = second_piece + file[second_piece..].find(r#"format="ascii">"#).unwrap() + 15;
+        let value_end = values + file[values..].find(' ').unwrap();
+        file.replace_range(values..value_end, "oops");
+
+        let error = vtk::parse::parse_xml_pieces::<
+            Rectilinear3D<f64, vtk::Binary>,
+            _,
+            AsciiFields,
+            Mesh3D<f64, vtk::Binary>,
+            _,
+            _,
+        >(file.as_bytes())
+        .unwrap_err();
+
+        match error {
+            vtk::Error::Nom(vtk::ParseError::InvalidNumber { value, offset, .. }) => {
+                assert_eq!(value, "oops");
+                assert_eq!(offset, Some(values));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn image_data_pieces() {
+        // the origin and spacing of the pieces are attributes of the dataset element
+        let spacing = [0.5, 1., 2.];
+        let blocks: Vec<_> = (0..2)
+            .map(|block| {
+                let spans = block_spans(block);
+                let origin = [(spans.x_start - 1) as f64 * spacing[0], -1., 3.];
+                let mesh = vtk::UniformMesh3D::new(origin, spacing);
+
+                VtkData::new(
+                    vtk::Uniform3D::new(mesh, spans),
+                    AsciiFields {
+                        rho: block_rho(block),
+                    },
+                )
+            })
+            .collect();
+
+        let mut file = Vec::new();
+        vtk::write_vtk_pieces(&mut file, blocks.clone(), vtk::WriteOptions::new()).unwrap();
+
+        let pieces = vtk::parse::parse_xml_pieces::<
+            vtk::Uniform3D<f64>,
+            _,
+            AsciiFields,
+            vtk::UniformMesh3D<f64>,
+            _,
+            _,
+        >(&file)
+        .unwrap();
+
+        assert_eq!(pieces, blocks);
+    }
+}
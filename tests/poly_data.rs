@@ -0,0 +1,225 @@
+#[cfg(feature = "derive")]
+mod poly_data {
+    use vtk::prelude::*;
+
+    use vtk::PolyData;
+    use vtk::PolyMesh;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::PolySpans")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        velocity: Vec<[f64; 3]>,
+        diameter: Vec<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::PolySpans")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        velocity: Vec<[f64; 3]>,
+        diameter: Vec<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::PolySpans")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        velocity: Vec<[f64; 3]>,
+        diameter: Vec<f64>,
+    }
+
+    const NUM_POINTS: usize = 8;
+
+    fn points() -> Vec<[f64; 3]> {
+        (0..NUM_POINTS)
+            .map(|i| [i as f64, (i % 2) as f64, 1.0 + (i / 4) as f64])
+            .collect()
+    }
+
+    fn velocity() -> Vec<[f64; 3]> {
+        (0..NUM_POINTS)
+            .map(|i| [i as f64 + 1., 2. * i as f64 + 1., -(i as f64) - 1.])
+            .collect()
+    }
+
+    fn diameter() -> Vec<f64> {
+        (0..NUM_POINTS).map(|i| 0.1 * (i + 1) as f64).collect()
+    }
+
+    /// particles
+    fn particle_mesh<T>() -> PolyMesh<f64, T> {
+        let mut mesh = PolyMesh::new(points());
+
+        for i in 0..NUM_POINTS as i64 {
+            mesh.verts.push(&[i]);
+        }
+
+        mesh
+    }
+
+    /// a streamline, a surface made of polygons and a triangle strip
+    fn mixed_mesh<T>() -> PolyMesh<f64, T> {
+        let mut mesh = PolyMesh::new(points());
+
+        mesh.lines.push(&[0, 1, 2, 3]);
+        mesh.polys.push(&[0, 1, 5, 4]);
+        mesh.polys.push(&[1, 2, 6]);
+        mesh.strips.push(&[2, 3, 6, 7]);
+
+        mesh
+    }
+
+    #[test]
+    fn write_particles() {
+        let domain = PolyData::new(particle_mesh::<vtk::Binary>());
+        let data = Binary {
+            velocity: velocity(),
+            diameter: diameter(),
+        };
+        let vtk = VtkData::new(domain, data);
+
+        let file = std::fs::File::create("./test_vtks/particles.vtp").unwrap();
+        vtk::write_vtk(file, vtk).unwrap();
+    }
+
+    #[test]
+    fn writes_sections() {
+        let domain = PolyData::new(mixed_mesh::<vtk::Ascii>());
+        let data = Ascii {
+            velocity: velocity(),
+            diameter: diameter(),
+        };
+        let vtk = VtkData::new(domain, data);
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(file.contains(r#"<VTKFile type="PolyData""#));
+        assert!(file.contains(
+            r#"<Piece NumberOfPoints="8" NumberOfVerts="0" NumberOfLines="1" NumberOfStrips="1" NumberOfPolys="2">"#
+        ));
+        assert!(file.contains("<Lines>"));
+        assert!(file.contains("<Strips>"));
+        assert!(file.contains("<Polys>"));
+        assert!(!file.contains("<Verts>"));
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        for mesh in [particle_mesh(), mixed_mesh()] {
+            let domain = PolyData::<_, vtk::Binary>::new(mesh);
+            let data = Binary {
+                velocity: velocity(),
+                diameter: diameter(),
+            };
+            let vtk = VtkData::new(domain, data);
+            let expected = vtk.clone();
+
+            let mut file = Vec::new();
+            vtk::write_vtk(&mut file, vtk).unwrap();
+
+            let out: VtkData<PolyData<f64, vtk::Binary>, Binary> =
+                vtk::parse::parse_xml_document(&file).unwrap();
+
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        for mesh in [particle_mesh(), mixed_mesh()] {
+            let domain = PolyData::<_, vtk::Base64>::new(mesh);
+            let data = Base64 {
+                velocity: velocity(),
+                diameter: diameter(),
+            };
+            let vtk = VtkData::new(domain, data);
+            let expected = vtk.clone();
+
+            let mut file = Vec::new();
+            vtk::write_vtk(&mut file, vtk).unwrap();
+
+            let out: VtkData<PolyData<f64, vtk::Binary>, Base64> =
+                vtk::parse::parse_xml_document(&file).unwrap();
+
+            assert_eq!(out.domain.spans, expected.domain.spans);
+            assert!(out.domain.mesh == expected.domain.mesh);
+            assert_eq!(out.data, expected.data);
+        }
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        for mesh in [particle_mesh(), mixed_mesh()] {
+            let domain = PolyData::<_, vtk::Ascii>::new(mesh);
+            let data = Ascii {
+                velocity: velocity(),
+                diameter: diameter(),
+            };
+            let vtk = VtkData::new(domain, data);
+            let expected = vtk.clone();
+
+            let mut file = Vec::new();
+            vtk::write_vtk(&mut file, vtk).unwrap();
+
+            let out: VtkData<PolyData<f64, vtk::Binary>, Ascii> =
+                vtk::parse::parse_xml_document(&file).unwrap();
+
+            assert_eq!(out.domain.spans, expected.domain.spans);
+            assert!(out.domain.mesh == expected.domain.mesh);
+            assert_eq!(out.data, expected.data);
+        }
+    }
+
+    /// an ascii file of the mixed mesh with `from` replaced by `to`, which is expected to be
+    /// inconsistent
+    fn length_error(from: &str, to: &str) -> (String, usize, usize) {
+        let domain = PolyData::new(mixed_mesh::<vtk::Ascii>());
+        let data = Ascii {
+            velocity: velocity(),
+            diameter: diameter(),
+        };
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, VtkData::new(domain, data)).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        assert!(file.contains(from));
+        let file = file.replace(from, to);
+
+        let out: Result<VtkData<PolyData<f64, vtk::Binary>, Ascii>, _> =
+            vtk::parse::parse_xml_document(file.as_bytes());
+
+        match out {
+            Err(vtk::Error::Nom(vtk::ParseError::ArrayLength {
+                array,
+                expected,
+                found,
+            })) => (array, expected, found),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn inconsistent_number_of_polys() {
+        let error = length_error(r#"NumberOfPolys="2""#, r#"NumberOfPolys="3""#);
+        assert_eq!(error, ("offsets".to_string(), 3, 2));
+    }
+
+    #[test]
+    fn truncated_connectivity() {
+        // the offsets of the polys, whose last cell now ends past the end of `connectivity`
+        let error = length_error(">4 7 <", ">4 8 <");
+        assert_eq!(error, ("connectivity".to_string(), 8, 7));
+    }
+
+    #[test]
+    fn missing_offsets() {
+        let domain = PolyData::<_, vtk::Binary>::new(mixed_mesh());
+        let mut writer = vtk::EventWriter::new(Vec::new());
+
+        let result = domain.write_mesh_header(&mut writer, &[0, 8], vtk::ByteOrder::LittleEndian);
+        assert!(matches!(result, Err(vtk::Error::Io(_))));
+    }
+}
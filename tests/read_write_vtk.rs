@@ -49,26 +49,10 @@ mod inner {
             vec![0., 1., 2., 3., 4.],
         );
 
-        let rho: Vec<_> = std::iter::repeat(0)
-            .take(length)
-            .enumerate()
-            .map(|(i, _)| i as f64)
-            .collect();
-        let u = std::iter::repeat(0)
-            .take(length)
-            .enumerate()
-            .map(|(i, _)| i as f64)
-            .collect();
-        let v = std::iter::repeat(0)
-            .take(length)
-            .enumerate()
-            .map(|(i, _)| i as f64)
-            .collect();
-        let w = std::iter::repeat(0)
-            .take(length)
-            .enumerate()
-            .map(|(i, _)| i as f64)
-            .collect();
+        let rho: Vec<_> = (0..length).map(|i| i as f64).collect();
+        let u = (0..length).map(|i| i as f64).collect();
+        let v = (0..length).map(|i| i as f64).collect();
+        let w = (0..length).map(|i| i as f64).collect();
 
         dbg!(rho.len());
 
@@ -76,9 +60,7 @@ mod inner {
 
         let domain = Rectilinear3D::new(mesh, spans);
 
-        let data = vtk::VtkData { domain, data };
-
-        data
+        vtk::VtkData::new(domain, data)
     }
 
     fn check_assertions<T, V>(
@@ -0,0 +1,267 @@
+mod stream {
+    //! writing the arrays of a file in chunks
+    use vtk::prelude::*;
+
+    use vtk::{Error, Mesh3D, Precision, Rectilinear3D, Spans3D};
+    use vtk::{StreamArray, StreamError, StreamSchema, StreamWriter};
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+    const LEN: usize = NX * NY * NZ;
+    const CELLS: usize = (NX - 1) * (NY - 1) * (NZ - 1);
+
+    fn setup_domain() -> Rectilinear3D<f64, vtk::Binary> {
+        let mesh = Mesh3D::new(vec![0., 0.5, 1., 1.5], vec![-1., 0., 1.], vec![0., 2.]);
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    fn schema() -> StreamSchema {
+        StreamSchema::new()
+            .with_array(StreamArray::new("rho", Precision::Float64, 1, LEN))
+            .with_array(StreamArray::new("rank", Precision::Int32, 1, CELLS).cell())
+    }
+
+    fn setup_writer() -> StreamWriter<Vec<u8>> {
+        StreamWriter::new(Vec::new(), VtkData::new(setup_domain(), schema())).unwrap()
+    }
+
+    fn stream_error(result: Result<impl std::fmt::Debug, Error>) -> StreamError {
+        match result {
+            Err(Error::Stream(error)) => error,
+            other => panic!("expected a stream error, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unexpected_array() {
+        let mut writer = setup_writer();
+        writer.write_values("rho", &[0.; NX * NY]).unwrap();
+
+        // rho is only half written
+        let error = stream_error(writer.write_values("rank", &[0i32; CELLS]));
+        assert_eq!(
+            error,
+            StreamError::UnexpectedArray {
+                expected: "rho".into(),
+                found: "rank".into()
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_array() {
+        let mut writer = setup_writer();
+        let error = stream_error(writer.write_values("pressure", &[0.; LEN]));
+        assert_eq!(
+            error,
+            StreamError::UnknownArray {
+                array: "pressure".into()
+            }
+        );
+
+        // arrays can not be written again once the next array is started
+        writer.write_values("rho", &[0.; LEN]).unwrap();
+        writer.write_values("rank", &[0i32; 1]).unwrap();
+        let error = stream_error(writer.write_values("rho", &[0.; 1]));
+        assert_eq!(
+            error,
+            StreamError::UnknownArray {
+                array: "rho".into()
+            }
+        );
+    }
+
+    #[test]
+    fn wrong_precision() {
+        let mut writer = setup_writer();
+        let error = stream_error(writer.write_values("rho", &[0f32; LEN]));
+        assert_eq!(
+            error,
+            StreamError::Precision {
+                array: "rho".into(),
+                expected: Precision::Float64,
+                found: Precision::Float32
+            }
+        );
+    }
+
+    #[test]
+    fn too_many_values() {
+        let mut writer = setup_writer();
+        writer.write_values("rho", &[0.; NX * NY]).unwrap();
+
+        let error = stream_error(writer.write_values("rho", &[0.; LEN]));
+        assert_eq!(
+            error,
+            StreamError::TooManyValues {
+                array: "rho".into(),
+                expected: LEN,
+                found: LEN + NX * NY
+            }
+        );
+
+        // the values that did not fit were not written
+        writer.write_values("rho", &[0.; NX * NY]).unwrap();
+    }
+
+    #[test]
+    fn missing_values() {
+        let mut writer = setup_writer();
+        writer.write_values("rho", &[0.; LEN]).unwrap();
+        writer.write_values("rank", &[0i32; CELLS - 1]).unwrap();
+
+        let error = stream_error(writer.finish());
+        assert_eq!(
+            error,
+            StreamError::MissingValues {
+                array: "rank".into(),
+                expected: CELLS,
+                found: CELLS - 1
+            }
+        );
+
+        // an array that was never started
+        let mut writer = setup_writer();
+        writer.write_values("rho", &[0.; LEN]).unwrap();
+        let error = stream_error(writer.finish());
+        assert_eq!(
+            error,
+            StreamError::MissingValues {
+                array: "rank".into(),
+                expected: CELLS,
+                found: 0
+            }
+        );
+    }
+
+    #[test]
+    fn empty_arrays() {
+        let schema = schema().with_array(StreamArray::new("empty", Precision::Float32, 3, 0));
+        let mut writer =
+            StreamWriter::new(Vec::new(), VtkData::new(setup_domain(), schema)).unwrap();
+        writer.write_values("rho", &[0.; LEN]).unwrap();
+        writer.write_values("rank", &[0i32; CELLS]).unwrap();
+
+        let file = writer.finish().unwrap();
+        let vtk = vtk::parse_dynamic(&file).unwrap();
+        assert_eq!(vtk.point_data.get("empty").unwrap().tuples(), 0);
+    }
+}
+
+#[cfg(feature = "derive")]
+mod stream_derive {
+    use vtk::prelude::*;
+
+    use vtk::{ByteOrder, Mesh3D, Precision, Rectilinear3D, Spans3D};
+    use vtk::{StreamArray, StreamSchema, StreamWriter};
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f32>,
+        #[vtk(cell)]
+        rank: Vec<i32>,
+    }
+
+    const NX: usize = 4;
+    const NY: usize = 3;
+    const NZ: usize = 2;
+    const LEN: usize = NX * NY * NZ;
+    const CELLS: usize = (NX - 1) * (NY - 1) * (NZ - 1);
+
+    fn setup_domain() -> Rectilinear3D<f64, vtk::Binary> {
+        let mesh = Mesh3D::new(vec![0., 0.5, 1., 1.5], vec![-1., 0., 1.], vec![0., 2.]);
+        Rectilinear3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    fn setup_data() -> Binary {
+        let rho = ndarray::Array1::from_iter((0..LEN).map(|i| i as f64 * 0.25))
+            .into_shape((NX, NY, NZ))
+            .unwrap();
+        let velocity = ndarray::Array1::from_iter((0..3 * LEN).map(|i| i as f32 * -0.5))
+            .into_shape((3, NX, NY, NZ))
+            .unwrap();
+
+        Binary {
+            rho: vtk::Scalar3D::new(rho),
+            velocity: vtk::Vector3D::new(velocity),
+            rank: (0..CELLS).map(|i| i as i32 - 2).collect(),
+        }
+    }
+
+    /// stream the arrays of `data` one z-plane at a time
+    fn stream(data: &Binary, byte_order: ByteOrder) -> Vec<u8> {
+        let schema = StreamSchema::new()
+            .with_array(StreamArray::new("rho", Precision::Float64, 1, LEN))
+            .with_array(StreamArray::new("velocity", Precision::Float32, 3, LEN))
+            .with_array(StreamArray::new("rank", Precision::Int32, 1, CELLS).cell());
+
+        let mut field_data = vtk::FieldData::new();
+        field_data.insert("TimeValue", 0.5);
+        let vtk = VtkData::new(setup_domain(), schema).with_field_data(field_data);
+
+        let mut writer = StreamWriter::with_byte_order(Vec::new(), vtk, byte_order).unwrap();
+
+        for z in 0..NZ {
+            let plane: Vec<f64> = (0..NY)
+                .flat_map(|y| (0..NX).map(move |x| (x, y)))
+                .map(|(x, y)| data.rho[(x, y, z)])
+                .collect();
+            writer.write_values("rho", &plane).unwrap();
+        }
+
+        for z in 0..NZ {
+            let plane: Vec<f32> = (0..NY)
+                .flat_map(|y| (0..NX).map(move |x| (x, y)))
+                .flat_map(|(x, y)| (0..3).map(move |c| data.velocity[(c, x, y, z)]))
+                .collect();
+            writer.write_values("velocity", &plane).unwrap();
+        }
+
+        let (first, second) = data.rank.split_at(CELLS / 2);
+        writer.write_values("rank", first).unwrap();
+        writer.write_values("rank", second).unwrap();
+
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn matches_write_vtk() {
+        for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+            let data = setup_data();
+            let mut field_data = vtk::FieldData::new();
+            field_data.insert("TimeValue", 0.5);
+
+            let mut expected = Vec::new();
+            let vtk = VtkData::new(setup_domain(), data.clone()).with_field_data(field_data);
+            vtk::write_vtk_with_options(
+                &mut expected,
+                vtk,
+                vtk::WriteOptions::new().with_byte_order(byte_order),
+            )
+            .unwrap();
+
+            let streamed = stream(&data, byte_order);
+            assert_eq!(
+                String::from_utf8_lossy(&streamed),
+                String::from_utf8_lossy(&expected)
+            );
+        }
+    }
+
+    #[test]
+    fn read_back() {
+        let data = setup_data();
+        let file = stream(&data, ByteOrder::LittleEndian);
+
+        let vtk: VtkData<Rectilinear3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document::<_, _, _, Mesh3D<f64, vtk::Binary>, _, _>(&file)
+                .unwrap();
+
+        assert_eq!(vtk.data, data);
+        assert_eq!(vtk.domain, setup_domain());
+    }
+}
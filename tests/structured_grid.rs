@@ -0,0 +1,145 @@
+#[cfg(feature = "derive")]
+mod structured_3d {
+    use vtk::prelude::*;
+
+    use vtk::Spans3D;
+    use vtk::Structured3D;
+    use vtk::StructuredMesh3D;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::Spans3D")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        rho: vtk::Scalar3D<f64>,
+        velocity: vtk::Vector3D<f64>,
+    }
+
+    const NX: usize = 5;
+    const NY: usize = 4;
+    const NZ: usize = 3;
+
+    /// a mesh stretched in y and sheared in x so that it cannot be
+    /// described by separable coordinate arrays
+    fn setup_points() -> vtk::Vector3D<f64> {
+        let mut points = ndarray::Array4::zeros((3, NX, NY, NZ));
+
+        for i in 0..NX {
+            for j in 0..NY {
+                for k in 0..NZ {
+                    let y = (j as f64 / NY as f64).powi(2) + 1.0;
+                    points[[0, i, j, k]] = i as f64 + 0.5 * y;
+                    points[[1, i, j, k]] = y;
+                    points[[2, i, j, k]] = k as f64 * 0.25 + 1.0;
+                }
+            }
+        }
+
+        vtk::Vector3D::new(points)
+    }
+
+    fn setup_arrays() -> (vtk::Scalar3D<f64>, vtk::Vector3D<f64>) {
+        let rho: ndarray::Array3<f64> = ndarray::Array1::range(1., (NX * NY * NZ) as f64 + 1., 1.)
+            .into_shape((NX, NY, NZ))
+            .unwrap();
+        let velocity: ndarray::Array4<f64> =
+            ndarray::Array1::range(1., (3 * NX * NY * NZ) as f64 + 1., 1.)
+                .into_shape((3, NX, NY, NZ))
+                .unwrap();
+
+        (vtk::Scalar3D::new(rho), vtk::Vector3D::new(velocity))
+    }
+
+    fn setup_domain<T>() -> Structured3D<f64, T> {
+        let mesh = StructuredMesh3D::new(setup_points());
+        Structured3D::new(mesh, Spans3D::new(NX, NY, NZ))
+    }
+
+    #[test]
+    fn write_structured_grid() {
+        let (rho, velocity) = setup_arrays();
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), Binary { rho, velocity });
+
+        let file = std::fs::File::create("./test_vtks/structured_3d.vts").unwrap();
+        vtk::write_vtk(file, vtk).unwrap();
+    }
+
+    #[test]
+    fn writes_points_element() {
+        let (rho, velocity) = setup_arrays();
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), Ascii { rho, velocity });
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(file.contains(r#"<VTKFile type="StructuredGrid""#));
+        assert!(file.contains("<StructuredGrid"));
+        assert!(file.contains("<Points>"));
+        assert!(!file.contains("<Coordinates"));
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        let (rho, velocity) = setup_arrays();
+        let vtk = VtkData::new(setup_domain::<vtk::Binary>(), Binary { rho, velocity });
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Structured3D<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        let (rho, velocity) = setup_arrays();
+        let vtk = VtkData::new(setup_domain::<vtk::Base64>(), Base64 { rho, velocity });
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Structured3D<f64, vtk::Binary>, Base64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.domain.spans, expected.domain.spans);
+        assert!(out.domain.mesh == expected.domain.mesh);
+        assert_eq!(out.data, expected.data);
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        let (rho, velocity) = setup_arrays();
+        let vtk = VtkData::new(setup_domain::<vtk::Ascii>(), Ascii { rho, velocity });
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Structured3D<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.domain.spans, expected.domain.spans);
+        assert!(out.domain.mesh == expected.domain.mesh);
+        assert_eq!(out.data, expected.data);
+    }
+}
@@ -0,0 +1,114 @@
+mod summary {
+    //! describing files from their XML elements, without reading their arrays
+    use std::path::Path;
+
+    use vtk::parse::DataArrayHeader;
+    use vtk::{GridType, Precision, Spans3D};
+
+    fn appended_raw() -> Vec<u8> {
+        std::fs::read("./static/vtk_appended_raw.vtr").unwrap()
+    }
+
+    #[test]
+    fn appended_rectilinear() {
+        let summary = vtk::read_summary(Path::new("./static/vtk_appended_raw.vtr")).unwrap();
+
+        assert_eq!(summary.grid_type, Some(GridType::RectilinearGrid));
+        assert_eq!(summary.attributes.header_type, Precision::UInt32);
+        assert!(summary.field_data.is_empty());
+
+        let extent = Spans3D::from_span_string("0 3 0 2 0 1");
+        assert_eq!(summary.whole_extent, Some(extent.clone()));
+
+        assert_eq!(summary.pieces.len(), 1);
+        let piece = &summary.pieces[0];
+        assert_eq!(piece.extent, Some(extent));
+        assert!(piece.sizes.is_empty());
+
+        let sections: Vec<_> = piece.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(sections, ["PointData", "CellData", "Coordinates"]);
+
+        let point_data = &piece.section("PointData").unwrap().arrays;
+        assert_eq!(point_data[0].name, "rho");
+        assert_eq!(point_data[0].range, Some((0., 5.75)));
+        assert_eq!(
+            point_data[1].header,
+            DataArrayHeader::AppendedBinary {
+                offset: 196,
+                components: 3,
+                precision: Precision::Float32,
+            }
+        );
+
+        let rank = &piece.section("CellData").unwrap().arrays[0];
+        assert_eq!(rank.name, "rank");
+        assert_eq!(rank.header.precision(), Precision::Int32);
+
+        assert_eq!(piece.section("Coordinates").unwrap().arrays.len(), 3);
+    }
+
+    #[test]
+    fn inline_arrays_and_field_data() {
+        let summary = vtk::read_summary(Path::new("./static/vtk_ascii.vtr")).unwrap();
+
+        assert_eq!(summary.field_data.len(), 1);
+        assert_eq!(summary.field_data[0].name, "TimeValue");
+
+        let point_data = &summary.pieces[0].section("PointData").unwrap().arrays;
+        let names: Vec<_> = point_data.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["rho", "velocity"]);
+        assert_eq!(point_data[1].header.components(), 3);
+    }
+
+    #[test]
+    fn appended_data_is_not_read() {
+        // anything in the appended section, even if it looks like an element, is ignored
+        let mut file = appended_raw();
+        file.extend_from_slice(br#"<DataArray type="Unknown" Name="bogus">"#);
+
+        let summary = vtk::parse_summary(&file).unwrap();
+        let arrays: usize = summary.pieces[0]
+            .sections
+            .iter()
+            .map(|s| s.arrays.len())
+            .sum();
+        assert_eq!(arrays, 6);
+    }
+
+    #[test]
+    fn unstructured_sizes() {
+        let file = br#"<VTKFile type="UnstructuredGrid" version="1.0" byte_order="LittleEndian">
+            <UnstructuredGrid>
+            <Piece NumberOfPoints="4" NumberOfCells="1">
+            <Points>
+                <DataArray type="Float32" NumberOfComponents="3" format="appended" offset="0"/>
+            </Points>
+            <Cells>
+                <DataArray type="Int64" Name="connectivity" format="appended" offset="56"/>
+                <DataArray type="Int64" Name="offsets" format="appended" offset="96"/>
+                <DataArray type="UInt8" Name="types" format="appended" offset="112"/>
+            </Cells>
+            </Piece>
+            <Piece NumberOfPoints="8" NumberOfCells="2">
+            </Piece>
+            </UnstructuredGrid>
+            <AppendedData encoding="raw">_"#;
+
+        let summary = vtk::parse_summary(file).unwrap();
+        assert_eq!(summary.grid_type, Some(GridType::UnstructuredGrid));
+        assert_eq!(summary.whole_extent, None);
+
+        assert_eq!(summary.pieces.len(), 2);
+        assert_eq!(summary.pieces[0].size("NumberOfPoints"), Some(4));
+        assert_eq!(summary.pieces[1].size("NumberOfCells"), Some(2));
+        assert_eq!(summary.pieces[0].extent, None);
+
+        let cells = &summary.pieces[0].section("Cells").unwrap().arrays;
+        assert_eq!(cells[2].header.precision(), Precision::UInt8);
+
+        // an array without a name
+        let points = &summary.pieces[0].section("Points").unwrap().arrays;
+        assert_eq!(points[0].name, "");
+        assert!(summary.pieces[1].sections.is_empty());
+    }
+}
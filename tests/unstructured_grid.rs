@@ -0,0 +1,238 @@
+#[cfg(feature = "derive")]
+mod unstructured {
+    use vtk::prelude::*;
+
+    use vtk::CellType;
+    use vtk::Unstructured;
+    use vtk::UnstructuredMesh;
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::UnstructuredSpans")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Binary {
+        temperature: Vec<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::UnstructuredSpans")]
+    #[vtk_write(encoding = "base64")]
+    pub struct Base64 {
+        temperature: Vec<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::UnstructuredSpans")]
+    #[vtk_write(encoding = "ascii")]
+    pub struct Ascii {
+        temperature: Vec<f64>,
+    }
+
+    #[derive(vtk::DataArray, vtk::ParseArray, Debug, Clone, PartialEq)]
+    #[vtk_parse(spans = "vtk::UnstructuredSpans")]
+    #[vtk_write(encoding = "binary")]
+    pub struct Vectors {
+        velocity: Vec<[f64; 3]>,
+        #[vtk(cell)]
+        normal: Vec<[f32; 3]>,
+    }
+
+    /// a unit cube split into one of each supported cell type
+    fn setup_mesh<T>() -> UnstructuredMesh<f64, T> {
+        let points = vec![
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [1., 1., 0.],
+            [0., 1., 0.],
+            [0., 0., 1.],
+            [1., 0., 1.],
+            [1., 1., 1.],
+            [0., 1., 1.],
+            [0.5, 0.5, 2.],
+        ];
+
+        let mut mesh = UnstructuredMesh::new(points, vec![], vec![], vec![]);
+
+        mesh.push_cell(CellType::Hexahedron, &[0, 1, 2, 3, 4, 5, 6, 7]);
+        mesh.push_cell(CellType::Tetra, &[4, 5, 7, 8]);
+        mesh.push_cell(CellType::Wedge, &[0, 1, 3, 4, 5, 7]);
+        mesh.push_cell(CellType::Quad, &[0, 1, 2, 3]);
+        mesh.push_cell(CellType::Triangle, &[4, 5, 8]);
+        mesh.push_cell(CellType::Line, &[6, 8]);
+        mesh.push_cell(CellType::Vertex, &[8]);
+
+        mesh
+    }
+
+    fn temperature() -> Vec<f64> {
+        (0..9).map(|i| i as f64 + 1.).collect()
+    }
+
+    #[test]
+    fn write_unstructured() {
+        let domain = Unstructured::new(setup_mesh::<vtk::Binary>());
+        let vtk = VtkData::new(
+            domain,
+            Binary {
+                temperature: temperature(),
+            },
+        );
+
+        let file = std::fs::File::create("./test_vtks/unstructured.vtu").unwrap();
+        vtk::write_vtk(file, vtk).unwrap();
+    }
+
+    #[test]
+    fn writes_cells() {
+        let domain = Unstructured::new(setup_mesh::<vtk::Ascii>());
+        let vtk = VtkData::new(
+            domain,
+            Ascii {
+                temperature: temperature(),
+            },
+        );
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8_lossy(&file);
+
+        assert!(file.contains(r#"<VTKFile type="UnstructuredGrid""#));
+        assert!(file.contains(r#"<Piece NumberOfPoints="9" NumberOfCells="7">"#));
+        assert!(file.contains(r#"type="Int64" NumberOfComponents="1" Name="connectivity""#));
+        assert!(file.contains(r#"type="UInt8" NumberOfComponents="1" Name="types""#));
+        assert!(file.contains("12 10 13 9 5 3 1"));
+        assert!(!file.contains("WholeExtent"));
+    }
+
+    #[test]
+    fn read_binary_after_write() {
+        let domain = Unstructured::new(setup_mesh::<vtk::Binary>());
+        let vtk = VtkData::new(
+            domain,
+            Binary {
+                temperature: temperature(),
+            },
+        );
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Unstructured<f64, vtk::Binary>, Binary> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn read_base64_after_write() {
+        let domain = Unstructured::new(setup_mesh::<vtk::Base64>());
+        let vtk = VtkData::new(
+            domain,
+            Base64 {
+                temperature: temperature(),
+            },
+        );
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Unstructured<f64, vtk::Binary>, Base64> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.domain.spans, expected.domain.spans);
+        assert!(out.domain.mesh == expected.domain.mesh);
+        assert_eq!(out.data, expected.data);
+    }
+
+    #[test]
+    fn read_ascii_after_write() {
+        let domain = Unstructured::new(setup_mesh::<vtk::Ascii>());
+        let vtk = VtkData::new(
+            domain,
+            Ascii {
+                temperature: temperature(),
+            },
+        );
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Unstructured<f64, vtk::Binary>, Ascii> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out.domain.spans, expected.domain.spans);
+        assert!(out.domain.mesh == expected.domain.mesh);
+        assert_eq!(out.data, expected.data);
+    }
+
+    #[test]
+    fn read_vector_arrays_after_write() {
+        let domain = Unstructured::new(setup_mesh::<vtk::Binary>());
+        let vtk = VtkData::new(
+            domain,
+            Vectors {
+                velocity: (0..9).map(|i| [i as f64, 0.5, -1.]).collect(),
+                normal: (0..7).map(|i| [0., 0., i as f32]).collect(),
+            },
+        );
+        let expected = vtk.clone();
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+
+        let out: VtkData<Unstructured<f64, vtk::Binary>, Vectors> =
+            vtk::parse::parse_xml_document(&file).unwrap();
+
+        assert_eq!(out, expected);
+    }
+
+    /// an ascii file with `from` replaced by `to`, which is expected to be inconsistent
+    fn length_error(from: &str, to: &str) -> (String, usize, usize) {
+        let domain = Unstructured::new(setup_mesh::<vtk::Ascii>());
+        let vtk = VtkData::new(
+            domain,
+            Ascii {
+                temperature: temperature(),
+            },
+        );
+
+        let mut file = Vec::new();
+        vtk::write_vtk(&mut file, vtk).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        assert!(file.contains(from));
+        let file = file.replace(from, to);
+
+        let out: Result<VtkData<Unstructured<f64, vtk::Binary>, Ascii>, _> =
+            vtk::parse::parse_xml_document(file.as_bytes());
+
+        match out {
+            Err(vtk::Error::Nom(vtk::ParseError::ArrayLength {
+                array,
+                expected,
+                found,
+            })) => (array, expected, found),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn inconsistent_number_of_points() {
+        let error = length_error(r#"NumberOfPoints="9""#, r#"NumberOfPoints="8""#);
+        assert_eq!(error, ("Points".to_string(), 24, 27));
+    }
+
+    #[test]
+    fn inconsistent_number_of_cells() {
+        let error = length_error(r#"NumberOfCells="7""#, r#"NumberOfCells="8""#);
+        assert_eq!(error, ("offsets".to_string(), 8, 7));
+    }
+
+    #[test]
+    fn truncated_connectivity() {
+        // the last cell ends past the end of the connectivity array
+        let error = length_error("25 27 28 ", "25 27 29 ");
+        assert_eq!(error, ("connectivity".to_string(), 29, 28));
+    }
+}